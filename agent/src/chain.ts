@@ -59,6 +59,36 @@ export interface RoundState {
   bump: number;
 }
 
+export interface GenerationState {
+  arena: PublicKey;
+  generationNumber: number;
+  roundsRun: number;
+  agentsBorn: number;
+  genomesSubmitted: number;
+  agentsPruned: number;
+  bestFitness: number;
+  averageFitness: number;
+  champion: PublicKey;
+  championGenomeHash: number[];
+  previousHash: number[];
+  summaryHash: number[];
+  closedAt: number;
+  bump: number;
+}
+
+export interface FitnessSimulation {
+  fitness: number;
+  breakdown: {
+    proximity: number;
+    diversity: number;
+    balance: number;
+    pattern: number;
+    sequence: number;
+    novelty: number;
+  };
+  novelty: number;
+}
+
 export class ChainInteractor {
   private connection: Connection;
   private provider: AnchorProvider;
@@ -131,6 +161,29 @@ export class ChainInteractor {
     );
   }
 
+  getPopulationPda(arenaKey: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("population"), arenaKey.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  getHallOfFamePda(arenaKey: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("hall_of_fame"), arenaKey.toBuffer()],
+      this.program.programId
+    );
+  }
+
+  getGenerationPda(arenaKey: PublicKey, generationNumber: number): [PublicKey, number] {
+    const genBuf = Buffer.alloc(8);
+    genBuf.writeBigUInt64LE(BigInt(generationNumber));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("generation"), arenaKey.toBuffer(), genBuf],
+      this.program.programId
+    );
+  }
+
   async initializeArena(
     maxAgents: number,
     minFitnessThreshold: number,
@@ -147,6 +200,7 @@ export class ChainInteractor {
       )
       .accounts({
         arena: arenaPda,
+        population: this.getPopulationPda(arenaPda)[0],
         authority: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       } as any)
@@ -166,6 +220,7 @@ export class ChainInteractor {
       .registerAgent(Buffer.from(genome))
       .accounts({
         arena: arenaPda,
+        population: this.getPopulationPda(arenaPda)[0],
         agent: agentPda,
         owner: this.wallet.publicKey,
         systemProgram: SystemProgram.programId,
@@ -221,13 +276,16 @@ export class ChainInteractor {
   ): Promise<string> {
     const [arenaPda] = this.getArenaPda();
     const [roundPda] = this.getRoundPda(arenaPda, roundNumber);
+    const arena = await (this.program.account as any).arena.fetch(arenaPda);
 
     const tx = await this.program.methods
       .scoreAgent()
       .accounts({
         arena: arenaPda,
+        population: this.getPopulationPda(arenaPda)[0],
         round: roundPda,
         agent: agentPda,
+        hallOfFame: arena.hallOfFameEnabled ? this.getHallOfFamePda(arenaPda)[0] : null,
         authority: this.wallet.publicKey,
       } as any)
       .rpc();
@@ -235,6 +293,34 @@ export class ChainInteractor {
     return tx;
   }
 
+  /** Score a candidate genome against a round without sending a transaction. */
+  async simulateFitness(genome: Buffer, roundNumber: number): Promise<FitnessSimulation> {
+    const [arenaPda] = this.getArenaPda();
+    const [roundPda] = this.getRoundPda(arenaPda, roundNumber);
+
+    const result = await this.program.methods
+      .simulateFitness(genome, new anchor.BN(roundNumber))
+      .accounts({
+        arena: arenaPda,
+        round: roundPda,
+      } as any)
+      .view();
+
+    const b = result.breakdown;
+    return {
+      fitness: result.fitness.toNumber(),
+      breakdown: {
+        proximity: b.proximity.toNumber(),
+        diversity: b.diversity.toNumber(),
+        balance: b.balance.toNumber(),
+        pattern: b.pattern.toNumber(),
+        sequence: b.sequence.toNumber(),
+        novelty: b.novelty.toNumber(),
+      },
+      novelty: result.novelty.toNumber(),
+    };
+  }
+
   async completeRound(roundNumber: number): Promise<string> {
     const [arenaPda] = this.getArenaPda();
     const [roundPda] = this.getRoundPda(arenaPda, roundNumber);
@@ -244,6 +330,7 @@ export class ChainInteractor {
       .accounts({
         arena: arenaPda,
         round: roundPda,
+        population: this.getPopulationPda(arenaPda)[0],
         authority: this.wallet.publicKey,
       } as any)
       .rpc();
@@ -254,12 +341,18 @@ export class ChainInteractor {
 
   async pruneAgent(agentPda: PublicKey): Promise<string> {
     const [arenaPda] = this.getArenaPda();
+    const arena = await (this.program.account as any).arena.fetch(arenaPda);
+    const agent = await (this.program.account as any).agentAccount.fetch(agentPda);
+    const currentRound = arena.currentRound.toNumber();
 
     const tx = await this.program.methods
       .pruneAgent()
       .accounts({
         arena: arenaPda,
+        population: this.getPopulationPda(arenaPda)[0],
         agent: agentPda,
+        round: currentRound > 0 ? this.getRoundPda(arenaPda, currentRound)[0] : null,
+        owner: agent.owner,
         authority: this.wallet.publicKey,
       } as any)
       .rpc();
@@ -269,12 +362,19 @@ export class ChainInteractor {
 
   async advanceGeneration(): Promise<string> {
     const [arenaPda] = this.getArenaPda();
+    const arena = await (this.program.account as any).arena.fetch(arenaPda);
+    const [generationPda] = this.getGenerationPda(
+      arenaPda,
+      arena.currentGeneration.toNumber()
+    );
 
     const tx = await this.program.methods
       .advanceGeneration()
       .accounts({
         arena: arenaPda,
+        generation: generationPda,
         authority: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
 
@@ -353,6 +453,32 @@ export class ChainInteractor {
     }
   }
 
+  async getGeneration(generationNumber: number): Promise<GenerationState | null> {
+    try {
+      const [arenaPda] = this.getArenaPda();
+      const [generationPda] = this.getGenerationPda(arenaPda, generationNumber);
+      const g = await (this.program.account as any).generation.fetch(generationPda);
+      return {
+        arena: g.arena,
+        generationNumber: g.generationNumber.toNumber(),
+        roundsRun: g.roundsRun.toNumber(),
+        agentsBorn: g.agentsBorn.toNumber(),
+        genomesSubmitted: g.genomesSubmitted.toNumber(),
+        agentsPruned: g.agentsPruned.toNumber(),
+        bestFitness: g.bestFitness.toNumber(),
+        averageFitness: g.averageFitness.toNumber(),
+        champion: g.champion,
+        championGenomeHash: Array.from(g.championGenomeHash),
+        previousHash: Array.from(g.previousHash),
+        summaryHash: Array.from(g.summaryHash),
+        closedAt: g.closedAt.toNumber(),
+        bump: g.bump,
+      };
+    } catch {
+      return null;
+    }
+  }
+
   async getAllAgents(): Promise<AgentState[]> {
     const accounts = await (this.program.account as any).agentAccount.all();
     return accounts.map((a: any) => ({
@@ -0,0 +1,112 @@
+import { createHash } from "crypto";
+
+/**
+ * Off-chain port of the program's `compute_fitness` (scorer version 2).
+ *
+ * Must agree with the on-chain scorer bit for bit; it is checked against
+ * the golden vectors in tests/vectors/fitness.json, which are generated
+ * from the Rust implementation by `cargo run -p saea-vectors`.
+ */
+
+export const FITNESS_VERSION = 2;
+export const MAX_GENOME_LEN = 32;
+
+export interface FitnessBreakdown {
+  proximity: number;
+  diversity: number;
+  balance: number;
+  pattern: number;
+  sequence: number;
+  novelty: number;
+}
+
+export interface FitnessTargets {
+  geneTargets: number[];
+  ascendingPairs: boolean[];
+}
+
+function hashv(...parts: Buffer[]): Buffer {
+  const hash = createHash("sha256");
+  for (const part of parts) {
+    hash.update(part);
+  }
+  return hash.digest();
+}
+
+export function deriveTargets(seed: Buffer): FitnessTargets {
+  const geneTargets: number[] = [];
+  for (let i = 0; i < MAX_GENOME_LEN; i++) {
+    geneTargets.push(hashv(seed, Buffer.from([i]))[0]);
+  }
+  const ascendingPairs: boolean[] = [];
+  for (let i = 0; i < MAX_GENOME_LEN - 1; i++) {
+    const direction = hashv(seed, Buffer.from([i]), Buffer.from([0xff]))[0];
+    ascendingPairs.push(direction > 127);
+  }
+  return { geneTargets, ascendingPairs };
+}
+
+export function computeFitnessBreakdown(
+  genome: Buffer,
+  seed: Buffer,
+  targets: FitnessTargets = deriveTargets(seed)
+): FitnessBreakdown {
+  const breakdown: FitnessBreakdown = {
+    proximity: 0,
+    diversity: 0,
+    balance: 0,
+    pattern: 0,
+    sequence: 0,
+    novelty: 0,
+  };
+  const length = genome.length;
+  if (length === 0) {
+    return breakdown;
+  }
+
+  // Target proximity
+  for (let i = 0; i < Math.min(length, MAX_GENOME_LEN); i++) {
+    breakdown.proximity += 255 - Math.abs(genome[i] - targets.geneTargets[i]);
+  }
+
+  // Diversity bonus
+  const total = genome.reduce((sum, gene) => sum + gene, 0);
+  const mean = Math.floor(total / length);
+  const squares = genome.reduce(
+    (sum, gene) => sum + (gene - mean) * (gene - mean),
+    0
+  );
+  const variance = Math.floor(squares / length);
+  breakdown.diversity = Math.min(Math.floor(variance / 10), 500);
+
+  // Balance bonus
+  const maxBalanceDiff = 128 * length;
+  const balanceDiff = Math.abs(total - maxBalanceDiff);
+  breakdown.balance = Math.floor(
+    (500 * (maxBalanceDiff - balanceDiff)) / maxBalanceDiff
+  );
+
+  // Pattern bonus
+  for (let i = 0; i < length; i++) {
+    let bits = genome[i] ^ seed[i % 32];
+    while (bits) {
+      breakdown.pattern += (bits & 1) * 10;
+      bits >>= 1;
+    }
+  }
+
+  // Sequence bonus
+  for (let i = 0; i < Math.min(length - 1, MAX_GENOME_LEN - 1); i++) {
+    const isAscending = genome[i + 1] >= genome[i];
+    if (targets.ascendingPairs[i] === isAscending) {
+      breakdown.sequence += Math.min(Math.abs(genome[i + 1] - genome[i]), 30);
+    }
+  }
+
+  return breakdown;
+}
+
+export function computeFitness(genome: Buffer, seed: Buffer): number {
+  const b = computeFitnessBreakdown(genome, seed);
+  return b.proximity + b.diversity + b.balance + b.pattern + b.sequence;
+}
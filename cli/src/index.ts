@@ -179,32 +179,37 @@ program
 
     const table = new Table({
       head: [
-        chalk.white("Round"),
         chalk.white("Gen"),
-        chalk.white("Participants"),
+        chalk.white("Rounds"),
+        chalk.white("Born"),
+        chalk.white("Mutated"),
+        chalk.white("Pruned"),
         chalk.white("Best"),
         chalk.white("Average"),
-        chalk.white("Worst"),
-        chalk.white("Complete"),
+        chalk.white("Champion"),
+        chalk.white("Summary Hash"),
       ],
     });
 
-    for (let r = 1; r <= arena.currentRound; r++) {
-      const round = await chain.getRound(r);
-      if (round) {
+    for (let g = 1; g < arena.currentGeneration; g++) {
+      const gen = await chain.getGeneration(g);
+      if (gen) {
         table.push([
-          round.roundNumber,
-          round.generation,
-          round.participants,
-          round.bestFitness,
-          round.averageFitness,
-          round.worstFitness,
-          round.isComplete ? chalk.green("Yes") : chalk.yellow("No"),
+          gen.generationNumber,
+          gen.roundsRun,
+          gen.agentsBorn,
+          gen.genomesSubmitted,
+          gen.agentsPruned,
+          gen.bestFitness,
+          gen.averageFitness,
+          gen.champion.toBase58().slice(0, 16) + "...",
+          Buffer.from(gen.summaryHash).toString("hex").slice(0, 16) + "...",
         ]);
       }
     }
 
     console.log(table.toString());
+    console.log(`\nCurrent generation: ${arena.currentGeneration} (in progress)`);
   });
 
 program.parse(process.argv);
@@ -0,0 +1,129 @@
+use saea_sim::genome::SelectionStrategy;
+use std::env;
+use std::str::FromStr;
+
+/// How the offchain mutation rate moves between generations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MutationStrategy {
+    /// Raise on stagnation, lower on improvement.
+    Adaptive,
+    /// Keep `mutation_rate_bps` for the whole run.
+    Fixed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl FromStr for MutationStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "adaptive" => Ok(Self::Adaptive),
+            "fixed" => Ok(Self::Fixed),
+            _ => Err(format!("unknown mutation strategy: {s}")),
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("unknown log level: {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AgentConfig {
+    pub rpc_url: String,
+    pub wallet_path: String,
+    pub state_path: String,
+    pub population_size: usize,
+    pub genome_len: usize,
+    pub generations_to_run: u64,
+    pub rounds_per_generation: u64,
+    pub mutation_rate_bps: u16,
+    pub min_fitness_threshold: u64,
+    pub max_agents: u64,
+    pub elitism_count: usize,
+    pub tournament_size: usize,
+    pub crossover_rate: f64,
+    pub selection: SelectionStrategy,
+    pub mutation: MutationStrategy,
+    pub offspring_candidates: usize, // children bred per slot, best kept by local fitness
+    pub log_level: LogLevel,
+}
+
+impl Default for AgentConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: "http://localhost:8899".to_string(),
+            wallet_path: format!(
+                "{}/.config/solana/id.json",
+                env::var("HOME").unwrap_or_default()
+            ),
+            state_path: "agent-state.json".to_string(),
+            population_size: 8,
+            genome_len: 16,
+            generations_to_run: 5,
+            rounds_per_generation: 2,
+            mutation_rate_bps: 3000,
+            min_fitness_threshold: 500,
+            max_agents: 64,
+            elitism_count: 2,
+            tournament_size: 3,
+            crossover_rate: 0.7,
+            selection: SelectionStrategy::Tournament,
+            mutation: MutationStrategy::Adaptive,
+            offspring_candidates: 1,
+            log_level: LogLevel::Info,
+        }
+    }
+}
+
+fn override_from_env<T: FromStr>(field: &mut T, name: &str) -> Result<(), String>
+where
+    T::Err: std::fmt::Display,
+{
+    if let Ok(value) = env::var(name) {
+        *field = value
+            .parse()
+            .map_err(|e| format!("invalid {name}={value}: {e}"))?;
+    }
+    Ok(())
+}
+
+impl AgentConfig {
+    /// Defaults overridden by the environment variables documented in the
+    /// README, plus `STATE_PATH`, `SELECTION`, `MUTATION` and
+    /// `OFFSPRING_CANDIDATES` for the Rust agent.
+    pub fn from_env() -> Result<Self, String> {
+        let mut config = Self::default();
+        override_from_env(&mut config.rpc_url, "RPC_URL")?;
+        override_from_env(&mut config.wallet_path, "WALLET_PATH")?;
+        override_from_env(&mut config.state_path, "STATE_PATH")?;
+        override_from_env(&mut config.generations_to_run, "GENERATIONS")?;
+        override_from_env(&mut config.population_size, "POPULATION_SIZE")?;
+        override_from_env(&mut config.rounds_per_generation, "ROUNDS_PER_GEN")?;
+        override_from_env(&mut config.selection, "SELECTION")?;
+        override_from_env(&mut config.mutation, "MUTATION")?;
+        override_from_env(&mut config.offspring_candidates, "OFFSPRING_CANDIDATES")?;
+        override_from_env(&mut config.log_level, "LOG_LEVEL")?;
+        if config.offspring_candidates == 0 {
+            return Err("OFFSPRING_CANDIDATES must be at least 1".to_string());
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,42 @@
+use saea_client::ClientError;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AgentError {
+    /// An environment variable or the wallet could not be read.
+    Config(String),
+    /// The RPC node could not be reached or returned an error.
+    Rpc(String),
+    /// A transaction failed or was not confirmed in time.
+    Transaction(String),
+    /// The resume file could not be read or written.
+    State(String),
+    /// A program account could not be fetched or decoded.
+    Client(ClientError),
+    /// The arena is configured in a way this run cannot drive.
+    Arena(String),
+    /// A genome could not be scored under the arena's fitness mode.
+    Evaluation(String),
+}
+
+impl fmt::Display for AgentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgentError::Config(e) => write!(f, "config: {e}"),
+            AgentError::Rpc(e) => write!(f, "rpc: {e}"),
+            AgentError::Transaction(e) => write!(f, "transaction: {e}"),
+            AgentError::State(e) => write!(f, "state: {e}"),
+            AgentError::Client(e) => write!(f, "{e}"),
+            AgentError::Arena(e) => write!(f, "arena: {e}"),
+            AgentError::Evaluation(e) => write!(f, "evaluation: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AgentError {}
+
+impl From<ClientError> for AgentError {
+    fn from(e: ClientError) -> Self {
+        AgentError::Client(e)
+    }
+}
@@ -0,0 +1,588 @@
+//! The evolution loop of `agent/src/evolution.ts`: register a population,
+//! run scored rounds, prune, breed and submit children, advance.
+
+use crate::config::{AgentConfig, MutationStrategy};
+use crate::error::AgentError;
+use crate::logger::Logger;
+use crate::rpc::RpcClient;
+use crate::state::{AgentState, GenerationReport};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use rand::rngs::ThreadRng;
+use saea::instructions::evaluate_genes;
+use saea::state::{
+    AgentAccount, Arena, Benchmark, CompletionPolicy, FitnessMode, NoveltyArchive, Round,
+};
+use saea_client::accounts::{fetch, fetch_agent, fetch_arena, fetch_population, fetch_round};
+use saea_client::instructions::{self as ix, ScoreAccounts};
+use saea_client::{pda, ClientError};
+use saea_sim::genome::{
+    adapt_mutation_rate, crossover, genome_hash, mutate_genome, random_genome, select, Candidate,
+};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const AIRDROP_LAMPORTS: u64 = 20_000_000_000;
+
+fn short(key: &Pubkey) -> String {
+    key.to_string()[..8].to_string()
+}
+
+/// The arena, latest round and fitness-mode accounts children are scored
+/// against, as `score_agent` would score them.
+struct Evaluation {
+    arena: Arena,
+    round: Round,
+    benchmark: Option<Benchmark>,
+    novelty_archive: Option<NoveltyArchive>,
+}
+
+impl Evaluation {
+    fn predict(&self, genome: &[u8]) -> Result<u64, AgentError> {
+        evaluate_genes(
+            &self.round,
+            self.arena.object_genes(genome),
+            self.benchmark.as_ref(),
+            self.novelty_archive.as_ref(),
+        )
+        .map(|simulation| simulation.fitness)
+        .map_err(|e| AgentError::Evaluation(e.to_string()))
+    }
+}
+
+pub struct EvolutionEngine {
+    rpc: RpcClient,
+    wallet: Keypair,
+    config: AgentConfig,
+    logger: Logger,
+    state: AgentState,
+    state_path: PathBuf,
+    rng: ThreadRng,
+}
+
+impl EvolutionEngine {
+    pub fn new(rpc: RpcClient, wallet: Keypair, config: AgentConfig, logger: Logger) -> Self {
+        let state_path = PathBuf::from(&config.state_path);
+        let state = AgentState {
+            mutation_rate_bps: config.mutation_rate_bps,
+            ..AgentState::default()
+        };
+        Self {
+            rpc,
+            wallet,
+            config,
+            logger,
+            state,
+            state_path,
+            rng: rand::thread_rng(),
+        }
+    }
+
+    pub fn reports(&self) -> &[GenerationReport] {
+        &self.state.reports
+    }
+
+    fn send(&self, instruction: Instruction) -> Result<String, AgentError> {
+        self.rpc.send_and_confirm(&[instruction], &self.wallet)
+    }
+
+    fn authority(&self) -> Pubkey {
+        self.wallet.pubkey()
+    }
+
+    fn agent_keys(&self) -> Result<Vec<Pubkey>, AgentError> {
+        self.state
+            .agents
+            .iter()
+            .map(|k| Pubkey::from_str(k).map_err(|e| AgentError::State(format!("{k}: {e}"))))
+            .collect()
+    }
+
+    /// Active agents owned by this run, sorted by descending selection
+    /// fitness under `arena`'s metric.
+    fn active_agents(&self, arena: &Arena) -> Result<Vec<(Pubkey, AgentAccount)>, AgentError> {
+        let mut agents = Vec::new();
+        for key in self.agent_keys()? {
+            let agent = fetch_agent(&self.rpc, &key)?;
+            if agent.is_active {
+                agents.push((key, agent));
+            }
+        }
+        agents.sort_by_key(|(_, a)| std::cmp::Reverse(a.selection_fitness(arena)));
+        Ok(agents)
+    }
+
+    /// Under the Strict completion policy a round only completes once every
+    /// eligible agent is scored, and this run scores only its own; stop
+    /// before opening a round that could never complete.
+    fn check_completion_policy(&self, arena: &Arena) -> Result<(), AgentError> {
+        if arena.completion_policy != CompletionPolicy::Strict {
+            return Ok(());
+        }
+        let own = self.agent_keys()?;
+        let foreign = fetch_population(&self.rpc)?
+            .entries
+            .iter()
+            .filter(|e| !own.contains(&e.agent))
+            .count();
+        if foreign > 0 {
+            return Err(AgentError::Arena(format!(
+                "the Strict completion policy needs every active agent scored, but {foreign} \
+                 belong to other owners; configure a Quorum or Lenient policy to share the arena"
+            )));
+        }
+        Ok(())
+    }
+
+    fn save(&self) -> Result<(), AgentError> {
+        self.state.save(&self.state_path)
+    }
+
+    pub fn initialize(&mut self) -> Result<(), AgentError> {
+        let c = &self.config;
+        self.logger.info("=== PHASE 1: PLANNING ===");
+        self.logger
+            .info(&format!("Population size: {}", c.population_size));
+        self.logger
+            .info(&format!("Genome length: {}", c.genome_len));
+        self.logger
+            .info(&format!("Generations to run: {}", c.generations_to_run));
+        self.logger.info(&format!(
+            "Rounds per generation: {}",
+            c.rounds_per_generation
+        ));
+        self.logger.info(&format!(
+            "Selection: {:?}, mutation: {:?}",
+            c.selection, c.mutation
+        ));
+
+        let resumed = match AgentState::load(&self.state_path)? {
+            Some(state) => {
+                self.logger.info(&format!(
+                    "Resuming from {:?}: {} agents, {} generations and {} rounds completed",
+                    self.state_path,
+                    state.agents.len(),
+                    state.generations_completed,
+                    state.rounds_completed
+                ));
+                self.state = state;
+                true
+            }
+            None => false,
+        };
+
+        if let Err(e) = self
+            .rpc
+            .request_airdrop(&self.authority(), AIRDROP_LAMPORTS)
+        {
+            self.logger
+                .warn(&format!("Airdrop failed (may already have SOL): {e}"));
+        }
+
+        let arena = match fetch_arena(&self.rpc) {
+            Ok(arena) => {
+                self.logger.info("Arena already exists, reusing...");
+                arena
+            }
+            Err(ClientError::AccountNotFound(_)) => {
+                self.send(ix::initialize_arena(
+                    self.authority(),
+                    self.config.max_agents,
+                    self.config.min_fitness_threshold,
+                    self.config.mutation_rate_bps,
+                ))?;
+                self.logger.info("Arena initialized successfully");
+                fetch_arena(&self.rpc)?
+            }
+            Err(e) => return Err(e.into()),
+        };
+        if resumed {
+            self.reconcile(&arena)
+        } else {
+            self.state.arena_generation = arena.current_generation;
+            Ok(())
+        }
+    }
+
+    /// Count generations the arena advanced after the last save as
+    /// completed, so a run stopped between `advance_generation` and saving
+    /// does not repeat one.
+    fn reconcile(&mut self, arena: &Arena) -> Result<(), AgentError> {
+        let advanced = arena
+            .current_generation
+            .saturating_sub(self.state.arena_generation);
+        if advanced == 0 {
+            return Ok(());
+        }
+        self.logger.warn(&format!(
+            "Arena advanced {advanced} generation(s) since the last save; counting them as completed"
+        ));
+        self.state.generations_completed += advanced;
+        self.state.rounds_completed = 0;
+        self.state.arena_generation = arena.current_generation;
+        self.save()
+    }
+
+    /// Register agents until this run owns `population_size` of them,
+    /// saving after each so an interrupted registration is not repeated.
+    pub fn register_population(&mut self) -> Result<(), AgentError> {
+        if self.state.agents.len() >= self.config.population_size {
+            return Ok(());
+        }
+        self.logger
+            .info("=== PHASE 2: REGISTERING INITIAL POPULATION ===");
+        let arena_key = pda::arena().0;
+        while self.state.agents.len() < self.config.population_size {
+            let index = fetch_arena(&self.rpc)?.total_agents;
+            let genome = random_genome(&mut self.rng, self.config.genome_len);
+            let hash = genome_hash(&genome);
+            self.send(ix::register_agent(self.authority(), index, genome))?;
+            let agent = pda::agent(&arena_key, &self.authority(), index).0;
+            self.state.agents.push(agent.to_string());
+            self.save()?;
+            self.logger.info(&format!(
+                "Agent {} registered: {}... genome={}...",
+                self.state.agents.len() - 1,
+                short(&agent),
+                &hash[..12]
+            ));
+        }
+        Ok(())
+    }
+
+    /// Score the remaining agents of a round left open by an interrupted
+    /// run and complete it.
+    fn finish_open_round(&mut self) -> Result<(), AgentError> {
+        let arena = fetch_arena(&self.rpc)?;
+        if arena.current_round == 0 {
+            return Ok(());
+        }
+        let round = fetch_round(&self.rpc, arena.current_round)?;
+        if round.is_complete {
+            return Ok(());
+        }
+        self.logger.info(&format!(
+            "Finishing round {} left open by a previous run",
+            round.round_number
+        ));
+        self.score_and_complete(round.round_number)
+    }
+
+    /// Score this run's agents in `round_number`, complete it and save, so
+    /// a resumed run does not repeat it.
+    fn score_and_complete(&mut self, round_number: u64) -> Result<(), AgentError> {
+        let arena = fetch_arena(&self.rpc)?;
+        let round = fetch_round(&self.rpc, round_number)?;
+        let optional = ScoreAccounts::required(&arena, &round);
+        for key in self.agent_keys()? {
+            let agent = fetch_agent(&self.rpc, &key)?;
+            if !agent.is_active || agent.last_round >= round_number {
+                continue;
+            }
+            self.send(ix::score_agent(
+                self.authority(),
+                round_number,
+                key,
+                optional,
+            ))?;
+        }
+        self.send(ix::complete_round(self.authority(), round_number))?;
+        let round = fetch_round(&self.rpc, round_number)?;
+        self.logger.info(&format!(
+            "Round {round_number} results: best={}, avg={}, participants={}",
+            round.best_fitness, round.average_fitness, round.participants
+        ));
+        self.state.rounds_completed += 1;
+        self.save()
+    }
+
+    /// Best of `offspring_candidates` children of two selected parents,
+    /// ranked by the program's own evaluation of their object genes.
+    fn breed(
+        &mut self,
+        population: &[Candidate],
+        evaluation: &Evaluation,
+    ) -> Result<(Vec<u8>, u64), AgentError> {
+        let mut best: Option<(Vec<u8>, u64)> = None;
+        for _ in 0..self.config.offspring_candidates {
+            let parent1 = select(
+                &mut self.rng,
+                population,
+                self.config.selection,
+                self.config.tournament_size,
+            );
+            let parent2 = select(
+                &mut self.rng,
+                population,
+                self.config.selection,
+                self.config.tournament_size,
+            );
+            let child = crossover(
+                &mut self.rng,
+                &parent1.genome,
+                &parent2.genome,
+                self.config.crossover_rate,
+            );
+            let child = mutate_genome(&mut self.rng, &child, self.state.mutation_rate_bps);
+            let predicted = evaluation.predict(&child)?;
+            self.logger
+                .debug(&format!("Candidate child predicted fitness {predicted}"));
+            if best.as_ref().is_none_or(|(_, f)| predicted > *f) {
+                best = Some((child, predicted));
+            }
+        }
+        Ok(best.unwrap())
+    }
+
+    /// Everything `breed` scores children against: the latest round and
+    /// the accounts its fitness mode reads.
+    fn evaluation(&self, arena: Arena) -> Result<Evaluation, AgentError> {
+        let arena_key = pda::arena().0;
+        let round = fetch_round(&self.rpc, arena.current_round)?;
+        let benchmark = (round.fitness_mode == FitnessMode::Benchmark)
+            .then(|| fetch(&self.rpc, &pda::benchmark(&arena_key).0))
+            .transpose()?;
+        let novelty_archive = (round.fitness_mode == FitnessMode::Novelty)
+            .then(|| fetch(&self.rpc, &pda::novelty_archive(&arena_key).0))
+            .transpose()?;
+        Ok(Evaluation {
+            arena,
+            round,
+            benchmark,
+            novelty_archive,
+        })
+    }
+
+    pub fn run_generation(&mut self, generation: u64) -> Result<GenerationReport, AgentError> {
+        let mut report = GenerationReport {
+            generation,
+            mutation_rate: self.state.mutation_rate_bps,
+            ..GenerationReport::default()
+        };
+        let mut decisions = vec![format!("Starting generation {generation}")];
+        self.logger
+            .info(&format!("\n=== GENERATION {generation} ==="));
+
+        self.check_completion_policy(&fetch_arena(&self.rpc)?)?;
+        self.finish_open_round()?;
+        while self.state.rounds_completed < self.config.rounds_per_generation {
+            let round_number = fetch_arena(&self.rpc)?.current_round + 1;
+            self.logger.info(&format!("--- Round {round_number} ---"));
+            self.send(ix::run_round(self.authority(), round_number))?;
+            self.score_and_complete(round_number)?;
+        }
+        report.rounds_run = self.state.rounds_completed;
+
+        let mut arena = fetch_arena(&self.rpc)?;
+        let agents = self.active_agents(&arena)?;
+        if agents.is_empty() {
+            self.logger.warn("No active agents found!");
+            report.decisions = decisions;
+            return Ok(report);
+        }
+
+        let total: u64 = agents.iter().map(|(_, a)| a.fitness).sum();
+        let (_, best) = agents.iter().max_by_key(|(_, a)| a.fitness).unwrap();
+        report.best_fitness = best.fitness;
+        report.worst_fitness = agents.iter().map(|(_, a)| a.fitness).min().unwrap();
+        report.average_fitness = total / agents.len() as u64;
+        report.best_genome_hash = genome_hash(&best.genome);
+
+        self.logger
+            .info(&format!("\nGeneration {generation} Summary:"));
+        self.logger
+            .info(&format!("  Best fitness:  {}", report.best_fitness));
+        self.logger
+            .info(&format!("  Avg fitness:   {}", report.average_fitness));
+        self.logger
+            .info(&format!("  Worst fitness: {}", report.worst_fitness));
+        self.logger
+            .info(&format!("  Active agents: {}", agents.len()));
+
+        // Adapt the offchain mutation rate to the fitness trend.
+        let old_rate = self.state.mutation_rate_bps;
+        if self.config.mutation == MutationStrategy::Adaptive {
+            self.state.mutation_rate_bps = adapt_mutation_rate(
+                old_rate,
+                self.state.previous_best_fitness,
+                report.best_fitness,
+            );
+        }
+        if self.state.mutation_rate_bps != old_rate {
+            let direction = if self.state.mutation_rate_bps > old_rate {
+                "increased"
+            } else {
+                "decreased"
+            };
+            decisions.push(format!(
+                "Mutation rate {direction} from {old_rate} to {} bps (prev best: {}, curr best: {})",
+                self.state.mutation_rate_bps, self.state.previous_best_fitness, report.best_fitness
+            ));
+            self.logger.info(&format!(
+                "  Mutation rate {direction}: {old_rate} -> {} bps",
+                self.state.mutation_rate_bps
+            ));
+        }
+        report.mutation_rate = self.state.mutation_rate_bps;
+        self.state.previous_best_fitness = report.best_fitness;
+
+        let elitism = self.config.elitism_count.min(agents.len());
+        let champion = agents[0].0;
+        decisions.push(format!(
+            "Elitism: preserving top {elitism} agents (selection fitness: {})",
+            agents[..elitism]
+                .iter()
+                .map(|(_, a)| a.selection_fitness(&arena).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        // Prune as the program would allow: by the arena's selection rule,
+        // never emptying an island.
+        let current_round = (arena.current_round > 0).then_some(arena.current_round);
+        let mut pruned = Vec::new();
+        for (key, agent) in &agents {
+            if !agent.is_prunable(&arena) {
+                continue;
+            }
+            let island = agent.island as usize;
+            if arena.island_count > 1 && arena.island_populations[island] <= 1 {
+                decisions.push(format!(
+                    "Kept agent {} as the last on island {island}",
+                    short(key)
+                ));
+                continue;
+            }
+            self.send(ix::prune_agent(
+                self.authority(),
+                *key,
+                agent.owner,
+                current_round,
+            ))?;
+            arena.island_populations[island] -= 1;
+            report.agents_pruned += 1;
+            pruned.push(*key);
+            decisions.push(format!(
+                "Pruned agent {} (selection fitness: {})",
+                short(key),
+                agent.selection_fitness(&arena)
+            ));
+        }
+
+        let population: Vec<Candidate> = agents
+            .iter()
+            .map(|(_, a)| Candidate {
+                genome: a.genome.clone(),
+                fitness: a.selection_fitness(&arena),
+            })
+            .collect();
+        let evaluation = self.evaluation(arena.clone())?;
+
+        for (key, agent) in agents.iter().skip(elitism) {
+            if pruned.contains(key) {
+                continue;
+            }
+            if arena.strategy_genes > 0 {
+                // Self-adapting arenas only take genomes mutated on-chain.
+                let mutate = ix::mutate_genome(self.authority(), *key, arena.current_round);
+                match self.send(mutate) {
+                    Ok(_) => {
+                        report.agents_mutated += 1;
+                        decisions.push(format!("Self-mutated agent {}", short(key)));
+                    }
+                    Err(e) => self
+                        .logger
+                        .warn(&format!("Failed to mutate agent {}: {e}", short(key))),
+                }
+                continue;
+            }
+            let (child, predicted) = self.breed(&population, &evaluation)?;
+            let child_hash = genome_hash(&child);
+            let submit = ix::submit_genome(self.authority(), *key, child, champion);
+            match self.send(submit) {
+                Ok(_) => {
+                    report.agents_mutated += 1;
+                    decisions.push(format!(
+                        "Mutated agent {}: genome {} -> {} (predicted fitness {predicted})",
+                        short(key),
+                        &genome_hash(&agent.genome)[..8],
+                        &child_hash[..8]
+                    ));
+                }
+                Err(e) => self.logger.warn(&format!(
+                    "Failed to submit genome for agent {}: {e}",
+                    short(key)
+                )),
+            }
+        }
+
+        self.logger
+            .info(&format!("  Agents mutated: {}", report.agents_mutated));
+        self.logger
+            .info(&format!("  Agents pruned:  {}", report.agents_pruned));
+
+        self.send(ix::advance_generation(
+            self.authority(),
+            arena.current_generation,
+        ))?;
+        self.logger.info("Generation advanced");
+
+        report.decisions = decisions;
+        self.state.generations_completed = generation;
+        self.state.rounds_completed = 0;
+        self.state.arena_generation = arena.current_generation + 1;
+        self.state.reports.push(report.clone());
+        self.save()?;
+        Ok(report)
+    }
+
+    pub fn run(&mut self) -> Result<(), AgentError> {
+        self.logger.info("========================================");
+        self.logger.info("  SOLANA AUTONOMOUS EVOLUTION ARENA");
+        self.logger.info("  Autonomous AI Agent Starting...");
+        self.logger
+            .info("========================================\n");
+
+        self.initialize()?;
+        self.register_population()?;
+
+        for generation in self.state.generations_completed + 1..=self.config.generations_to_run {
+            self.run_generation(generation)?;
+        }
+
+        self.logger
+            .info("\n========================================");
+        self.logger.info("  FINAL LEADERBOARD");
+        self.logger.info("========================================");
+        let mut entries = fetch_population(&self.rpc)?.entries;
+        entries.sort_by(|a, b| b.fitness.cmp(&a.fitness));
+        for (i, entry) in entries.iter().enumerate() {
+            self.logger.info(&format!(
+                "  #{} | Fitness: {} | Last round: {} | {}...",
+                i + 1,
+                entry.fitness,
+                entry.last_round,
+                &entry.agent.to_string()[..12]
+            ));
+        }
+
+        self.logger
+            .info("\n========================================");
+        self.logger.info("  EVOLUTION SUMMARY");
+        self.logger.info("========================================");
+        for r in &self.state.reports {
+            self.logger.info(&format!(
+                "  Gen {}: best={}, avg={}, mutated={}, pruned={}, rate={}bps",
+                r.generation,
+                r.best_fitness,
+                r.average_fitness,
+                r.agents_mutated,
+                r.agents_pruned,
+                r.mutation_rate
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use crate::config::LogLevel;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct Logger {
+    level: LogLevel,
+}
+
+impl Logger {
+    pub fn new(level: LogLevel) -> Self {
+        Self { level }
+    }
+
+    fn log(&self, level: LogLevel, msg: &str) {
+        if level < self.level {
+            return;
+        }
+        let ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let line = format!("[{ts}] [{}] {msg}", format!("{level:?}").to_uppercase());
+        if level >= LogLevel::Warn {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    pub fn debug(&self, msg: &str) {
+        self.log(LogLevel::Debug, msg);
+    }
+
+    pub fn info(&self, msg: &str) {
+        self.log(LogLevel::Info, msg);
+    }
+
+    pub fn warn(&self, msg: &str) {
+        self.log(LogLevel::Warn, msg);
+    }
+}
@@ -0,0 +1,50 @@
+//! Rust port of the TypeScript evolution agent in `agent/src`.
+//!
+//! Reads the same environment variables as `yarn agent` and keeps its
+//! progress in `STATE_PATH` so a stopped run can be resumed.
+
+mod config;
+mod error;
+mod evolution;
+mod logger;
+mod rpc;
+mod state;
+
+use config::AgentConfig;
+use error::AgentError;
+use evolution::EvolutionEngine;
+use logger::Logger;
+use rpc::RpcClient;
+use std::fs;
+use std::process::ExitCode;
+
+const REPORT_PATH: &str = "agent-run-report.json";
+
+fn run() -> Result<(), AgentError> {
+    let config = AgentConfig::from_env().map_err(AgentError::Config)?;
+    let logger = Logger::new(config.log_level);
+    logger.info("Starting Solana Autonomous Evolution Arena Agent...");
+
+    let wallet = solana_keypair::read_keypair_file(&config.wallet_path)
+        .map_err(|e| AgentError::Config(format!("{}: {e}", config.wallet_path)))?;
+    let rpc = RpcClient::new(&config.rpc_url);
+
+    let mut engine = EvolutionEngine::new(rpc, wallet, config, logger);
+    engine.run()?;
+
+    let report = serde_json::to_vec_pretty(engine.reports())
+        .map_err(|e| AgentError::State(e.to_string()))?;
+    fs::write(REPORT_PATH, report).map_err(|e| AgentError::State(format!("{REPORT_PATH}: {e}")))?;
+    println!("\nFull run report saved to: {REPORT_PATH}");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Agent fatal error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
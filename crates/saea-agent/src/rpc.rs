@@ -0,0 +1,141 @@
+//! Minimal blocking JSON-RPC client covering the calls the agent makes.
+
+use crate::error::AgentError;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use saea_client::accounts::AccountSource;
+use saea_client::ClientError;
+use serde_json::{json, Value};
+use solana_hash::Hash;
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use std::str::FromStr;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+const COMMITMENT: &str = "confirmed";
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(60);
+const CONFIRM_POLL: Duration = Duration::from_millis(500);
+
+pub struct RpcClient {
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, AgentError> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response = ureq::post(&self.url)
+            .send_json(&request)
+            .map_err(|e| AgentError::Rpc(format!("{method}: {e}")))?;
+        let mut body: Value = response
+            .body_mut()
+            .read_json()
+            .map_err(|e| AgentError::Rpc(format!("{method}: {e}")))?;
+        if let Some(error) = body.get("error") {
+            return Err(AgentError::Rpc(format!("{method}: {error}")));
+        }
+        Ok(body["result"].take())
+    }
+
+    pub fn latest_blockhash(&self) -> Result<Hash, AgentError> {
+        let result = self.call("getLatestBlockhash", json!([{ "commitment": COMMITMENT }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or_else(|| AgentError::Rpc("getLatestBlockhash: missing blockhash".into()))?;
+        Hash::from_str(blockhash).map_err(|e| AgentError::Rpc(format!("blockhash: {e}")))
+    }
+
+    /// Sign `instructions` with `payer`, send them in one transaction and
+    /// wait for confirmation. Returns the transaction signature.
+    pub fn send_and_confirm(
+        &self,
+        instructions: &[Instruction],
+        payer: &Keypair,
+    ) -> Result<String, AgentError> {
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            self.latest_blockhash()?,
+        );
+        let wire = bincode::serialize(&tx).map_err(|e| AgentError::Transaction(e.to_string()))?;
+        let signature = self
+            .call(
+                "sendTransaction",
+                json!([STANDARD.encode(wire), { "encoding": "base64", "preflightCommitment": COMMITMENT }]),
+            )?
+            .as_str()
+            .ok_or_else(|| AgentError::Rpc("sendTransaction: missing signature".into()))?
+            .to_string();
+        self.confirm(&signature)?;
+        Ok(signature)
+    }
+
+    fn confirm(&self, signature: &str) -> Result<(), AgentError> {
+        let started = Instant::now();
+        while started.elapsed() < CONFIRM_TIMEOUT {
+            let result = self.call("getSignatureStatuses", json!([[signature]]))?;
+            let status = &result["value"][0];
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(AgentError::Transaction(format!(
+                        "{signature} failed: {}",
+                        status["err"]
+                    )));
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(());
+                }
+            }
+            sleep(CONFIRM_POLL);
+        }
+        Err(AgentError::Transaction(format!(
+            "{signature} not confirmed within {}s",
+            CONFIRM_TIMEOUT.as_secs()
+        )))
+    }
+
+    pub fn request_airdrop(&self, to: &Pubkey, lamports: u64) -> Result<(), AgentError> {
+        let signature = self
+            .call(
+                "requestAirdrop",
+                json!([to.to_string(), lamports, { "commitment": COMMITMENT }]),
+            )?
+            .as_str()
+            .ok_or_else(|| AgentError::Rpc("requestAirdrop: missing signature".into()))?
+            .to_string();
+        self.confirm(&signature)
+    }
+}
+
+impl AccountSource for RpcClient {
+    fn account_data(&self, key: &Pubkey) -> Result<Option<Vec<u8>>, ClientError> {
+        let result = self
+            .call(
+                "getAccountInfo",
+                json!([key.to_string(), { "encoding": "base64", "commitment": COMMITMENT }]),
+            )
+            .map_err(|e| ClientError::Source(e.to_string()))?;
+        let value = &result["value"];
+        if value.is_null() {
+            return Ok(None);
+        }
+        let data = value["data"][0]
+            .as_str()
+            .ok_or_else(|| ClientError::Source(format!("getAccountInfo {key}: missing data")))?;
+        STANDARD
+            .decode(data)
+            .map(Some)
+            .map_err(|e| ClientError::Source(format!("getAccountInfo {key}: {e}")))
+    }
+}
@@ -0,0 +1,55 @@
+//! Progress persisted between runs so an interrupted agent resumes where it
+//! stopped instead of registering a fresh population.
+
+use crate::error::AgentError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerationReport {
+    pub generation: u64,
+    pub rounds_run: u64,
+    pub best_fitness: u64,
+    pub average_fitness: u64,
+    pub worst_fitness: u64,
+    pub best_genome_hash: String,
+    pub agents_pruned: u64,
+    pub agents_mutated: u64,
+    pub mutation_rate: u16,
+    pub decisions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AgentState {
+    pub agents: Vec<String>, // base58 agent PDAs registered by this agent
+    pub generations_completed: u64,
+    pub rounds_completed: u64, // rounds of the next generation already completed
+    pub arena_generation: u64, // arena.current_generation when last saved
+    pub mutation_rate_bps: u16,
+    pub previous_best_fitness: u64,
+    pub reports: Vec<GenerationReport>,
+}
+
+impl AgentState {
+    /// Load the state at `path`, or `None` if no run has been saved there.
+    pub fn load(path: &Path) -> Result<Option<Self>, AgentError> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let data = fs::read(path).map_err(|e| AgentError::State(format!("{path:?}: {e}")))?;
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(|e| AgentError::State(format!("{path:?}: {e}")))
+    }
+
+    /// Write through a temporary file so a crash never leaves a torn state.
+    pub fn save(&self, path: &Path) -> Result<(), AgentError> {
+        let tmp = path.with_extension("tmp");
+        let data = serde_json::to_vec_pretty(self).map_err(|e| AgentError::State(e.to_string()))?;
+        fs::write(&tmp, data).map_err(|e| AgentError::State(format!("{tmp:?}: {e}")))?;
+        fs::rename(&tmp, path).map_err(|e| AgentError::State(format!("{path:?}: {e}")))
+    }
+}
@@ -0,0 +1,97 @@
+//! Deserialization of saea accounts, and fetching through any
+//! [`AccountSource`] (an RPC client, a test bank, a snapshot).
+
+use crate::{pda, ClientError};
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, Discriminator};
+use saea::state::{
+    AgentAccount, Arena, Generation, Population, PopulationEntry, Round, POPULATION_HEADER_LEN,
+};
+use std::mem::size_of;
+
+/// Anything that can return the raw data of an account.
+pub trait AccountSource {
+    /// Data of the account at `key`, or `None` if it does not exist.
+    fn account_data(&self, key: &Pubkey) -> Result<Option<Vec<u8>>, ClientError>;
+}
+
+/// Deserialize a borsh account, checking its discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+pub fn fetch<T: AccountDeserialize>(
+    source: &impl AccountSource,
+    key: &Pubkey,
+) -> Result<T, ClientError> {
+    let data = source
+        .account_data(key)?
+        .ok_or(ClientError::AccountNotFound(*key))?;
+    decode(&data)
+}
+
+pub fn fetch_arena(source: &impl AccountSource) -> Result<Arena, ClientError> {
+    fetch(source, &pda::arena().0)
+}
+
+pub fn fetch_agent(
+    source: &impl AccountSource,
+    agent: &Pubkey,
+) -> Result<AgentAccount, ClientError> {
+    fetch(source, agent)
+}
+
+pub fn fetch_round(source: &impl AccountSource, round_number: u64) -> Result<Round, ClientError> {
+    fetch(source, &pda::round(&pda::arena().0, round_number).0)
+}
+
+pub fn fetch_generation(
+    source: &impl AccountSource,
+    generation_number: u64,
+) -> Result<Generation, ClientError> {
+    fetch(
+        source,
+        &pda::generation(&pda::arena().0, generation_number).0,
+    )
+}
+
+/// Decoded `Population` account: its header and active-agent entries.
+#[derive(Clone)]
+pub struct PopulationSnapshot {
+    pub arena: Pubkey,
+    pub entries: Vec<PopulationEntry>,
+}
+
+/// Decode a zero-copy `Population` account. Entries are read unaligned, so
+/// any byte buffer works.
+pub fn decode_population(data: &[u8]) -> Result<PopulationSnapshot, ClientError> {
+    let invalid =
+        || ClientError::Deserialize(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    if data.len() < POPULATION_HEADER_LEN || &data[..8] != Population::DISCRIMINATOR {
+        return Err(invalid());
+    }
+    let header: Population = bytemuck::pod_read_unaligned(&data[8..POPULATION_HEADER_LEN]);
+    let entry_len = size_of::<PopulationEntry>();
+    let body = &data[POPULATION_HEADER_LEN..];
+    if body.len() < header.len as usize * entry_len {
+        return Err(invalid());
+    }
+    let entries = body
+        .chunks_exact(entry_len)
+        .take(header.len as usize)
+        .map(bytemuck::pod_read_unaligned)
+        .collect();
+    Ok(PopulationSnapshot {
+        arena: header.arena,
+        entries,
+    })
+}
+
+pub fn fetch_population(source: &impl AccountSource) -> Result<PopulationSnapshot, ClientError> {
+    let key = pda::population(&pda::arena().0).0;
+    let data = source
+        .account_data(&key)?
+        .ok_or(ClientError::AccountNotFound(key))?;
+    decode_population(&data)
+}
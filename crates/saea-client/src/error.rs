@@ -0,0 +1,30 @@
+use anchor_lang::prelude::Pubkey;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No account exists at the address.
+    AccountNotFound(Pubkey),
+    /// The account data does not hold the expected account type.
+    Deserialize(anchor_lang::error::Error),
+    /// The account source failed to return data.
+    Source(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::AccountNotFound(key) => write!(f, "account {key} not found"),
+            ClientError::Deserialize(e) => write!(f, "failed to deserialize account: {e}"),
+            ClientError::Source(e) => write!(f, "account source error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(e: anchor_lang::error::Error) -> Self {
+        ClientError::Deserialize(e)
+    }
+}
@@ -0,0 +1,103 @@
+//! Decoding of saea events from transaction logs. `emit!` writes each event
+//! as a `Program data: <base64>` line holding its discriminator followed by
+//! its borsh encoding.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use saea::events::*;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+macro_rules! saea_events {
+    ($($event:ident),* $(,)?) => {
+        /// Every event the saea program emits.
+        pub enum SaeaEvent {
+            $($event($event),)*
+        }
+
+        impl SaeaEvent {
+            /// Decode one event from its discriminator-prefixed bytes, or
+            /// `None` if the bytes are not a known saea event.
+            pub fn decode(data: &[u8]) -> Option<Self> {
+                $(
+                    if let Some(mut body) = data.strip_prefix($event::DISCRIMINATOR) {
+                        return $event::deserialize(&mut body).ok().map(SaeaEvent::$event);
+                    }
+                )*
+                None
+            }
+
+            /// Name of the event, as it appears in the IDL.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(SaeaEvent::$event(_) => stringify!($event),)*
+                }
+            }
+        }
+    };
+}
+
+saea_events!(
+    ArenaInitialized,
+    AgentRegistered,
+    GenomeSubmitted,
+    RoundCompleted,
+    AgentScored,
+    AgentPruned,
+    GenerationAdvanced,
+    BenchmarkFrozen,
+    FitnessModeChanged,
+    SelectionConfigured,
+    MatchPlayed,
+    RatingUpdated,
+    HistogramConfigured,
+    HallOfFameUpdated,
+    MutationRateAdjusted,
+    AgentMigrated,
+    NoveltyArchived,
+    ParetoRanked,
+);
+
+/// Decode every saea event in a transaction's log messages, in order.
+///
+/// Only `Program data:` lines logged while saea itself is the executing
+/// program are considered, so events of other programs invoked in the same
+/// transaction are skipped even if their discriminators collide. The
+/// invocation stack follows the runtime's own `Program <id> invoke [n]`,
+/// `Program <id> success` and `Program <id> failed: ` lines; program
+/// messages mentioning success or failure leave it untouched.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<SaeaEvent> {
+    let mut stack: Vec<bool> = Vec::new();
+    let mut events = Vec::new();
+    for line in logs.iter().map(AsRef::as_ref) {
+        if let Some(payload) = line.strip_prefix(PROGRAM_DATA) {
+            if stack.last() != Some(&true) {
+                continue;
+            }
+            if let Some(event) = STANDARD
+                .decode(payload)
+                .ok()
+                .and_then(|d| SaeaEvent::decode(&d))
+            {
+                events.push(event);
+            }
+        } else if let Some((program, status)) = runtime_line(line) {
+            if status.starts_with("invoke [") {
+                stack.push(program == saea::ID);
+            } else if status == "success" || status.starts_with("failed: ") {
+                stack.pop();
+            }
+        }
+    }
+    events
+}
+
+/// Split a `Program <id> <status>` line logged by the runtime. Lines
+/// logged by programs (`Program log:`, `Program data:`, ...) have no
+/// program id and yield `None`.
+fn runtime_line(line: &str) -> Option<(Pubkey, &str)> {
+    let (program, status) = line.strip_prefix("Program ")?.split_once(' ')?;
+    Some((program.parse().ok()?, status))
+}
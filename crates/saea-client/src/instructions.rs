@@ -0,0 +1,617 @@
+//! Typed builders for every saea instruction. PDAs are derived from the
+//! arguments, so callers only supply signers, agent addresses and the
+//! round or generation an instruction targets.
+
+use crate::pda;
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use saea::state::{
+    Arena, BenchmarkModel, CompletionPolicy, FitnessMode, GenomeDistance, MigrationTopology, Round,
+    SelectionMetric,
+};
+use saea::{accounts, instruction};
+
+fn build(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining: &[AccountMeta],
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend_from_slice(remaining);
+    Instruction {
+        program_id: saea::ID,
+        accounts: metas,
+        data: data.data(),
+    }
+}
+
+fn writable(keys: &[Pubkey]) -> Vec<AccountMeta> {
+    keys.iter().map(|k| AccountMeta::new(*k, false)).collect()
+}
+
+/// Optional accounts passed to `score_agent`; each is required by some
+/// arena configuration (benchmark or novelty mode, speciation, a hall of
+/// fame).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ScoreAccounts {
+    pub benchmark: bool,
+    pub hall_of_fame: bool,
+    pub species: bool,
+    pub novelty_archive: bool,
+}
+
+impl ScoreAccounts {
+    /// The accounts `arena` requires for a score in `round`.
+    pub fn required(arena: &Arena, round: &Round) -> Self {
+        Self {
+            benchmark: round.fitness_mode == FitnessMode::Benchmark,
+            hall_of_fame: arena.hall_of_fame_enabled,
+            species: arena.speciation_enabled,
+            novelty_archive: round.fitness_mode == FitnessMode::Novelty,
+        }
+    }
+}
+
+pub fn initialize_arena(
+    authority: Pubkey,
+    max_agents: u64,
+    min_fitness_threshold: u64,
+    mutation_rate_bps: u16,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::InitializeArena {
+            arena,
+            population: pda::population(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeArena {
+            max_agents,
+            min_fitness_threshold,
+            mutation_rate_bps,
+        },
+        &[],
+    )
+}
+
+/// Register `owner`'s agent at `agent_index`, which must equal the arena's
+/// current `total_agents`.
+pub fn register_agent(owner: Pubkey, agent_index: u64, genome: Vec<u8>) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::RegisterAgent {
+            arena,
+            agent: pda::agent(&arena, &owner, agent_index).0,
+            population: pda::population(&arena).0,
+            owner,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterAgent { genome },
+        &[],
+    )
+}
+
+pub fn submit_genome(
+    owner: Pubkey,
+    agent: Pubkey,
+    new_genome: Vec<u8>,
+    parent_key: Pubkey,
+) -> Instruction {
+    build(
+        accounts::SubmitGenome {
+            arena: pda::arena().0,
+            agent,
+            owner,
+        },
+        instruction::SubmitGenome {
+            new_genome,
+            parent_key,
+        },
+        &[],
+    )
+}
+
+/// Self-adaptive mutation of `agent`, seeded by the arena's latest round
+/// `current_round`.
+pub fn mutate_genome(owner: Pubkey, agent: Pubkey, current_round: u64) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::MutateGenome {
+            arena,
+            round: pda::round(&arena, current_round).0,
+            agent,
+            owner,
+        },
+        instruction::MutateGenome {},
+        &[],
+    )
+}
+
+/// Start round `round_number` (the arena's `current_round + 1`).
+pub fn run_round(authority: Pubkey, round_number: u64) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::RunRound {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RunRound {},
+        &[],
+    )
+}
+
+/// Start round `round_number` restricted to agents living on `island`.
+pub fn run_island_round(authority: Pubkey, round_number: u64, island: u8) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::RunRound {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RunIslandRound { island },
+        &[],
+    )
+}
+
+pub fn score_agent(
+    authority: Pubkey,
+    round_number: u64,
+    agent: Pubkey,
+    optional: ScoreAccounts,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::ScoreAgent {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            agent,
+            benchmark: optional.benchmark.then(|| pda::benchmark(&arena).0),
+            hall_of_fame: optional.hall_of_fame.then(|| pda::hall_of_fame(&arena).0),
+            species: optional.species.then(|| pda::species(&arena).0),
+            novelty_archive: optional
+                .novelty_archive
+                .then(|| pda::novelty_archive(&arena).0),
+            population: pda::population(&arena).0,
+            authority,
+        },
+        instruction::ScoreAgent {},
+        &[],
+    )
+}
+
+pub fn complete_round(authority: Pubkey, round_number: u64) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::CompleteRound {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            population: pda::population(&arena).0,
+            authority,
+        },
+        instruction::CompleteRound {},
+        &[],
+    )
+}
+
+/// Prune `agent`, refunding its registry rent to `owner`, the agent's owner.
+/// `current_round` is the arena's latest round, required once there is one.
+pub fn prune_agent(
+    authority: Pubkey,
+    agent: Pubkey,
+    owner: Pubkey,
+    current_round: Option<u64>,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::PruneAgent {
+            arena,
+            agent,
+            population: pda::population(&arena).0,
+            round: current_round.map(|n| pda::round(&arena, n).0),
+            owner,
+            authority,
+        },
+        instruction::PruneAgent {},
+        &[],
+    )
+}
+
+/// Close generation `current_generation` and start the next one.
+pub fn advance_generation(authority: Pubkey, current_generation: u64) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::AdvanceGeneration {
+            arena,
+            generation: pda::generation(&arena, current_generation).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::AdvanceGeneration {},
+        &[],
+    )
+}
+
+pub fn create_benchmark(
+    authority: Pubkey,
+    model: BenchmarkModel,
+    feature_count: u8,
+    class_count: u8,
+    case_count: u32,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::CreateBenchmark {
+            arena,
+            benchmark: pda::benchmark(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::CreateBenchmark {
+            model,
+            feature_count,
+            class_count,
+            case_count,
+        },
+        &[],
+    )
+}
+
+pub fn upload_benchmark_chunk(authority: Pubkey, offset: u32, data: Vec<u8>) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::UploadBenchmarkChunk {
+            arena,
+            benchmark: pda::benchmark(&arena).0,
+            authority,
+        },
+        instruction::UploadBenchmarkChunk { offset, data },
+        &[],
+    )
+}
+
+pub fn freeze_benchmark(authority: Pubkey) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::FreezeBenchmark {
+            arena,
+            benchmark: pda::benchmark(&arena).0,
+            authority,
+        },
+        instruction::FreezeBenchmark {},
+        &[],
+    )
+}
+
+pub fn set_fitness_mode(authority: Pubkey, mode: FitnessMode) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::SetFitnessMode {
+            arena,
+            benchmark: (mode == FitnessMode::Benchmark).then(|| pda::benchmark(&arena).0),
+            novelty_archive: (mode == FitnessMode::Novelty).then(|| pda::novelty_archive(&arena).0),
+            authority,
+        },
+        instruction::SetFitnessMode { mode },
+        &[],
+    )
+}
+
+pub fn configure_selection(
+    authority: Pubkey,
+    metric: SelectionMetric,
+    half_life: u16,
+    window: u8,
+) -> Instruction {
+    build(
+        accounts::ConfigureSelection {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureSelection {
+            metric,
+            half_life,
+            window,
+        },
+        &[],
+    )
+}
+
+/// Start tournament round `round_number` among every active agent.
+pub fn run_tournament_round(authority: Pubkey, round_number: u64) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::RunTournamentRound {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            population: pda::population(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::RunTournamentRound {},
+        &[],
+    )
+}
+
+pub fn play_match(
+    authority: Pubkey,
+    round_number: u64,
+    match_index: u16,
+    agent_a: Pubkey,
+    agent_b: Pubkey,
+    optional: ScoreAccounts,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::PlayMatch {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            agent_a,
+            agent_b,
+            species: optional.species.then(|| pda::species(&arena).0),
+            hall_of_fame: optional.hall_of_fame.then(|| pda::hall_of_fame(&arena).0),
+            population: pda::population(&arena).0,
+            authority,
+        },
+        instruction::PlayMatch { match_index },
+        &[],
+    )
+}
+
+/// Rate `agent` after solo round `round_number` against `opponents`, its
+/// `saea::instructions::rating_opponents` in that round.
+pub fn update_ratings(
+    authority: Pubkey,
+    round_number: u64,
+    agent: Pubkey,
+    opponents: &[Pubkey],
+) -> Instruction {
+    let arena = pda::arena().0;
+    let opponents: Vec<AccountMeta> = opponents
+        .iter()
+        .map(|k| AccountMeta::new_readonly(*k, false))
+        .collect();
+    build(
+        accounts::UpdateRatings {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            population: pda::population(&arena).0,
+            agent,
+            authority,
+        },
+        instruction::UpdateRatings {},
+        &opponents,
+    )
+}
+
+pub fn configure_histogram(authority: Pubkey, base: u64, bucket_width: u64) -> Instruction {
+    build(
+        accounts::ConfigureHistogram {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureHistogram { base, bucket_width },
+        &[],
+    )
+}
+
+pub fn initialize_hall_of_fame(authority: Pubkey) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::InitializeHallOfFame {
+            arena,
+            hall_of_fame: pda::hall_of_fame(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeHallOfFame {},
+        &[],
+    )
+}
+
+pub fn configure_mutation(
+    authority: Pubkey,
+    adaptive: bool,
+    min_rate_bps: u16,
+    max_rate_bps: u16,
+    stagnation_window: u16,
+) -> Instruction {
+    build(
+        accounts::ConfigureMutation {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureMutation {
+            adaptive,
+            min_rate_bps,
+            max_rate_bps,
+            stagnation_window,
+        },
+        &[],
+    )
+}
+
+pub fn configure_strategy_genes(authority: Pubkey, strategy_genes: u8) -> Instruction {
+    build(
+        accounts::ConfigureMutation {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureStrategyGenes { strategy_genes },
+        &[],
+    )
+}
+
+pub fn configure_islands(
+    authority: Pubkey,
+    island_count: u8,
+    migration_interval: u64,
+    topology: MigrationTopology,
+    migrants_per_island: u8,
+) -> Instruction {
+    build(
+        accounts::ConfigureIslands {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureIslands {
+            island_count,
+            migration_interval,
+            topology,
+            migrants_per_island,
+        },
+        &[],
+    )
+}
+
+/// Move `migrants`, the fittest agents of each island by the registry's
+/// ranking (see `saea::instructions::migrants`), to their destination
+/// islands. `current_round` is the arena's latest round, required by
+/// `MigrationTopology::Random`.
+pub fn migrate(authority: Pubkey, current_round: Option<u64>, migrants: &[Pubkey]) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::Migrate {
+            arena,
+            population: pda::population(&arena).0,
+            round: current_round.map(|n| pda::round(&arena, n).0),
+            authority,
+        },
+        instruction::Migrate {},
+        &writable(migrants),
+    )
+}
+
+pub fn initialize_species(
+    authority: Pubkey,
+    distance: GenomeDistance,
+    threshold: u32,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::InitializeSpecies {
+            arena,
+            species: pda::species(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeSpecies {
+            distance,
+            threshold,
+        },
+        &[],
+    )
+}
+
+pub fn share_fitness(authority: Pubkey, round_number: u64, agents: &[Pubkey]) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::ShareFitness {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            species: pda::species(&arena).0,
+            population: pda::population(&arena).0,
+            authority,
+        },
+        instruction::ShareFitness {},
+        &writable(agents),
+    )
+}
+
+pub fn initialize_novelty_archive(
+    authority: Pubkey,
+    k_nearest: u8,
+    threshold: u64,
+    novelty_weight: u64,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::InitializeNoveltyArchive {
+            arena,
+            novelty_archive: pda::novelty_archive(&arena).0,
+            authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeNoveltyArchive {
+            k_nearest,
+            threshold,
+            novelty_weight,
+        },
+        &[],
+    )
+}
+
+pub fn configure_pareto(authority: Pubkey, enabled: bool, prune_front: u8) -> Instruction {
+    build(
+        accounts::ConfigurePareto {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigurePareto {
+            enabled,
+            prune_front,
+        },
+        &[],
+    )
+}
+
+/// Rank round `round_number` by Pareto front; `agents` must be every agent
+/// scored in it.
+pub fn rank_pareto(authority: Pubkey, round_number: u64, agents: &[Pubkey]) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::RankPareto {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            population: pda::population(&arena).0,
+            authority,
+        },
+        instruction::RankPareto {},
+        &writable(agents),
+    )
+}
+
+/// View instruction; send through `simulateTransaction` and decode the
+/// return data as `saea::instructions::FitnessSimulation`.
+pub fn simulate_fitness(
+    genome: Vec<u8>,
+    round_number: u64,
+    benchmark: bool,
+    novelty_archive: bool,
+) -> Instruction {
+    let arena = pda::arena().0;
+    build(
+        accounts::SimulateFitness {
+            arena,
+            round: pda::round(&arena, round_number).0,
+            benchmark: benchmark.then(|| pda::benchmark(&arena).0),
+            novelty_archive: novelty_archive.then(|| pda::novelty_archive(&arena).0),
+        },
+        instruction::SimulateFitness {
+            genome,
+            round_number,
+        },
+        &[],
+    )
+}
+
+pub fn configure_completion(
+    authority: Pubkey,
+    policy: CompletionPolicy,
+    quorum_pct: u8,
+) -> Instruction {
+    build(
+        accounts::ConfigureCompletion {
+            arena: pda::arena().0,
+            authority,
+        },
+        instruction::ConfigureCompletion { policy, quorum_pct },
+        &[],
+    )
+}
@@ -0,0 +1,16 @@
+//! Client helpers for the saea program, built on the program's own types.
+//!
+//! - [`pda`]: addresses of every program-derived account
+//! - [`instructions`]: typed builders for every instruction
+//! - [`accounts`]: deserialization and fetching of program accounts
+//! - [`events`]: decoding of emitted events from transaction logs
+
+pub mod accounts;
+pub mod error;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+pub use error::ClientError;
+pub use saea;
+pub use saea::ID as PROGRAM_ID;
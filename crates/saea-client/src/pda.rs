@@ -0,0 +1,58 @@
+//! Program-derived addresses, mirroring the `seeds` constraints on-chain.
+
+use anchor_lang::prelude::Pubkey;
+use saea::state::{
+    AgentAccount, Arena, Benchmark, Generation, HallOfFame, NoveltyArchive, Population, Round,
+    SpeciesRegistry,
+};
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &saea::ID)
+}
+
+pub fn arena() -> (Pubkey, u8) {
+    find(&[Arena::SEED])
+}
+
+/// Agent registered as the arena's `index`-th agent (`Arena::total_agents`
+/// at registration time).
+pub fn agent(arena: &Pubkey, owner: &Pubkey, index: u64) -> (Pubkey, u8) {
+    find(&[
+        AgentAccount::SEED,
+        arena.as_ref(),
+        owner.as_ref(),
+        &index.to_le_bytes(),
+    ])
+}
+
+pub fn round(arena: &Pubkey, round_number: u64) -> (Pubkey, u8) {
+    find(&[Round::SEED, arena.as_ref(), &round_number.to_le_bytes()])
+}
+
+pub fn generation(arena: &Pubkey, generation_number: u64) -> (Pubkey, u8) {
+    find(&[
+        Generation::SEED,
+        arena.as_ref(),
+        &generation_number.to_le_bytes(),
+    ])
+}
+
+pub fn population(arena: &Pubkey) -> (Pubkey, u8) {
+    find(&[Population::SEED, arena.as_ref()])
+}
+
+pub fn benchmark(arena: &Pubkey) -> (Pubkey, u8) {
+    find(&[Benchmark::SEED, arena.as_ref()])
+}
+
+pub fn hall_of_fame(arena: &Pubkey) -> (Pubkey, u8) {
+    find(&[HallOfFame::SEED, arena.as_ref()])
+}
+
+pub fn species(arena: &Pubkey) -> (Pubkey, u8) {
+    find(&[SpeciesRegistry::SEED, arena.as_ref()])
+}
+
+pub fn novelty_archive(arena: &Pubkey) -> (Pubkey, u8) {
+    find(&[NoveltyArchive::SEED, arena.as_ref()])
+}
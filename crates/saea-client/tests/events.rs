@@ -0,0 +1,85 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use saea::events::AgentPruned;
+use saea_client::events::{parse_logs, SaeaEvent};
+
+const OTHER: &str = "11111111111111111111111111111111";
+
+/// `Program data:` line carrying an `AgentPruned` event for `agent`.
+fn data(agent: Pubkey) -> String {
+    let event = AgentPruned {
+        agent,
+        fitness: 7,
+        generation: 1,
+    };
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn pruned(logs: &[String]) -> Vec<Pubkey> {
+    parse_logs(logs)
+        .into_iter()
+        .map(|event| match event {
+            SaeaEvent::AgentPruned(e) => e.agent,
+            other => panic!("unexpected {}", other.name()),
+        })
+        .collect()
+}
+
+#[test]
+fn nested_invocations_keep_only_saea_events() {
+    let (outer, inner) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let logs = vec![
+        format!("Program {} invoke [1]", saea::ID),
+        format!("Program {OTHER} invoke [2]"),
+        data(inner),
+        format!("Program {OTHER} success"),
+        data(outer),
+        format!("Program {} success", saea::ID),
+    ];
+    assert_eq!(pruned(&logs), [outer]);
+}
+
+#[test]
+fn saea_called_from_another_program() {
+    let agent = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {OTHER} invoke [1]"),
+        format!("Program {} invoke [2]", saea::ID),
+        data(agent),
+        format!("Program {} success", saea::ID),
+        data(Pubkey::new_unique()),
+        format!("Program {OTHER} success"),
+    ];
+    assert_eq!(pruned(&logs), [agent]);
+}
+
+#[test]
+fn failed_inner_call_returns_to_saea() {
+    let agent = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {} invoke [1]", saea::ID),
+        format!("Program {OTHER} invoke [2]"),
+        format!("Program {OTHER} failed: custom program error: 0x1"),
+        data(agent),
+        format!("Program {} failed: custom program error: 0x1", saea::ID),
+    ];
+    assert_eq!(pruned(&logs), [agent]);
+}
+
+#[test]
+fn messages_mentioning_failure_or_success_do_not_pop() {
+    let agent = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {} invoke [1]", saea::ID),
+        format!("Program {OTHER} invoke [2]"),
+        "Program log: transfer failed, retrying".to_string(),
+        "Program log: retry success".to_string(),
+        data(Pubkey::new_unique()),
+        format!("Program {OTHER} success"),
+        data(agent),
+        format!("Program {} success", saea::ID),
+    ];
+    assert_eq!(pruned(&logs), [agent]);
+}
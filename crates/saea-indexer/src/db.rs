@@ -0,0 +1,331 @@
+//! SQLite schema and event ingestion.
+//!
+//! Each transaction is ingested at most once, in one SQL transaction, and
+//! every event row is keyed by its signature and position so re-running a
+//! poll or a backfill over the same history changes nothing. Rows are
+//! written so that ingesting transactions out of order (a backfill that
+//! overlaps an earlier poll, say) gives the same tables.
+
+use crate::rpc::TransactionLogs;
+use anchor_lang::prelude::Pubkey;
+use rusqlite::{params, Connection, OptionalExtension};
+use saea_client::events::{parse_logs, SaeaEvent};
+use std::path::Path;
+
+/// Bumped whenever `SCHEMA` changes incompatibly.
+const SCHEMA_VERSION: i64 = 1;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS transactions (
+    signature   TEXT PRIMARY KEY,
+    slot        INTEGER NOT NULL,
+    block_time  INTEGER,
+    failed      INTEGER NOT NULL,
+    event_count INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS transactions_slot ON transactions (slot);
+
+-- Newest signature `poll` has indexed everything up to. Backfills store
+-- transactions anywhere in the history, so they never move it.
+CREATE TABLE IF NOT EXISTS poll_tip (
+    id        INTEGER PRIMARY KEY CHECK (id = 0),
+    signature TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS agents (
+    agent                 TEXT PRIMARY KEY,
+    owner                 TEXT,
+    registered_generation INTEGER,
+    registered_signature  TEXT,
+    pruned_generation     INTEGER,
+    pruned_fitness        INTEGER,
+    pruned_signature      TEXT
+);
+
+-- Every genome an agent has held: the one it registered with (no parent)
+-- and each submitted replacement.
+CREATE TABLE IF NOT EXISTS genomes (
+    signature      TEXT NOT NULL,
+    event_index    INTEGER NOT NULL,
+    agent          TEXT NOT NULL,
+    genome_hash    TEXT NOT NULL,
+    generation     INTEGER NOT NULL,
+    mutation_count INTEGER NOT NULL,
+    parent         TEXT,
+    slot           INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS genomes_agent ON genomes (agent);
+
+CREATE TABLE IF NOT EXISTS scores (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    agent        TEXT NOT NULL,
+    round_number INTEGER NOT NULL,
+    fitness      INTEGER NOT NULL,
+    genome_hash  TEXT NOT NULL,
+    species      INTEGER NOT NULL,
+    proximity    INTEGER NOT NULL,
+    diversity    INTEGER NOT NULL,
+    balance      INTEGER NOT NULL,
+    pattern      INTEGER NOT NULL,
+    sequence     INTEGER NOT NULL,
+    novelty      INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS scores_agent ON scores (agent, round_number);
+CREATE INDEX IF NOT EXISTS scores_round ON scores (round_number);
+
+CREATE TABLE IF NOT EXISTS rounds (
+    round_number           INTEGER PRIMARY KEY,
+    generation             INTEGER NOT NULL,
+    participants           INTEGER NOT NULL,
+    best_fitness           INTEGER NOT NULL,
+    average_fitness        INTEGER NOT NULL,
+    worst_fitness          INTEGER NOT NULL,
+    fitness_variance       INTEGER NOT NULL,
+    lower_quartile_fitness INTEGER NOT NULL,
+    median_fitness         INTEGER NOT NULL,
+    upper_quartile_fitness INTEGER NOT NULL,
+    histogram              TEXT NOT NULL, -- JSON array of bucket counts
+    genome_diversity       INTEGER NOT NULL,
+    agents_skipped         INTEGER NOT NULL,
+    signature              TEXT NOT NULL,
+    slot                   INTEGER NOT NULL
+);
+
+-- One row per closed generation.
+CREATE TABLE IF NOT EXISTS generations (
+    generation      INTEGER PRIMARY KEY,
+    next_generation INTEGER NOT NULL,
+    active_agents   INTEGER NOT NULL,
+    signature       TEXT NOT NULL,
+    slot            INTEGER NOT NULL
+);
+";
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn key(pubkey: &Pubkey) -> String {
+    pubkey.to_string()
+}
+
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> rusqlite::Result<Self> {
+        let version: i64 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version > SCHEMA_VERSION {
+            return Err(rusqlite::Error::InvalidParameterName(format!(
+                "database schema version {version} is newer than {SCHEMA_VERSION}"
+            )));
+        }
+        conn.execute_batch(SCHEMA)?;
+        conn.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        Ok(Self { conn })
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    pub fn is_indexed(&self, signature: &str) -> rusqlite::Result<bool> {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM transactions WHERE signature = ?1",
+                [signature],
+                |_| Ok(()),
+            )
+            .optional()
+            .map(|row| row.is_some())
+    }
+
+    /// Where polling resumes: every transaction up to this signature has
+    /// been indexed. None before the first poll.
+    pub fn poll_tip(&self) -> rusqlite::Result<Option<String>> {
+        self.conn
+            .query_row("SELECT signature FROM poll_tip WHERE id = 0", [], |row| {
+                row.get(0)
+            })
+            .optional()
+    }
+
+    pub fn set_poll_tip(&mut self, signature: &str) -> rusqlite::Result<()> {
+        self.conn.execute(
+            "INSERT INTO poll_tip (id, signature) VALUES (0, ?1)
+             ON CONFLICT (id) DO UPDATE SET signature = excluded.signature",
+            [signature],
+        )?;
+        Ok(())
+    }
+
+    /// Store the saea events in `tx`. Returns false, writing nothing, if the
+    /// signature was already indexed.
+    pub fn ingest(&mut self, tx: &TransactionLogs) -> rusqlite::Result<bool> {
+        if self.is_indexed(&tx.signature)? {
+            return Ok(false);
+        }
+        let events = if tx.failed {
+            Vec::new()
+        } else {
+            parse_logs(&tx.logs)
+        };
+
+        let sql = self.conn.transaction()?;
+        sql.execute(
+            "INSERT INTO transactions (signature, slot, block_time, failed, event_count)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                tx.signature,
+                tx.slot,
+                tx.block_time,
+                tx.failed,
+                events.len()
+            ],
+        )?;
+        for (index, event) in events.iter().enumerate() {
+            insert_event(&sql, &tx.signature, tx.slot, index, event)?;
+        }
+        sql.commit()?;
+        Ok(true)
+    }
+}
+
+fn insert_event(
+    sql: &Connection,
+    signature: &str,
+    slot: u64,
+    index: usize,
+    event: &SaeaEvent,
+) -> rusqlite::Result<()> {
+    match event {
+        SaeaEvent::AgentRegistered(e) => {
+            sql.execute(
+                "INSERT INTO agents (agent, owner, registered_generation, registered_signature)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (agent) DO UPDATE SET
+                     owner = excluded.owner,
+                     registered_generation = excluded.registered_generation,
+                     registered_signature = excluded.registered_signature",
+                params![key(&e.agent), key(&e.owner), e.generation, signature],
+            )?;
+            sql.execute(
+                "INSERT OR IGNORE INTO genomes
+                     (signature, event_index, agent, genome_hash, generation, mutation_count, parent, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, 0, NULL, ?6)",
+                params![signature, index, key(&e.agent), hex(&e.genome_hash), e.generation, slot],
+            )?;
+        }
+        SaeaEvent::GenomeSubmitted(e) => {
+            sql.execute(
+                "INSERT OR IGNORE INTO genomes
+                     (signature, event_index, agent, genome_hash, generation, mutation_count, parent, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    signature,
+                    index,
+                    key(&e.agent),
+                    hex(&e.genome_hash),
+                    e.generation,
+                    e.mutation_count,
+                    key(&e.parent),
+                    slot
+                ],
+            )?;
+        }
+        SaeaEvent::AgentScored(e) => {
+            let b = &e.breakdown;
+            sql.execute(
+                "INSERT OR IGNORE INTO scores
+                     (signature, event_index, agent, round_number, fitness, genome_hash, species,
+                      proximity, diversity, balance, pattern, sequence, novelty, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+                params![
+                    signature,
+                    index,
+                    key(&e.agent),
+                    e.round_number,
+                    e.fitness,
+                    hex(&e.genome_hash),
+                    e.species,
+                    b.proximity,
+                    b.diversity,
+                    b.balance,
+                    b.pattern,
+                    b.sequence,
+                    b.novelty,
+                    slot
+                ],
+            )?;
+        }
+        SaeaEvent::RoundCompleted(e) => {
+            let histogram = serde_json::to_string(&e.histogram).expect("histogram serializes");
+            sql.execute(
+                "INSERT OR IGNORE INTO rounds
+                     (round_number, generation, participants, best_fitness, average_fitness,
+                      worst_fitness, fitness_variance, lower_quartile_fitness, median_fitness,
+                      upper_quartile_fitness, histogram, genome_diversity, agents_skipped,
+                      signature, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+                params![
+                    e.round_number,
+                    e.generation,
+                    e.participants,
+                    e.best_fitness,
+                    e.average_fitness,
+                    e.worst_fitness,
+                    e.fitness_variance,
+                    e.lower_quartile_fitness,
+                    e.median_fitness,
+                    e.upper_quartile_fitness,
+                    histogram,
+                    e.genome_diversity,
+                    e.agents_skipped,
+                    signature,
+                    slot
+                ],
+            )?;
+        }
+        SaeaEvent::AgentPruned(e) => {
+            sql.execute(
+                "INSERT INTO agents (agent, pruned_generation, pruned_fitness, pruned_signature)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (agent) DO UPDATE SET
+                     pruned_generation = excluded.pruned_generation,
+                     pruned_fitness = excluded.pruned_fitness,
+                     pruned_signature = excluded.pruned_signature",
+                params![key(&e.agent), e.generation, e.fitness, signature],
+            )?;
+        }
+        SaeaEvent::GenerationAdvanced(e) => {
+            sql.execute(
+                "INSERT OR IGNORE INTO generations
+                     (generation, next_generation, active_agents, signature, slot)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    e.old_generation,
+                    e.new_generation,
+                    e.active_agents,
+                    signature,
+                    slot
+                ],
+            )?;
+        }
+        // Configuration and secondary events are left in the logs.
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum IndexerError {
+    /// The RPC node could not be reached or returned an error.
+    Rpc(String),
+    /// The index database could not be opened or written.
+    Db(rusqlite::Error),
+}
+
+impl fmt::Display for IndexerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexerError::Rpc(e) => write!(f, "rpc: {e}"),
+            IndexerError::Db(e) => write!(f, "db: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for IndexerError {}
+
+impl From<rusqlite::Error> for IndexerError {
+    fn from(e: rusqlite::Error) -> Self {
+        IndexerError::Db(e)
+    }
+}
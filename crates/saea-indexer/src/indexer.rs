@@ -0,0 +1,105 @@
+use crate::db::Database;
+use crate::error::IndexerError;
+use crate::rpc::{RpcClient, SignatureInfo, SIGNATURE_PAGE};
+
+/// Counts from one pass over a batch of signatures.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IngestSummary {
+    pub ingested: usize,
+    pub skipped: usize,
+    /// Signatures left unindexed because the node did not return the
+    /// first of them (not yet available, or no longer kept). The next pass
+    /// retries from there.
+    pub missing: usize,
+}
+
+pub struct Indexer {
+    rpc: RpcClient,
+    db: Database,
+}
+
+impl Indexer {
+    pub fn new(rpc: RpcClient, db: Database) -> Self {
+        Self { rpc, db }
+    }
+
+    /// Program signatures newer than `until` (all of them when None),
+    /// oldest first, stopping after `limit` when given.
+    fn history(
+        &self,
+        until: Option<&str>,
+        limit: Option<usize>,
+    ) -> Result<Vec<SignatureInfo>, IndexerError> {
+        let mut signatures: Vec<SignatureInfo> = Vec::new();
+        loop {
+            let before = signatures.last().map(|s| s.signature.as_str());
+            let page = self.rpc.signatures_for_address(&saea::ID, before, until)?;
+            let exhausted = page.len() < SIGNATURE_PAGE;
+            signatures.extend(page);
+            if let Some(limit) = limit {
+                if signatures.len() >= limit {
+                    signatures.truncate(limit);
+                    break;
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+        signatures.reverse();
+        Ok(signatures)
+    }
+
+    /// Fetch and store each signature in order, skipping indexed ones
+    /// without a request. Stops at the first transaction the node does not
+    /// return: polling resumes after the newest stored signature, so
+    /// storing anything past a gap would skip it for good.
+    pub fn ingest_signatures<S: AsRef<str>>(
+        &mut self,
+        signatures: &[S],
+    ) -> Result<IngestSummary, IndexerError> {
+        let mut summary = IngestSummary::default();
+        for (i, signature) in signatures.iter().map(AsRef::as_ref).enumerate() {
+            if self.db.is_indexed(signature)? {
+                summary.skipped += 1;
+                continue;
+            }
+            match self.rpc.transaction(signature)? {
+                Some(tx) => {
+                    if self.db.ingest(&tx)? {
+                        summary.ingested += 1;
+                    } else {
+                        summary.skipped += 1;
+                    }
+                }
+                None => {
+                    summary.missing = signatures.len() - i;
+                    break;
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Index every program transaction since the poll tip, then advance the
+    /// tip to the newest one indexed before any gap.
+    pub fn poll(&mut self) -> Result<IngestSummary, IndexerError> {
+        let until = self.db.poll_tip()?;
+        let signatures = self.history(until.as_deref(), None)?;
+        let signatures: Vec<String> = signatures.into_iter().map(|s| s.signature).collect();
+        let summary = self.ingest_signatures(&signatures)?;
+        if let Some(tip) = signatures[..signatures.len() - summary.missing].last() {
+            self.db.set_poll_tip(tip)?;
+        }
+        Ok(summary)
+    }
+
+    /// Index the program's full history, or its latest `limit`
+    /// transactions, regardless of what is already stored. Leaves the poll
+    /// tip alone.
+    pub fn backfill(&mut self, limit: Option<usize>) -> Result<IngestSummary, IndexerError> {
+        let signatures = self.history(None, limit)?;
+        let signatures: Vec<String> = signatures.into_iter().map(|s| s.signature).collect();
+        self.ingest_signatures(&signatures)
+    }
+}
@@ -0,0 +1,8 @@
+//! Event indexer for the saea program: reads the program's transaction
+//! history over JSON-RPC, decodes its events from the logs and stores them
+//! in SQLite.
+
+pub mod db;
+pub mod error;
+pub mod indexer;
+pub mod rpc;
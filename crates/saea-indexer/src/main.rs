@@ -0,0 +1,91 @@
+use clap::{Parser, Subcommand};
+use saea_indexer::db::Database;
+use saea_indexer::error::IndexerError;
+use saea_indexer::indexer::{Indexer, IngestSummary};
+use saea_indexer::rpc::RpcClient;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread::sleep;
+use std::time::Duration;
+
+/// Index saea program events from transaction logs into SQLite.
+#[derive(Parser)]
+#[command(name = "saea-indexer")]
+struct Args {
+    #[arg(long, env = "RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+    /// SQLite database, created if missing.
+    #[arg(long, default_value = "saea-index.db")]
+    db: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Follow the program, indexing new transactions as they land.
+    Poll {
+        /// Seconds between polls.
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Poll once and exit.
+        #[arg(long)]
+        once: bool,
+    },
+    /// Index past transactions: the given signatures, or else the
+    /// program's whole history.
+    Backfill {
+        signatures: Vec<String>,
+        /// Only the latest N transactions of the history.
+        #[arg(long, conflicts_with = "signatures")]
+        limit: Option<usize>,
+    },
+}
+
+fn report(summary: IngestSummary) {
+    println!(
+        "ingested {}, already indexed {}, missing {}",
+        summary.ingested, summary.skipped, summary.missing
+    );
+}
+
+fn run() -> Result<(), IndexerError> {
+    let args = Args::parse();
+    let db = Database::open(&args.db)?;
+    let mut indexer = Indexer::new(RpcClient::new(&args.rpc_url), db);
+
+    match args.command {
+        Command::Poll { interval, once } => loop {
+            match indexer.poll() {
+                Ok(summary) if summary.ingested > 0 || once => report(summary),
+                Ok(_) => {}
+                // Keep following through transient node errors.
+                Err(IndexerError::Rpc(e)) if !once => eprintln!("rpc: {e}"),
+                Err(e) => return Err(e),
+            }
+            if once {
+                return Ok(());
+            }
+            sleep(Duration::from_secs(interval));
+        },
+        Command::Backfill { signatures, limit } => {
+            let summary = if signatures.is_empty() {
+                indexer.backfill(limit)?
+            } else {
+                indexer.ingest_signatures(&signatures)?
+            };
+            report(summary);
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Indexer fatal error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Minimal blocking JSON-RPC client covering the two history calls the
+//! indexer makes.
+
+use crate::error::IndexerError;
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Value};
+
+const COMMITMENT: &str = "confirmed";
+
+/// Most signatures `getSignaturesForAddress` returns per call.
+pub const SIGNATURE_PAGE: usize = 1000;
+
+/// One entry of `getSignaturesForAddress`.
+#[derive(Clone, Debug)]
+pub struct SignatureInfo {
+    pub signature: String,
+    pub slot: u64,
+}
+
+/// The parts of a fetched transaction the indexer stores.
+#[derive(Clone, Debug)]
+pub struct TransactionLogs {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    /// Failed transactions are recorded but their events are ignored.
+    pub failed: bool,
+    pub logs: Vec<String>,
+}
+
+pub struct RpcClient {
+    url: String,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    fn call(&self, method: &str, params: Value) -> Result<Value, IndexerError> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let mut response = ureq::post(&self.url)
+            .send_json(&request)
+            .map_err(|e| IndexerError::Rpc(format!("{method}: {e}")))?;
+        let mut body: Value = response
+            .body_mut()
+            .read_json()
+            .map_err(|e| IndexerError::Rpc(format!("{method}: {e}")))?;
+        if let Some(error) = body.get("error") {
+            return Err(IndexerError::Rpc(format!("{method}: {error}")));
+        }
+        Ok(body["result"].take())
+    }
+
+    /// Signatures touching `address`, newest first, strictly older than
+    /// `before` and newer than `until` when given.
+    pub fn signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before: Option<&str>,
+        until: Option<&str>,
+    ) -> Result<Vec<SignatureInfo>, IndexerError> {
+        let mut config = json!({ "limit": SIGNATURE_PAGE, "commitment": COMMITMENT });
+        if let Some(before) = before {
+            config["before"] = json!(before);
+        }
+        if let Some(until) = until {
+            config["until"] = json!(until);
+        }
+        let result = self.call(
+            "getSignaturesForAddress",
+            json!([address.to_string(), config]),
+        )?;
+        let entries = result.as_array().ok_or_else(|| {
+            IndexerError::Rpc("getSignaturesForAddress: expected an array".into())
+        })?;
+        entries
+            .iter()
+            .map(|entry| {
+                Ok(SignatureInfo {
+                    signature: entry["signature"]
+                        .as_str()
+                        .ok_or_else(|| {
+                            IndexerError::Rpc("getSignaturesForAddress: missing signature".into())
+                        })?
+                        .to_string(),
+                    slot: entry["slot"].as_u64().unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// The transaction's slot, status and log messages, or None if the node
+    /// does not have it.
+    pub fn transaction(&self, signature: &str) -> Result<Option<TransactionLogs>, IndexerError> {
+        let result = self.call(
+            "getTransaction",
+            json!([signature, {
+                "encoding": "json",
+                "commitment": COMMITMENT,
+                "maxSupportedTransactionVersion": 0,
+            }]),
+        )?;
+        if result.is_null() {
+            return Ok(None);
+        }
+        let meta = &result["meta"];
+        Ok(Some(TransactionLogs {
+            signature: signature.to_string(),
+            slot: result["slot"].as_u64().unwrap_or_default(),
+            block_time: result["blockTime"].as_i64(),
+            failed: !meta["err"].is_null(),
+            logs: meta["logMessages"]
+                .as_array()
+                .map(|lines| {
+                    lines
+                        .iter()
+                        .filter_map(|line| line.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }))
+    }
+}
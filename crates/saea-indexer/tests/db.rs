@@ -0,0 +1,237 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use saea::events::{
+    AgentPruned, AgentRegistered, AgentScored, GenerationAdvanced, GenomeSubmitted, RoundCompleted,
+};
+use saea::state::{FitnessBreakdown, HISTOGRAM_BUCKETS};
+use saea_indexer::db::Database;
+use saea_indexer::rpc::TransactionLogs;
+
+/// Logs of one saea instruction emitting `events`.
+fn logs(events: &[Vec<u8>]) -> Vec<String> {
+    let mut logs = vec![format!("Program {} invoke [1]", saea::ID)];
+    logs.extend(
+        events
+            .iter()
+            .map(|data| format!("Program data: {}", STANDARD.encode(data))),
+    );
+    logs.push(format!("Program {} success", saea::ID));
+    logs
+}
+
+fn tx(signature: &str, slot: u64, events: &[Vec<u8>]) -> TransactionLogs {
+    TransactionLogs {
+        signature: signature.to_string(),
+        slot,
+        block_time: Some(1_700_000_000 + slot as i64),
+        failed: false,
+        logs: logs(events),
+    }
+}
+
+fn count(db: &Database, table: &str) -> i64 {
+    db.connection()
+        .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+}
+
+struct History {
+    agent: Pubkey,
+    owner: Pubkey,
+    transactions: Vec<TransactionLogs>,
+}
+
+/// Register, resubmit, score, complete the round, prune and advance.
+fn history() -> History {
+    let agent = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let registered = AgentRegistered {
+        agent,
+        owner,
+        genome_hash: [1; 32],
+        generation: 0,
+    }
+    .data();
+    let submitted = GenomeSubmitted {
+        agent,
+        genome_hash: [2; 32],
+        generation: 0,
+        mutation_count: 1,
+        parent: agent,
+    }
+    .data();
+    let scored = AgentScored {
+        agent,
+        round_number: 1,
+        fitness: 4200,
+        genome_hash: [2; 32],
+        species: 0,
+        breakdown: FitnessBreakdown {
+            proximity: 3000,
+            diversity: 400,
+            balance: 300,
+            pattern: 400,
+            sequence: 100,
+            novelty: 0,
+        },
+    }
+    .data();
+    let completed = RoundCompleted {
+        round_number: 1,
+        generation: 0,
+        participants: 1,
+        best_fitness: 4200,
+        average_fitness: 4200,
+        worst_fitness: 4200,
+        fitness_variance: 0,
+        lower_quartile_fitness: 4200,
+        median_fitness: 4200,
+        upper_quartile_fitness: 4200,
+        histogram: [0; HISTOGRAM_BUCKETS],
+        genome_diversity: 0,
+        agents_skipped: 0,
+    }
+    .data();
+    let pruned = AgentPruned {
+        agent,
+        fitness: 4200,
+        generation: 0,
+    }
+    .data();
+    let advanced = GenerationAdvanced {
+        old_generation: 0,
+        new_generation: 1,
+        active_agents: 0,
+    }
+    .data();
+
+    History {
+        agent,
+        owner,
+        transactions: vec![
+            tx("register", 10, &[registered]),
+            tx("submit", 11, &[submitted]),
+            tx("score", 12, &[scored]),
+            tx("complete", 13, &[completed]),
+            tx("prune-and-advance", 14, &[pruned, advanced]),
+        ],
+    }
+}
+
+fn ingest_all(db: &mut Database, transactions: &[TransactionLogs]) -> usize {
+    transactions
+        .iter()
+        .filter(|tx| db.ingest(tx).unwrap())
+        .count()
+}
+
+#[test]
+fn ingests_each_event_table() {
+    let History {
+        agent,
+        owner,
+        transactions,
+    } = history();
+    let mut db = Database::open_in_memory().unwrap();
+    assert_eq!(ingest_all(&mut db, &transactions), 5);
+
+    assert_eq!(count(&db, "transactions"), 5);
+    assert_eq!(count(&db, "agents"), 1);
+    assert_eq!(count(&db, "genomes"), 2);
+    assert_eq!(count(&db, "scores"), 1);
+    assert_eq!(count(&db, "rounds"), 1);
+    assert_eq!(count(&db, "generations"), 1);
+
+    let (stored_owner, pruned_generation): (String, i64) = db
+        .connection()
+        .query_row(
+            "SELECT owner, pruned_generation FROM agents WHERE agent = ?1",
+            [agent.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(stored_owner, owner.to_string());
+    assert_eq!(pruned_generation, 0);
+
+    let (fitness, proximity): (i64, i64) = db
+        .connection()
+        .query_row(
+            "SELECT fitness, proximity FROM scores WHERE round_number = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!((fitness, proximity), (4200, 3000));
+    // Only polling moves the poll tip.
+    assert_eq!(db.poll_tip().unwrap(), None);
+}
+
+#[test]
+fn reingestion_is_idempotent() {
+    let transactions = history().transactions;
+    let mut db = Database::open_in_memory().unwrap();
+    ingest_all(&mut db, &transactions);
+    assert_eq!(ingest_all(&mut db, &transactions), 0);
+
+    for (table, rows) in [
+        ("transactions", 5),
+        ("agents", 1),
+        ("genomes", 2),
+        ("scores", 1),
+        ("rounds", 1),
+        ("generations", 1),
+    ] {
+        assert_eq!(count(&db, table), rows, "{table}");
+    }
+}
+
+#[test]
+fn order_of_ingestion_does_not_matter() {
+    let History {
+        agent,
+        transactions,
+        ..
+    } = history();
+    let mut db = Database::open_in_memory().unwrap();
+    let reversed: Vec<_> = transactions.into_iter().rev().collect();
+    ingest_all(&mut db, &reversed);
+
+    let (owner, pruned_fitness): (Option<String>, Option<i64>) = db
+        .connection()
+        .query_row(
+            "SELECT owner, pruned_fitness FROM agents WHERE agent = ?1",
+            [agent.to_string()],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .unwrap();
+    assert!(owner.is_some());
+    assert_eq!(pruned_fitness, Some(4200));
+    // Only polling moves the poll tip.
+    assert_eq!(db.poll_tip().unwrap(), None);
+}
+
+#[test]
+fn failed_transactions_are_recorded_without_events() {
+    let mut failed = history().transactions.remove(0);
+    failed.failed = true;
+    let mut db = Database::open_in_memory().unwrap();
+    assert!(db.ingest(&failed).unwrap());
+    assert!(db.is_indexed("register").unwrap());
+    assert_eq!(count(&db, "agents"), 0);
+    assert_eq!(count(&db, "genomes"), 0);
+}
+
+#[test]
+fn other_programs_events_are_ignored() {
+    let mut tx = history().transactions.remove(0);
+    for line in &mut tx.logs {
+        *line = line.replace(&saea::ID.to_string(), &Pubkey::new_unique().to_string());
+    }
+    let mut db = Database::open_in_memory().unwrap();
+    db.ingest(&tx).unwrap();
+    assert_eq!(count(&db, "agents"), 0);
+}
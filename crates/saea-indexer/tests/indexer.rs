@@ -0,0 +1,148 @@
+use saea_indexer::db::Database;
+use saea_indexer::indexer::Indexer;
+use saea_indexer::rpc::RpcClient;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Program history as a node sees it, oldest first: each signature with
+/// whether `getTransaction` returns it yet.
+type Ledger = Arc<Mutex<Vec<(String, bool)>>>;
+
+fn respond(ledger: &Ledger, request: &Value) -> Value {
+    let ledger = ledger.lock().unwrap();
+    let params = &request["params"];
+    let result = match request["method"].as_str() {
+        Some("getSignaturesForAddress") => {
+            let position = |key: &str| {
+                params[1][key]
+                    .as_str()
+                    .and_then(|s| ledger.iter().position(|(signature, _)| signature == s))
+            };
+            let newest = position("before").unwrap_or(ledger.len());
+            let oldest = position("until").map_or(0, |i| i + 1);
+            let entries: Vec<Value> = (oldest..newest)
+                .rev()
+                .map(|slot| json!({ "signature": ledger[slot].0, "slot": slot }))
+                .collect();
+            json!(entries)
+        }
+        Some("getTransaction") => {
+            let signature = params[0].as_str().unwrap();
+            match ledger.iter().position(|(s, _)| s == signature) {
+                Some(slot) if ledger[slot].1 => json!({
+                    "slot": slot,
+                    "blockTime": null,
+                    "meta": { "err": null, "logMessages": [] },
+                }),
+                _ => Value::Null,
+            }
+        }
+        method => panic!("unexpected {method:?}"),
+    };
+    json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+}
+
+/// Answer JSON-RPC requests on one keep-alive connection until it closes.
+fn serve(stream: TcpStream, ledger: Ledger) {
+    let mut reader = BufReader::new(stream.try_clone().unwrap());
+    let mut stream = stream;
+    loop {
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                return;
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+        let request: Value = serde_json::from_slice(&body).unwrap();
+        let response = respond(&ledger, &request).to_string();
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+    }
+}
+
+/// A fake node serving `ledger`, and a client pointed at it.
+fn node(ledger: &Ledger) -> RpcClient {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let ledger = ledger.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let ledger = ledger.clone();
+            thread::spawn(move || serve(stream.unwrap(), ledger));
+        }
+    });
+    RpcClient::new(url)
+}
+
+fn ledger(entries: &[(&str, bool)]) -> Ledger {
+    Arc::new(Mutex::new(
+        entries
+            .iter()
+            .map(|&(signature, available)| (signature.to_string(), available))
+            .collect(),
+    ))
+}
+
+#[test]
+fn poll_retries_transactions_not_yet_returned() {
+    let ledger = ledger(&[("a", true), ("b", false), ("c", true)]);
+    let mut indexer = Indexer::new(node(&ledger), Database::open_in_memory().unwrap());
+
+    let summary = indexer.poll().unwrap();
+    assert_eq!((summary.ingested, summary.missing), (1, 2));
+
+    ledger.lock().unwrap()[1].1 = true;
+    let summary = indexer.poll().unwrap();
+    assert_eq!((summary.ingested, summary.missing), (2, 0));
+    let summary = indexer.poll().unwrap();
+    assert_eq!((summary.ingested, summary.missing), (0, 0));
+}
+
+#[test]
+fn backfill_stops_at_the_first_gap() {
+    let ledger = ledger(&[("a", false), ("b", true)]);
+    let mut indexer = Indexer::new(node(&ledger), Database::open_in_memory().unwrap());
+
+    let summary = indexer.backfill(None).unwrap();
+    assert_eq!((summary.ingested, summary.missing), (0, 2));
+
+    ledger.lock().unwrap()[0].1 = true;
+    let summary = indexer.backfill(None).unwrap();
+    assert_eq!((summary.ingested, summary.missing), (2, 0));
+}
+
+#[test]
+fn backfilled_signatures_do_not_move_the_poll_tip() {
+    let ledger = ledger(&[("a", true)]);
+    let mut indexer = Indexer::new(node(&ledger), Database::open_in_memory().unwrap());
+    indexer.poll().unwrap();
+
+    ledger
+        .lock()
+        .unwrap()
+        .extend([("b".to_string(), true), ("c".to_string(), true)]);
+    let summary = indexer.ingest_signatures(&["c"]).unwrap();
+    assert_eq!(summary.ingested, 1);
+
+    let summary = indexer.poll().unwrap();
+    assert_eq!((summary.ingested, summary.skipped), (1, 1));
+}
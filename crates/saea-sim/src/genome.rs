@@ -0,0 +1,129 @@
+//! Genome variation and parent selection, mirroring `agent/src/genome.ts`.
+//! Shared by the simulator and the Rust agent so both breed identically.
+
+use rand::Rng;
+use solana_sha256_hasher::hash;
+use std::str::FromStr;
+
+/// How parents are drawn from the scored population.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionStrategy {
+    /// Best of `tournament_size` uniformly drawn agents.
+    Tournament,
+    /// Probability proportional to fitness.
+    Roulette,
+    /// Uniform among the top half.
+    Truncation,
+}
+
+impl FromStr for SelectionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tournament" => Ok(Self::Tournament),
+            "roulette" => Ok(Self::Roulette),
+            "truncation" => Ok(Self::Truncation),
+            _ => Err(format!("unknown selection strategy: {s}")),
+        }
+    }
+}
+
+/// Scored genome available for breeding.
+#[derive(Clone, Debug)]
+pub struct Candidate {
+    pub genome: Vec<u8>,
+    pub fitness: u64,
+}
+
+pub fn random_genome(rng: &mut impl Rng, length: usize) -> Vec<u8> {
+    (0..length).map(|_| rng.gen()).collect()
+}
+
+/// Each gene mutates with probability `mutation_rate_bps` / 10000, by random
+/// reset, a creep of up to ±20, or a single bit flip.
+pub fn mutate_genome(rng: &mut impl Rng, genome: &[u8], mutation_rate_bps: u16) -> Vec<u8> {
+    let rate = mutation_rate_bps as f64 / 10_000.0;
+    genome
+        .iter()
+        .map(|&gene| {
+            if !rng.gen_bool(rate.min(1.0)) {
+                return gene;
+            }
+            match rng.gen_range(0..3) {
+                0 => rng.gen(),
+                1 => (gene as i16 + rng.gen_range(-20..=20)).clamp(0, 255) as u8,
+                _ => gene ^ (1 << rng.gen_range(0..8)),
+            }
+        })
+        .collect()
+}
+
+/// Two-point crossover: with probability `crossover_rate` the child takes
+/// `parent2`'s genes between two random points, otherwise it copies `parent1`.
+pub fn crossover(
+    rng: &mut impl Rng,
+    parent1: &[u8],
+    parent2: &[u8],
+    crossover_rate: f64,
+) -> Vec<u8> {
+    let length = parent1.len().min(parent2.len());
+    let mut child = parent1[..length].to_vec();
+    if length > 0 && rng.gen_bool(crossover_rate.clamp(0.0, 1.0)) {
+        let (a, b) = (rng.gen_range(0..length), rng.gen_range(0..length));
+        let (start, end) = (a.min(b), a.max(b));
+        child[start..=end].copy_from_slice(&parent2[start..=end]);
+    }
+    child
+}
+
+/// Draw one parent from a non-empty population sorted by descending fitness.
+pub fn select<'a>(
+    rng: &mut impl Rng,
+    population: &'a [Candidate],
+    strategy: SelectionStrategy,
+    tournament_size: usize,
+) -> &'a Candidate {
+    match strategy {
+        SelectionStrategy::Tournament => (0..tournament_size.max(1))
+            .map(|_| &population[rng.gen_range(0..population.len())])
+            .max_by_key(|c| c.fitness)
+            .unwrap(),
+        SelectionStrategy::Roulette => {
+            let total: u64 = population.iter().map(|c| c.fitness).sum();
+            if total == 0 {
+                return &population[rng.gen_range(0..population.len())];
+            }
+            let mut ticket = rng.gen_range(0..total);
+            for candidate in population {
+                if ticket < candidate.fitness {
+                    return candidate;
+                }
+                ticket -= candidate.fitness;
+            }
+            &population[population.len() - 1]
+        }
+        SelectionStrategy::Truncation => {
+            let cutoff = population.len().div_ceil(2);
+            &population[rng.gen_range(0..cutoff)]
+        }
+    }
+}
+
+pub fn genome_hash(genome: &[u8]) -> String {
+    hash(genome)
+        .to_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Raise the rate by 30% (up to 80%) when the best fitness stagnates and
+/// lower it by 20% (down to 10%) when it improves.
+pub fn adapt_mutation_rate(current_rate: u16, previous_best: u64, current_best: u64) -> u16 {
+    if current_best <= previous_best {
+        ((current_rate as u32 * 13 / 10).min(8000)) as u16
+    } else {
+        ((current_rate as u32 * 8 / 10).max(1000)) as u16
+    }
+}
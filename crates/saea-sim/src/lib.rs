@@ -0,0 +1,15 @@
+//! Offline saea arena simulator.
+//!
+//! Runs the register → score → complete → prune → breed → advance cycle in
+//! memory with the program's own `compute_fitness` and `Round` statistics,
+//! so arena parameters can be tuned before they are committed on-chain.
+//!
+//! - [`sim`]: the in-memory arena
+//! - [`genome`]: variation and selection operators, shared with `saea-agent`
+//! - [`output`]: CSV / JSON encodings of `RoundCompleted`
+
+pub mod genome;
+pub mod output;
+pub mod sim;
+
+pub use sim::{SimConfig, Simulator};
@@ -0,0 +1,115 @@
+use clap::Parser;
+use saea_sim::genome::SelectionStrategy;
+use saea_sim::output::{Format, RoundWriter};
+use saea_sim::{SimConfig, Simulator};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::Instant;
+
+/// Simulate a saea arena in memory and print per-round statistics.
+#[derive(Parser)]
+#[command(name = "saea-sim")]
+struct Args {
+    #[arg(long, default_value_t = 8)]
+    population: usize,
+    #[arg(long, default_value_t = 16)]
+    genome_len: usize,
+    #[arg(long, default_value_t = 5)]
+    generations: u64,
+    #[arg(long, default_value_t = 2)]
+    rounds_per_gen: u64,
+    #[arg(long, default_value_t = 64)]
+    max_agents: u64,
+    #[arg(long, default_value_t = 500)]
+    min_fitness_threshold: u64,
+    #[arg(long, default_value_t = 3000)]
+    mutation_rate_bps: u16,
+    /// Keep the mutation rate fixed instead of adapting it each generation.
+    #[arg(long)]
+    fixed_mutation: bool,
+    /// Adapt the arena's mutation rate after every round, as
+    /// `configure_mutation` enables on-chain, and breed with it.
+    #[arg(long)]
+    arena_adaptive_mutation: bool,
+    #[arg(long, default_value_t = 2)]
+    elitism: usize,
+    #[arg(long, default_value_t = 3)]
+    tournament_size: usize,
+    #[arg(long, default_value_t = 0.7)]
+    crossover_rate: f64,
+    /// tournament, roulette or truncation.
+    #[arg(long, default_value = "tournament")]
+    selection: SelectionStrategy,
+    /// Register random agents to replace pruned ones each generation.
+    #[arg(long)]
+    refill: bool,
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[arg(long, value_enum, default_value_t = Format::Csv)]
+    format: Format,
+    /// Write statistics here instead of stdout.
+    #[arg(long)]
+    output: Option<String>,
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let config = SimConfig {
+        population_size: args.population,
+        genome_len: args.genome_len,
+        generations: args.generations,
+        rounds_per_generation: args.rounds_per_gen,
+        max_agents: args.max_agents,
+        min_fitness_threshold: args.min_fitness_threshold,
+        mutation_rate_bps: args.mutation_rate_bps,
+        adaptive_mutation: !args.fixed_mutation,
+        arena_adaptive_mutation: args.arena_adaptive_mutation,
+        elitism_count: args.elitism,
+        tournament_size: args.tournament_size,
+        crossover_rate: args.crossover_rate,
+        selection: args.selection,
+        refill: args.refill,
+        seed: args.seed,
+        ..SimConfig::default()
+    };
+    config.validate()?;
+
+    let out: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path).map_err(|e| format!("{path}: {e}"))?),
+        None => Box::new(io::stdout().lock()),
+    };
+    let mut writer = RoundWriter::new(BufWriter::new(out), args.format);
+    let mut result = Ok(());
+    let started = Instant::now();
+
+    let mut sim = Simulator::new(config);
+    sim.run(|round| {
+        if result.is_ok() {
+            result = writer.write(round);
+        }
+    });
+    result
+        .and_then(|_| writer.flush())
+        .map_err(|e| e.to_string())?;
+
+    let elapsed = started.elapsed().as_secs_f64();
+    let generations = sim.current_generation() - 1;
+    eprintln!(
+        "{} generations in {:.3}s ({:.0} generations/s), {} active agents",
+        generations,
+        elapsed,
+        generations as f64 / elapsed.max(f64::EPSILON),
+        sim.active_agents()
+    );
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("saea-sim: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! CSV and JSON encodings of `RoundCompleted`, field for field.
+
+use saea::events::RoundCompleted;
+use saea::state::HISTOGRAM_BUCKETS;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// Serializable copy of `RoundCompleted`; the exhaustive destructuring in
+/// `From` stops this compiling if the event gains a field.
+#[derive(Clone, Debug, Serialize)]
+pub struct RoundRecord {
+    pub round_number: u64,
+    pub generation: u64,
+    pub participants: u64,
+    pub best_fitness: u64,
+    pub average_fitness: u64,
+    pub worst_fitness: u64,
+    pub fitness_variance: u64,
+    pub lower_quartile_fitness: u64,
+    pub median_fitness: u64,
+    pub upper_quartile_fitness: u64,
+    pub histogram: [u32; HISTOGRAM_BUCKETS],
+    pub genome_diversity: u64,
+    pub agents_skipped: u64,
+}
+
+impl From<&RoundCompleted> for RoundRecord {
+    fn from(event: &RoundCompleted) -> Self {
+        let RoundCompleted {
+            round_number,
+            generation,
+            participants,
+            best_fitness,
+            average_fitness,
+            worst_fitness,
+            fitness_variance,
+            lower_quartile_fitness,
+            median_fitness,
+            upper_quartile_fitness,
+            histogram,
+            genome_diversity,
+            agents_skipped,
+        } = *event;
+        Self {
+            round_number,
+            generation,
+            participants,
+            best_fitness,
+            average_fitness,
+            worst_fitness,
+            fitness_variance,
+            lower_quartile_fitness,
+            median_fitness,
+            upper_quartile_fitness,
+            histogram,
+            genome_diversity,
+            agents_skipped,
+        }
+    }
+}
+
+impl RoundRecord {
+    /// CSV header; the histogram spreads over `histogram_0..histogram_15`.
+    pub fn csv_header() -> String {
+        let mut columns = vec![
+            "round_number".to_string(),
+            "generation".into(),
+            "participants".into(),
+            "best_fitness".into(),
+            "average_fitness".into(),
+            "worst_fitness".into(),
+            "fitness_variance".into(),
+            "lower_quartile_fitness".into(),
+            "median_fitness".into(),
+            "upper_quartile_fitness".into(),
+        ];
+        columns.extend((0..HISTOGRAM_BUCKETS).map(|i| format!("histogram_{i}")));
+        columns.push("genome_diversity".into());
+        columns.push("agents_skipped".into());
+        columns.join(",")
+    }
+
+    pub fn csv_row(&self) -> String {
+        let mut values = vec![
+            self.round_number,
+            self.generation,
+            self.participants,
+            self.best_fitness,
+            self.average_fitness,
+            self.worst_fitness,
+            self.fitness_variance,
+            self.lower_quartile_fitness,
+            self.median_fitness,
+            self.upper_quartile_fitness,
+        ];
+        values.extend(self.histogram.iter().map(|&c| c as u64));
+        values.push(self.genome_diversity);
+        values.push(self.agents_skipped);
+        values
+            .iter()
+            .map(u64::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    Csv,
+    /// One JSON object per line.
+    Json,
+}
+
+/// Streams round records in the chosen format.
+pub struct RoundWriter<W: Write> {
+    out: W,
+    format: Format,
+    wrote_header: bool,
+}
+
+impl<W: Write> RoundWriter<W> {
+    pub fn new(out: W, format: Format) -> Self {
+        Self {
+            out,
+            format,
+            wrote_header: false,
+        }
+    }
+
+    pub fn write(&mut self, event: &RoundCompleted) -> io::Result<()> {
+        let record = RoundRecord::from(event);
+        match self.format {
+            Format::Csv => {
+                if !self.wrote_header {
+                    writeln!(self.out, "{}", RoundRecord::csv_header())?;
+                    self.wrote_header = true;
+                }
+                writeln!(self.out, "{}", record.csv_row())
+            }
+            Format::Json => {
+                serde_json::to_writer(&mut self.out, &record)?;
+                writeln!(self.out)
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.out.flush()
+    }
+}
@@ -0,0 +1,331 @@
+//! In-memory arena following the program's round lifecycle: agents are
+//! scored with `compute_fitness` into a real `Round`, rounds complete with
+//! `Round::finalize` and `Arena::adapt_mutation_rate` as in `complete_round`,
+//! weak agents are pruned against `min_fitness_threshold` as `prune_agent`
+//! allows, and survivors breed between generations. The arena's counters
+//! start where `initialize_arena` leaves them, in generation 1.
+
+use crate::genome::{
+    adapt_mutation_rate, crossover, mutate_genome, random_genome, select, Candidate,
+    SelectionStrategy,
+};
+use anchor_lang::prelude::Pubkey;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use saea::events::RoundCompleted;
+use saea::instructions::compute_fitness_breakdown_with_targets;
+use saea::state::{Arena, Round, RoundKind, MAX_GENOME_LEN};
+use solana_sha256_hasher::hashv;
+
+#[derive(Clone, Debug)]
+pub struct SimConfig {
+    pub population_size: usize,
+    pub genome_len: usize,
+    pub generations: u64,
+    pub rounds_per_generation: u64,
+    pub max_agents: u64, // cap on active agents, as enforced by register_agent
+    pub min_fitness_threshold: u64,
+    pub mutation_rate_bps: u16,
+    pub adaptive_mutation: bool, // adapt the breeding rate like the agent does
+    pub arena_adaptive_mutation: bool, // adapt the arena's rate each round and breed with it
+    pub elitism_count: usize,
+    pub tournament_size: usize,
+    pub crossover_rate: f64,
+    pub selection: SelectionStrategy,
+    pub refill: bool, // register random agents to replace pruned ones
+    pub histogram_base: u64,
+    pub histogram_bucket_width: u64,
+    pub seed: u64, // drives both round seeds and breeding
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 8,
+            genome_len: 16,
+            generations: 5,
+            rounds_per_generation: 2,
+            max_agents: 64,
+            min_fitness_threshold: 500,
+            mutation_rate_bps: 3000,
+            adaptive_mutation: true,
+            arena_adaptive_mutation: false,
+            elitism_count: 2,
+            tournament_size: 3,
+            crossover_rate: 0.7,
+            selection: SelectionStrategy::Tournament,
+            refill: false,
+            histogram_base: 0,
+            histogram_bucket_width: Arena::DEFAULT_BUCKET_WIDTH,
+            seed: 0,
+        }
+    }
+}
+
+impl SimConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.population_size == 0 || self.population_size as u64 > self.max_agents {
+            return Err("population size must be between 1 and max agents".into());
+        }
+        if self.genome_len == 0 || self.genome_len > MAX_GENOME_LEN {
+            return Err(format!(
+                "genome length must be between 1 and {MAX_GENOME_LEN}"
+            ));
+        }
+        if self.mutation_rate_bps > 10_000 {
+            return Err("mutation rate must be at most 10000 bps".into());
+        }
+        if !(0.0..=1.0).contains(&self.crossover_rate) {
+            return Err("crossover rate must be between 0 and 1".into());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SimAgent {
+    pub key: Pubkey, // synthetic, unique per registration
+    pub genome: Vec<u8>,
+    pub fitness: u64,
+    pub generation: u64,
+    pub mutation_count: u64,
+    pub rounds_participated: u64,
+    pub total_fitness: u64,
+    pub is_active: bool,
+}
+
+pub struct Simulator {
+    config: SimConfig,
+    rng: StdRng,
+    arena_key: Pubkey,
+    arena: Arena,
+    agents: Vec<SimAgent>,
+    mutation_rate_bps: u16,
+    previous_best_fitness: u64,
+}
+
+impl Simulator {
+    pub fn new(config: SimConfig) -> Self {
+        let rng = StdRng::seed_from_u64(config.seed);
+        let arena_key =
+            Pubkey::new_from_array(hashv(&[b"saea-sim", &config.seed.to_le_bytes()]).to_bytes());
+        let arena = Arena {
+            histogram_base: config.histogram_base,
+            histogram_bucket_width: config.histogram_bucket_width,
+            adaptive_mutation: config.arena_adaptive_mutation,
+            ..Arena::new(
+                Pubkey::default(),
+                config.max_agents,
+                config.min_fitness_threshold,
+                config.mutation_rate_bps,
+                0,
+            )
+        };
+        let mut sim = Self {
+            mutation_rate_bps: config.mutation_rate_bps,
+            config,
+            rng,
+            arena_key,
+            arena,
+            agents: Vec::new(),
+            previous_best_fitness: 0,
+        };
+        sim.register_up_to_population();
+        sim
+    }
+
+    pub fn arena_key(&self) -> Pubkey {
+        self.arena_key
+    }
+
+    pub fn agents(&self) -> &[SimAgent] {
+        &self.agents
+    }
+
+    pub fn active_agents(&self) -> usize {
+        self.agents.iter().filter(|a| a.is_active).count()
+    }
+
+    pub fn arena(&self) -> &Arena {
+        &self.arena
+    }
+
+    /// Rate children are bred with: the arena's while it adapts, otherwise
+    /// the agent-style rate adapted per generation.
+    pub fn mutation_rate_bps(&self) -> u16 {
+        if self.arena.adaptive_mutation {
+            self.arena.mutation_rate_bps
+        } else {
+            self.mutation_rate_bps
+        }
+    }
+
+    pub fn current_generation(&self) -> u64 {
+        self.arena.current_generation
+    }
+
+    fn register_up_to_population(&mut self) {
+        let target = self
+            .config
+            .population_size
+            .min(self.config.max_agents as usize);
+        while self.active_agents() < target {
+            let index = self.agents.len() as u64;
+            let key = Pubkey::new_from_array(
+                hashv(&[self.arena_key.as_ref(), &index.to_le_bytes()]).to_bytes(),
+            );
+            self.agents.push(SimAgent {
+                key,
+                genome: random_genome(&mut self.rng, self.config.genome_len),
+                fitness: 0,
+                generation: self.arena.current_generation,
+                mutation_count: 0,
+                rounds_participated: 0,
+                total_fitness: 0,
+                is_active: true,
+            });
+            self.arena.total_agents += 1;
+            self.arena.active_agents += 1;
+        }
+    }
+
+    /// A fresh round as `start_round` initializes it, with the clock held
+    /// at zero so runs are reproducible.
+    fn start_round(&mut self) -> Round {
+        self.arena.current_round += 1;
+        let round_number = self.arena.current_round;
+        let seed = Round::derive_seed(
+            &self.arena_key,
+            round_number,
+            self.arena.current_generation,
+            0,
+        );
+        Round::new(
+            self.arena_key,
+            &self.arena,
+            round_number,
+            seed,
+            0,
+            RoundKind::Solo,
+            0,
+        )
+    }
+
+    /// Start a round, score every active agent as `score_agent` does and
+    /// complete it as `complete_round` does, adapting the arena's mutation
+    /// rate.
+    pub fn run_round(&mut self) -> RoundCompleted {
+        let mut round = self.start_round();
+        for agent in self.agents.iter_mut().filter(|a| a.is_active) {
+            let fitness =
+                compute_fitness_breakdown_with_targets(&agent.genome, &round.seed, &round.targets)
+                    .total()
+                    .expect("fitness of a valid genome fits in u64");
+            agent.fitness = fitness;
+            agent.rounds_participated += 1;
+            agent.total_fitness += fitness;
+            round
+                .record_score(agent.key, fitness, &agent.genome)
+                .expect("round totals fit in u64");
+        }
+        let completed = round.finalize();
+        if round.participants > 0 {
+            self.arena.adapt_mutation_rate(round.best_fitness);
+        }
+        completed
+    }
+
+    /// Deactivate every agent under `min_fitness_threshold`, as
+    /// `prune_agent` would; this may leave the arena empty. Returns how many
+    /// were pruned.
+    pub fn prune(&mut self) -> usize {
+        let threshold = self.arena.min_fitness_threshold;
+        let mut pruned = 0;
+        for agent in self.agents.iter_mut() {
+            if agent.is_active && agent.fitness < threshold {
+                agent.is_active = false;
+                pruned += 1;
+            }
+        }
+        self.arena.active_agents -= pruned as u64;
+        pruned
+    }
+
+    /// Replace every non-elite survivor's genome with a child of selected
+    /// parents, as the agent does before `advance_generation`.
+    fn breed(&mut self, best_fitness: u64) {
+        if self.config.adaptive_mutation {
+            self.mutation_rate_bps = adapt_mutation_rate(
+                self.mutation_rate_bps,
+                self.previous_best_fitness,
+                best_fitness,
+            );
+        }
+        self.previous_best_fitness = best_fitness;
+        let rate_bps = self.mutation_rate_bps();
+
+        let mut ranked: Vec<usize> = (0..self.agents.len())
+            .filter(|&i| self.agents[i].is_active)
+            .collect();
+        ranked.sort_by(|&a, &b| self.agents[b].fitness.cmp(&self.agents[a].fitness));
+        let population: Vec<Candidate> = ranked
+            .iter()
+            .map(|&i| Candidate {
+                genome: self.agents[i].genome.clone(),
+                fitness: self.agents[i].fitness,
+            })
+            .collect();
+        if population.is_empty() {
+            return;
+        }
+
+        for &i in ranked.iter().skip(self.config.elitism_count) {
+            let parent1 = select(
+                &mut self.rng,
+                &population,
+                self.config.selection,
+                self.config.tournament_size,
+            );
+            let parent2 = select(
+                &mut self.rng,
+                &population,
+                self.config.selection,
+                self.config.tournament_size,
+            );
+            let child = crossover(
+                &mut self.rng,
+                &parent1.genome,
+                &parent2.genome,
+                self.config.crossover_rate,
+            );
+            let agent = &mut self.agents[i];
+            agent.genome = mutate_genome(&mut self.rng, &child, rate_bps);
+            agent.mutation_count += 1;
+            agent.generation = self.arena.current_generation;
+        }
+    }
+
+    /// Run one generation: its rounds, pruning, breeding, optional refill,
+    /// then the generation advance. Each completed round is passed to
+    /// `on_round`.
+    pub fn run_generation(&mut self, mut on_round: impl FnMut(&RoundCompleted)) {
+        let mut best_fitness = 0;
+        for _ in 0..self.config.rounds_per_generation {
+            let completed = self.run_round();
+            best_fitness = best_fitness.max(completed.best_fitness);
+            on_round(&completed);
+        }
+        self.prune();
+        self.breed(best_fitness);
+        if self.config.refill {
+            self.register_up_to_population();
+        }
+        self.arena.current_generation += 1;
+    }
+
+    pub fn run(&mut self, mut on_round: impl FnMut(&RoundCompleted)) {
+        for _ in 0..self.config.generations {
+            self.run_generation(&mut on_round);
+        }
+    }
+}
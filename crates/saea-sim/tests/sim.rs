@@ -0,0 +1,124 @@
+use anchor_lang::{AnchorDeserialize, Discriminator, Event};
+use saea::events::RoundCompleted;
+use saea::instructions::compute_fitness;
+use saea::state::{Arena, Round, RoundKind};
+use saea_sim::{SimConfig, Simulator};
+
+/// Run `config` to the end, keeping every completed round.
+fn rounds(config: SimConfig) -> (Simulator, Vec<RoundCompleted>) {
+    let mut sim = Simulator::new(config);
+    let mut rounds = Vec::new();
+    sim.run(|round| {
+        let data = round.data();
+        let mut body = &data[RoundCompleted::DISCRIMINATOR.len()..];
+        rounds.push(RoundCompleted::deserialize(&mut body).unwrap());
+    });
+    (sim, rounds)
+}
+
+fn encoded(rounds: &[RoundCompleted]) -> Vec<Vec<u8>> {
+    rounds.iter().map(Event::data).collect()
+}
+
+#[test]
+fn same_seed_same_rounds() {
+    let (_, first) = rounds(SimConfig::default());
+    let (_, second) = rounds(SimConfig::default());
+    assert_eq!(encoded(&first), encoded(&second));
+    let (_, other) = rounds(SimConfig {
+        seed: 1,
+        ..SimConfig::default()
+    });
+    assert_ne!(encoded(&first), encoded(&other));
+}
+
+#[test]
+fn rounds_score_every_active_agent() {
+    let config = SimConfig::default();
+    let (sim, rounds) = rounds(config.clone());
+    assert_eq!(
+        rounds.len() as u64,
+        config.generations * config.rounds_per_generation
+    );
+    for (i, round) in rounds.iter().enumerate() {
+        assert_eq!(round.round_number, i as u64 + 1);
+        assert_eq!(round.agents_skipped, 0);
+        assert!(round.worst_fitness <= round.average_fitness);
+        assert!(round.average_fitness <= round.best_fitness);
+        assert_eq!(
+            round.histogram.iter().map(|&n| n as u64).sum::<u64>(),
+            round.participants
+        );
+    }
+    assert_eq!(sim.current_generation(), config.generations + 1);
+}
+
+#[test]
+fn arena_adapts_its_rate_after_every_round() {
+    let config = SimConfig {
+        arena_adaptive_mutation: true,
+        generations: 20,
+        ..SimConfig::default()
+    };
+    let (sim, rounds) = rounds(config.clone());
+
+    let mut arena = Arena {
+        adaptive_mutation: true,
+        ..Arena::new(
+            Default::default(),
+            config.max_agents,
+            config.min_fitness_threshold,
+            config.mutation_rate_bps,
+            0,
+        )
+    };
+    let changes = rounds
+        .iter()
+        .filter(|round| arena.adapt_mutation_rate(round.best_fitness).is_some())
+        .count();
+    assert!(changes > 0);
+    assert_eq!(sim.arena().mutation_rate_bps, arena.mutation_rate_bps);
+    assert_eq!(sim.mutation_rate_bps(), arena.mutation_rate_bps);
+}
+
+#[test]
+fn arena_rate_is_fixed_by_default() {
+    let config = SimConfig::default();
+    let (sim, _) = rounds(config.clone());
+    assert_eq!(sim.arena().mutation_rate_bps, config.mutation_rate_bps);
+}
+
+#[test]
+fn first_round_matches_the_program() {
+    let mut sim = Simulator::new(SimConfig::default());
+    let arena = sim.arena().clone();
+    let genomes: Vec<_> = sim
+        .agents()
+        .iter()
+        .map(|a| (a.key, a.genome.clone()))
+        .collect();
+    let completed = sim.run_round();
+
+    let seed = Round::derive_seed(&sim.arena_key(), 1, 1, 0);
+    let mut round = Round::new(sim.arena_key(), &arena, 1, seed, 0, RoundKind::Solo, 0);
+    for (key, genome) in &genomes {
+        round
+            .record_score(*key, compute_fitness(genome, &seed), genome)
+            .unwrap();
+    }
+    let expected = round.finalize();
+    assert_eq!(expected.generation, 1);
+    assert_eq!(completed.data(), expected.data());
+}
+
+#[test]
+fn pruning_can_empty_the_arena() {
+    let mut sim = Simulator::new(SimConfig {
+        min_fitness_threshold: u64::MAX,
+        ..SimConfig::default()
+    });
+    sim.run_round();
+    assert_eq!(sim.prune(), SimConfig::default().population_size);
+    assert_eq!(sim.active_agents(), 0);
+    assert_eq!(sim.arena().active_agents, 0);
+}
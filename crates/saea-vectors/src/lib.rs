@@ -0,0 +1,242 @@
+//! Golden vectors for the program's `compute_fitness`: genome, seed, fitness
+//! and per-component breakdown, stored as JSON under
+//! `tests/vectors/fitness.json` so implementations outside this workspace
+//! (the TypeScript agent, for one) can be checked against the canonical
+//! scorer.
+//!
+//! `VERSION` names the scoring function the vectors were taken from; bump it
+//! together with any change to `compute_fitness` and regenerate the file with
+//! `cargo run -p saea-vectors`.
+
+use saea::instructions::compute_fitness_breakdown;
+use saea::state::{FitnessBreakdown, MAX_GENOME_LEN};
+use serde::{Deserialize, Serialize};
+use solana_sha256_hasher::hashv;
+use std::fmt;
+
+/// Version of `compute_fitness` (v2: with sequence bonus).
+pub const VERSION: u32 = 2;
+
+/// Vectors drawn from the deterministic input stream, after the edge cases.
+const GENERATED_VECTORS: u8 = 48;
+
+/// Path of the committed file, relative to the repository root.
+pub const VECTORS_PATH: &str = "tests/vectors/fitness.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VectorFile {
+    pub version: u32,
+    pub vectors: Vec<Vector>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Vector {
+    pub genome: Vec<u8>,
+    /// Round seed, hex-encoded.
+    pub seed: String,
+    pub fitness: u64,
+    pub breakdown: Breakdown,
+}
+
+/// `FitnessBreakdown` in the file's field order.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Breakdown {
+    pub proximity: u64,
+    pub diversity: u64,
+    pub balance: u64,
+    pub pattern: u64,
+    pub sequence: u64,
+    pub novelty: u64,
+}
+
+impl From<FitnessBreakdown> for Breakdown {
+    fn from(breakdown: FitnessBreakdown) -> Self {
+        let FitnessBreakdown {
+            proximity,
+            diversity,
+            balance,
+            pattern,
+            sequence,
+            novelty,
+        } = breakdown;
+        Self {
+            proximity,
+            diversity,
+            balance,
+            pattern,
+            sequence,
+            novelty,
+        }
+    }
+}
+
+/// A vector whose stored result differs from `compute_fitness`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    Version {
+        found: u32,
+    },
+    InvalidSeed {
+        index: usize,
+    },
+    Result {
+        index: usize,
+        expected: Box<Vector>,
+        found: Box<Vector>,
+    },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mismatch::Version { found } => {
+                write!(
+                    f,
+                    "vectors are version {found}, scorer is version {VERSION}"
+                )
+            }
+            Mismatch::InvalidSeed { index } => {
+                write!(f, "vector {index}: seed is not 32 hex-encoded bytes")
+            }
+            Mismatch::Result {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "vector {index}: genome {:?} seed {} scores {} {:?}, file has {} {:?}",
+                expected.genome,
+                expected.seed,
+                expected.fitness,
+                expected.breakdown,
+                found.fitness,
+                found.breakdown
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+pub fn encode_seed(seed: &[u8; 32]) -> String {
+    seed.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+pub fn decode_seed(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut seed = [0u8; 32];
+    for (i, byte) in seed.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(seed)
+}
+
+/// Score `genome` under `seed` into a vector.
+pub fn vector(genome: &[u8], seed: &[u8; 32]) -> Vector {
+    let breakdown = compute_fitness_breakdown(genome, seed);
+    Vector {
+        genome: genome.to_vec(),
+        seed: encode_seed(seed),
+        fitness: breakdown.total().expect("fitness fits in u64"),
+        breakdown: breakdown.into(),
+    }
+}
+
+/// Genomes at the edges of the scoring function: one gene, saturated and
+/// empty values, alternating extremes, monotone ramps.
+fn edge_genomes() -> Vec<Vec<u8>> {
+    vec![
+        vec![0],
+        vec![255],
+        vec![128; 16],
+        vec![0; MAX_GENOME_LEN],
+        vec![255; MAX_GENOME_LEN],
+        (0..MAX_GENOME_LEN)
+            .map(|i| if i % 2 == 0 { 0 } else { 255 })
+            .collect(),
+        (0..MAX_GENOME_LEN).map(|i| (i * 8) as u8).collect(),
+        (0..MAX_GENOME_LEN).map(|i| 255 - (i * 8) as u8).collect(),
+    ]
+}
+
+fn edge_seeds() -> Vec<[u8; 32]> {
+    vec![
+        [0; 32],
+        [0xFF; 32],
+        std::array::from_fn(|i| i as u8),
+        hashv(&[b"saea-vectors"]).to_bytes(),
+    ]
+}
+
+/// Every edge genome under every edge seed, then `GENERATED_VECTORS` inputs
+/// of varying length drawn from a hash chain, so the set never depends on a
+/// random number generator's algorithm.
+pub fn generate() -> VectorFile {
+    let mut vectors = Vec::new();
+    for genome in edge_genomes() {
+        for seed in edge_seeds() {
+            vectors.push(vector(&genome, &seed));
+        }
+    }
+    for i in 0..GENERATED_VECTORS {
+        let seed = hashv(&[b"saea-vectors/seed", &[i]]).to_bytes();
+        let genes = [
+            hashv(&[b"saea-vectors/genome", &[i], &[0]]).to_bytes(),
+            hashv(&[b"saea-vectors/genome", &[i], &[1]]).to_bytes(),
+        ]
+        .concat();
+        let len = 1 + seed[0] as usize % MAX_GENOME_LEN;
+        vectors.push(vector(&genes[..len], &seed));
+    }
+    VectorFile {
+        version: VERSION,
+        vectors,
+    }
+}
+
+/// Check every vector in `file` against `compute_fitness`, stopping at the
+/// first difference.
+pub fn check(file: &VectorFile) -> Result<(), Mismatch> {
+    if file.version != VERSION {
+        return Err(Mismatch::Version {
+            found: file.version,
+        });
+    }
+    for (index, found) in file.vectors.iter().enumerate() {
+        let seed = decode_seed(&found.seed).ok_or(Mismatch::InvalidSeed { index })?;
+        let expected = vector(&found.genome, &seed);
+        if expected != *found {
+            return Err(Mismatch::Result {
+                index,
+                expected: Box::new(expected),
+                found: Box::new(found.clone()),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The file as written to disk: one vector per line, so a scoring change
+/// shows up in a diff as exactly the vectors it moved.
+pub fn to_json(file: &VectorFile) -> String {
+    let lines: Vec<String> = file
+        .vectors
+        .iter()
+        .map(|vector| {
+            format!(
+                "    {}",
+                serde_json::to_string(vector).expect("vector serializes")
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"version\": {},\n  \"vectors\": [\n{}\n  ]\n}}\n",
+        file.version,
+        lines.join(",\n")
+    )
+}
@@ -0,0 +1,59 @@
+use clap::Parser;
+use saea_vectors::{check, generate, to_json, VectorFile, VECTORS_PATH};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Regenerate the golden compute_fitness vectors, or check a file against
+/// the current scorer.
+#[derive(Parser)]
+#[command(name = "saea-vectors")]
+struct Args {
+    /// Check the file instead of overwriting it.
+    #[arg(long)]
+    check: bool,
+    /// Vectors file; defaults to the repository's copy.
+    #[arg(long)]
+    path: Option<PathBuf>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let path = args.path.unwrap_or_else(|| {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap();
+        root.join(VECTORS_PATH)
+    });
+
+    if args.check {
+        let file: VectorFile = match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("{}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        };
+        return match check(&file) {
+            Ok(()) => {
+                println!("{}: {} vectors match", path.display(), file.vectors.len());
+                ExitCode::SUCCESS
+            }
+            Err(mismatch) => {
+                eprintln!("{}: {mismatch}", path.display());
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let file = generate();
+    if let Err(e) = std::fs::write(&path, to_json(&file)) {
+        eprintln!("{}: {e}", path.display());
+        return ExitCode::FAILURE;
+    }
+    println!("{}: wrote {} vectors", path.display(), file.vectors.len());
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,34 @@
+use saea_vectors::{check, generate, to_json, Mismatch, VectorFile, VERSION};
+
+const COMMITTED: &str = include_str!("../../../tests/vectors/fitness.json");
+
+#[test]
+fn committed_vectors_match_compute_fitness() {
+    let file: VectorFile = serde_json::from_str(COMMITTED).unwrap();
+    if let Err(mismatch) = check(&file) {
+        panic!("{mismatch}; if the scorer changed on purpose, bump VERSION and run `cargo run -p saea-vectors`");
+    }
+}
+
+#[test]
+fn committed_vectors_are_current() {
+    assert_eq!(
+        COMMITTED,
+        to_json(&generate()),
+        "regenerate with `cargo run -p saea-vectors`"
+    );
+}
+
+#[test]
+fn check_reports_changes() {
+    let mut file = generate();
+    file.vectors[3].breakdown.pattern += 10;
+    assert!(matches!(
+        check(&file),
+        Err(Mismatch::Result { index: 3, .. })
+    ));
+
+    let mut file = generate();
+    file.version = VERSION + 1;
+    assert_eq!(check(&file), Err(Mismatch::Version { found: VERSION + 1 }));
+}
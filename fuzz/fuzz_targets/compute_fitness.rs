@@ -0,0 +1,28 @@
+#![no_main]
+
+//! The scoring function on arbitrary genomes, including lengths the program
+//! never accepts: it must not panic and, on valid genomes, must agree with
+//! the precomputed-targets path and stay within its per-length bound.
+
+use libfuzzer_sys::fuzz_target;
+use saea::instructions::{
+    compute_fitness, compute_fitness_breakdown, compute_fitness_breakdown_with_targets,
+};
+use saea::state::{FitnessTargets, MAX_GENOME_LEN};
+
+fuzz_target!(|input: (Vec<u8>, [u8; 32])| {
+    let (genome, seed) = input;
+    let breakdown = compute_fitness_breakdown(&genome, &seed);
+    let fitness = breakdown.total().expect("fitness overflowed");
+    assert_eq!(compute_fitness(&genome, &seed), fitness);
+
+    if genome.len() <= MAX_GENOME_LEN {
+        assert_eq!(
+            compute_fitness_breakdown_with_targets(&genome, &seed, &FitnessTargets::derive(&seed)),
+            breakdown
+        );
+        let len = genome.len() as u64;
+        let bound = 255 * len + 500 + 500 + 80 * len + 30 * len.saturating_sub(1);
+        assert!(fitness <= bound, "{fitness} exceeds {bound}");
+    }
+});
@@ -0,0 +1,582 @@
+#![no_main]
+
+//! Arbitrary sequences of every program instruction against one arena in
+//! LiteSVM. Instructions may fail, but only with a program or framework
+//! error code: a panic, an arithmetic overflow or any other runtime failure
+//! is a finding. After every instruction the arena's counters must agree
+//! with the population registry.
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::error::InstructionError;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use litesvm::LiteSVM;
+use saea::errors::SaeaError;
+use saea::state::{
+    AgentAccount, Arena, BenchmarkModel, CompletionPolicy, FitnessMode, GenomeDistance,
+    MigrationTopology, PopulationEntry, SelectionMetric, MAX_GENOME_LEN,
+};
+use saea_client::accounts::decode_population;
+use saea_client::{instructions as ix, pda};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const OWNERS: usize = 4;
+
+/// Caps on variable-length inputs so every transaction fits in a packet.
+const MAX_GENES: usize = MAX_GENOME_LEN + 8;
+const MAX_CHUNK: usize = 512;
+const MAX_LISTED_AGENTS: usize = 24;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    max_agents: u8,
+    min_fitness_threshold: u16,
+    mutation_rate_bps: u16,
+    actions: Vec<Action>,
+}
+
+/// One instruction; `u8` agent and owner fields index into the agents
+/// registered so far and the fixed owner pool, and `round_back` steps back
+/// from the latest round.
+#[derive(Arbitrary, Debug)]
+enum Action {
+    RegisterAgent {
+        owner: u8,
+        genome: Vec<u8>,
+    },
+    SubmitGenome {
+        agent: u8,
+        genome: Vec<u8>,
+        parent: u8,
+        as_owner: u8,
+    },
+    MutateGenome {
+        agent: u8,
+    },
+    RunRound,
+    RunIslandRound {
+        island: u8,
+    },
+    ScoreAgent {
+        agent: u8,
+        round_back: u8,
+        benchmark: bool,
+        hall_of_fame: bool,
+        species: bool,
+        novelty_archive: bool,
+    },
+    CompleteRound {
+        round_back: u8,
+    },
+    PruneAgent {
+        agent: u8,
+    },
+    AdvanceGeneration,
+    CreateBenchmark {
+        decision_table: bool,
+        feature_count: u8,
+        class_count: u8,
+        case_count: u8,
+    },
+    UploadBenchmarkChunk {
+        offset: u16,
+        data: Vec<u8>,
+    },
+    FreezeBenchmark,
+    SetFitnessMode {
+        mode: u8,
+    },
+    ConfigureSelection {
+        metric: u8,
+        half_life: u16,
+        window: u8,
+    },
+    RunTournamentRound,
+    PlayMatch {
+        round_back: u8,
+        match_index: u8,
+        species: bool,
+        hall_of_fame: bool,
+    },
+    UpdateRatings {
+        round_back: u8,
+        agent: u8,
+        opponents: Vec<u8>,
+    },
+    ConfigureHistogram {
+        base: u16,
+        bucket_width: u16,
+    },
+    InitializeHallOfFame,
+    ConfigureMutation {
+        adaptive: bool,
+        min_rate_bps: u16,
+        max_rate_bps: u16,
+        stagnation_window: u8,
+    },
+    ConfigureStrategyGenes {
+        strategy_genes: u8,
+    },
+    ConfigureIslands {
+        island_count: u8,
+        migration_interval: u8,
+        random: bool,
+        migrants_per_island: u8,
+    },
+    Migrate {
+        migrants: Vec<u8>,
+        fittest: bool,
+        with_round: bool,
+    },
+    InitializeSpecies {
+        l1: bool,
+        threshold: u16,
+    },
+    ShareFitness {
+        round_back: u8,
+        agents: Vec<u8>,
+    },
+    InitializeNoveltyArchive {
+        k_nearest: u8,
+        threshold: u16,
+        novelty_weight: u8,
+    },
+    ConfigurePareto {
+        enabled: bool,
+        prune_front: u8,
+    },
+    RankPareto {
+        round_back: u8,
+        agents: Vec<u8>,
+    },
+    SimulateFitness {
+        genome: Vec<u8>,
+        round_back: u8,
+        benchmark: bool,
+        novelty_archive: bool,
+    },
+    ConfigureCompletion {
+        policy: u8,
+        quorum_pct: u8,
+    },
+}
+
+fn capped(mut bytes: Vec<u8>, len: usize) -> Vec<u8> {
+    bytes.truncate(len);
+    bytes
+}
+
+struct Runtime {
+    svm: LiteSVM,
+    authority: Keypair,
+    owners: Vec<Keypair>,
+    /// Registered agents with the index of their owner.
+    agents: Vec<(Pubkey, usize)>,
+}
+
+impl Runtime {
+    fn new() -> Self {
+        let mut svm = LiteSVM::new();
+        // Built by the LiteSVM suite's build script.
+        let path = env!("SAEA_PROGRAM_SO");
+        svm.add_program_from_file(saea::ID, path)
+            .unwrap_or_else(|e| panic!("load {path}: {e:?}"));
+        let authority = Keypair::new_from_array([1; 32]);
+        svm.airdrop(&authority.pubkey(), 1_000 * LAMPORTS_PER_SOL)
+            .unwrap();
+        let owners: Vec<Keypair> = (0..OWNERS)
+            .map(|i| Keypair::new_from_array([2 + i as u8; 32]))
+            .collect();
+        for owner in &owners {
+            svm.airdrop(&owner.pubkey(), 100 * LAMPORTS_PER_SOL)
+                .unwrap();
+        }
+        Self {
+            svm,
+            authority,
+            owners,
+            agents: Vec::new(),
+        }
+    }
+
+    fn arena(&self) -> Arena {
+        let account = self.svm.get_account(&pda::arena().0).unwrap();
+        Arena::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    fn agent(&self, index: u8) -> Option<Pubkey> {
+        (!self.agents.is_empty()).then(|| self.agents[index as usize % self.agents.len()].0)
+    }
+
+    fn agent_list(&self, indices: &[u8]) -> Vec<Pubkey> {
+        indices
+            .iter()
+            .take(MAX_LISTED_AGENTS)
+            .filter_map(|&i| self.agent(i))
+            .collect()
+    }
+
+    /// The population registry's entries, one per active agent.
+    fn registry(&self) -> Vec<PopulationEntry> {
+        let key = pda::population(&pda::arena().0).0;
+        let account = self.svm.get_account(&key).unwrap();
+        decode_population(&account.data).unwrap().entries
+    }
+
+    /// The latest round, `back` rounds earlier (wrapping at three).
+    fn round(&self, back: u8) -> u64 {
+        self.arena().current_round.saturating_sub(back as u64 % 3)
+    }
+
+    /// Send `instruction` signed by `signer` and check how it failed, if it
+    /// did. Returns whether it succeeded.
+    fn send(&mut self, instruction: Instruction, signer: &Keypair) -> bool {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        match result {
+            Ok(_) => true,
+            Err(failed) => {
+                let overflow = SaeaError::ArithmeticOverflow as u32 + ERROR_CODE_OFFSET;
+                match failed.err {
+                    TransactionError::InstructionError(0, InstructionError::Custom(code))
+                        if code != overflow => {}
+                    err => panic!("{err:?}\n{:#?}", failed.meta.logs),
+                }
+                false
+            }
+        }
+    }
+
+    fn send_as_authority(&mut self, instruction: Instruction) -> bool {
+        let authority = self.authority.insecure_clone();
+        self.send(instruction, &authority)
+    }
+
+    fn apply(&mut self, action: Action) {
+        let authority = self.authority.pubkey();
+        let instruction = match action {
+            Action::RegisterAgent { owner, genome } => {
+                let owner_index = owner as usize % OWNERS;
+                let owner = self.owners[owner_index].insecure_clone();
+                let index = self.arena().total_agents;
+                if self.send(
+                    ix::register_agent(owner.pubkey(), index, capped(genome, MAX_GENES)),
+                    &owner,
+                ) {
+                    let agent = pda::agent(&pda::arena().0, &owner.pubkey(), index).0;
+                    self.agents.push((agent, owner_index));
+                }
+                return;
+            }
+            Action::SubmitGenome {
+                agent,
+                genome,
+                parent,
+                as_owner,
+            } => {
+                let Some(key) = self.agent(agent) else { return };
+                let parent = self.agent(parent).unwrap();
+                // Mostly the agent's own owner, sometimes another one.
+                let owner_index = match as_owner % 4 {
+                    0 => as_owner as usize % OWNERS,
+                    _ => self.agents[agent as usize % self.agents.len()].1,
+                };
+                let owner = self.owners[owner_index].insecure_clone();
+                self.send(
+                    ix::submit_genome(owner.pubkey(), key, capped(genome, MAX_GENES), parent),
+                    &owner,
+                );
+                return;
+            }
+            Action::MutateGenome { agent } => {
+                let Some(key) = self.agent(agent) else { return };
+                let owner =
+                    self.owners[self.agents[agent as usize % self.agents.len()].1].insecure_clone();
+                self.send(
+                    ix::mutate_genome(owner.pubkey(), key, self.round(0)),
+                    &owner,
+                );
+                return;
+            }
+            Action::RunRound => ix::run_round(authority, self.round(0) + 1),
+            Action::RunIslandRound { island } => {
+                ix::run_island_round(authority, self.round(0) + 1, island % 10)
+            }
+            Action::ScoreAgent {
+                agent,
+                round_back,
+                benchmark,
+                hall_of_fame,
+                species,
+                novelty_archive,
+            } => {
+                let Some(key) = self.agent(agent) else { return };
+                ix::score_agent(
+                    authority,
+                    self.round(round_back),
+                    key,
+                    ix::ScoreAccounts {
+                        benchmark,
+                        hall_of_fame,
+                        species,
+                        novelty_archive,
+                    },
+                )
+            }
+            Action::CompleteRound { round_back } => {
+                ix::complete_round(authority, self.round(round_back))
+            }
+            Action::PruneAgent { agent } => {
+                let Some(key) = self.agent(agent) else { return };
+                let owner = self.owners[self.agents[agent as usize % self.agents.len()].1].pubkey();
+                let current_round = Some(self.arena().current_round).filter(|&n| n > 0);
+                ix::prune_agent(authority, key, owner, current_round)
+            }
+            Action::AdvanceGeneration => {
+                ix::advance_generation(authority, self.arena().current_generation)
+            }
+            Action::CreateBenchmark {
+                decision_table,
+                feature_count,
+                class_count,
+                case_count,
+            } => {
+                let model = if decision_table {
+                    BenchmarkModel::DecisionTable
+                } else {
+                    BenchmarkModel::Linear
+                };
+                ix::create_benchmark(
+                    authority,
+                    model,
+                    feature_count % 10,
+                    class_count % 18,
+                    case_count as u32,
+                )
+            }
+            Action::UploadBenchmarkChunk { offset, data } => {
+                ix::upload_benchmark_chunk(authority, offset as u32, capped(data, MAX_CHUNK))
+            }
+            Action::FreezeBenchmark => ix::freeze_benchmark(authority),
+            Action::SetFitnessMode { mode } => {
+                let mode = match mode % 3 {
+                    0 => FitnessMode::Seeded,
+                    1 => FitnessMode::Benchmark,
+                    _ => FitnessMode::Novelty,
+                };
+                ix::set_fitness_mode(authority, mode)
+            }
+            Action::ConfigureSelection {
+                metric,
+                half_life,
+                window,
+            } => {
+                let metric = match metric % 3 {
+                    0 => SelectionMetric::LastRound,
+                    1 => SelectionMetric::Decayed,
+                    _ => SelectionMetric::Windowed,
+                };
+                ix::configure_selection(authority, metric, half_life, window % 10)
+            }
+            Action::RunTournamentRound => ix::run_tournament_round(authority, self.round(0) + 1),
+            Action::PlayMatch {
+                round_back,
+                match_index,
+                species,
+                hall_of_fame,
+            } => {
+                let round_number = self.round(round_back);
+                let key = pda::round(&pda::arena().0, round_number).0;
+                let Some(account) = self.svm.get_account(&key) else {
+                    return;
+                };
+                let round =
+                    saea::state::Round::try_deserialize(&mut account.data.as_slice()).unwrap();
+                let match_index = match_index as u16 % 17;
+                let Some((a, b)) = round.pairing(match_index) else {
+                    return;
+                };
+                let optional = ix::ScoreAccounts {
+                    species,
+                    hall_of_fame,
+                    ..Default::default()
+                };
+                ix::play_match(authority, round_number, match_index, a, b, optional)
+            }
+            Action::UpdateRatings {
+                round_back,
+                agent,
+                opponents,
+            } => {
+                let Some(key) = self.agent(agent) else { return };
+                ix::update_ratings(
+                    authority,
+                    self.round(round_back),
+                    key,
+                    &self.agent_list(&opponents),
+                )
+            }
+            Action::ConfigureHistogram { base, bucket_width } => {
+                ix::configure_histogram(authority, base as u64, bucket_width as u64)
+            }
+            Action::InitializeHallOfFame => ix::initialize_hall_of_fame(authority),
+            Action::ConfigureMutation {
+                adaptive,
+                min_rate_bps,
+                max_rate_bps,
+                stagnation_window,
+            } => ix::configure_mutation(
+                authority,
+                adaptive,
+                min_rate_bps,
+                max_rate_bps,
+                stagnation_window as u16,
+            ),
+            Action::ConfigureStrategyGenes { strategy_genes } => {
+                ix::configure_strategy_genes(authority, strategy_genes % 34)
+            }
+            Action::ConfigureIslands {
+                island_count,
+                migration_interval,
+                random,
+                migrants_per_island,
+            } => {
+                let topology = if random {
+                    MigrationTopology::Random
+                } else {
+                    MigrationTopology::Ring
+                };
+                ix::configure_islands(
+                    authority,
+                    island_count % 10,
+                    migration_interval as u64 % 4,
+                    topology,
+                    migrants_per_island % 4,
+                )
+            }
+            Action::Migrate {
+                migrants,
+                fittest,
+                with_round,
+            } => {
+                let migrants = if fittest {
+                    saea::instructions::migrants(&self.registry(), self.arena().migrants_per_island)
+                } else {
+                    self.agent_list(&migrants)
+                };
+                ix::migrate(authority, with_round.then(|| self.round(0)), &migrants)
+            }
+            Action::InitializeSpecies { l1, threshold } => {
+                let distance = if l1 {
+                    GenomeDistance::L1
+                } else {
+                    GenomeDistance::Hamming
+                };
+                ix::initialize_species(authority, distance, threshold as u32)
+            }
+            Action::ShareFitness { round_back, agents } => {
+                ix::share_fitness(authority, self.round(round_back), &self.agent_list(&agents))
+            }
+            Action::InitializeNoveltyArchive {
+                k_nearest,
+                threshold,
+                novelty_weight,
+            } => ix::initialize_novelty_archive(
+                authority,
+                k_nearest % 70,
+                threshold as u64,
+                novelty_weight as u64,
+            ),
+            Action::ConfigurePareto {
+                enabled,
+                prune_front,
+            } => ix::configure_pareto(authority, enabled, prune_front % 4),
+            Action::RankPareto { round_back, agents } => {
+                ix::rank_pareto(authority, self.round(round_back), &self.agent_list(&agents))
+            }
+            Action::SimulateFitness {
+                genome,
+                round_back,
+                benchmark,
+                novelty_archive,
+            } => ix::simulate_fitness(
+                capped(genome, MAX_GENES),
+                self.round(round_back),
+                benchmark,
+                novelty_archive,
+            ),
+            Action::ConfigureCompletion { policy, quorum_pct } => {
+                let policy = match policy % 3 {
+                    0 => CompletionPolicy::Strict,
+                    1 => CompletionPolicy::Quorum,
+                    _ => CompletionPolicy::Lenient,
+                };
+                ix::configure_completion(authority, policy, quorum_pct % 110)
+            }
+        };
+        self.send_as_authority(instruction);
+    }
+
+    /// Counters on the arena agree with the population registry and with
+    /// each agent account.
+    fn check_invariants(&self) {
+        let arena = self.arena();
+        let key = pda::population(&pda::arena().0).0;
+        let population = decode_population(&self.svm.get_account(&key).unwrap().data).unwrap();
+
+        assert!(arena.active_agents <= arena.max_agents);
+        assert_eq!(arena.active_agents, population.entries.len() as u64);
+        let per_island: u64 = arena.island_populations[..arena.island_count as usize]
+            .iter()
+            .sum();
+        assert_eq!(per_island, arena.active_agents);
+
+        for (key, _) in &self.agents {
+            let account = self.svm.get_account(key).unwrap();
+            let agent = AgentAccount::try_deserialize(&mut account.data.as_slice()).unwrap();
+            let entry = population.entries.iter().find(|entry| entry.agent == *key);
+            assert_eq!(
+                agent.is_active,
+                entry.is_some(),
+                "registry out of sync for {key}"
+            );
+            if let Some(entry) = entry {
+                assert_eq!(
+                    entry.island, agent.island,
+                    "registry island out of sync for {key}"
+                );
+            }
+            assert!(agent.island < arena.island_count.max(1));
+        }
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let mut runtime = Runtime::new();
+    let authority = runtime.authority.pubkey();
+    if !runtime.send_as_authority(ix::initialize_arena(
+        authority,
+        input.max_agents as u64 % 20,
+        input.min_fitness_threshold as u64,
+        input.mutation_rate_bps % 11_000,
+    )) {
+        return;
+    }
+    for action in input.actions.into_iter().take(64) {
+        runtime.apply(action);
+        runtime.check_invariants();
+    }
+});
@@ -34,4 +34,88 @@ pub enum SaeaError {
     AgentAboveThreshold,
     #[msg("Arithmetic overflow")]
     ArithmeticOverflow,
+    #[msg("Benchmark shape is invalid or does not fit in a genome")]
+    InvalidBenchmarkShape,
+    #[msg("Benchmark chunk is out of order or exceeds the declared size")]
+    InvalidBenchmarkChunk,
+    #[msg("Benchmark is frozen")]
+    BenchmarkFrozen,
+    #[msg("Benchmark is not frozen")]
+    BenchmarkNotFrozen,
+    #[msg("Benchmark data is incomplete or contains invalid labels")]
+    InvalidBenchmarkData,
+    #[msg("Fitness mode requires the arena benchmark account")]
+    BenchmarkRequired,
+    #[msg("Half-life must be positive and window must be 1-8 rounds")]
+    InvalidSelectionConfig,
+    #[msg("Instruction does not apply to this kind of round")]
+    WrongRoundKind,
+    #[msg("Tournament needs at least 2 active agents")]
+    InvalidTournamentEntrants,
+    #[msg("Agents do not match the drawn pairing")]
+    PairingMismatch,
+    #[msg("Rating opponents must be the agent's seed-ordered neighbours in the round")]
+    InvalidRatingOpponents,
+    #[msg("Agent was not scored in this round")]
+    AgentNotInRound,
+    #[msg("Agent rating already includes this round")]
+    AlreadyRated,
+    #[msg("Histogram buckets must be positive and start within the fitness range")]
+    InvalidHistogramConfig,
+    #[msg("Mutation bounds must satisfy min <= max <= 10000 with a positive window")]
+    InvalidMutationBounds,
+    #[msg("Self-adaptive mutation is disabled for this arena")]
+    SelfAdaptationDisabled,
+    #[msg("Strategy prefix must leave at least one scored gene")]
+    InvalidStrategyGenes,
+    #[msg("Island index is out of range")]
+    InvalidIsland,
+    #[msg("Agent does not live on this round's island")]
+    WrongIsland,
+    #[msg("Cannot prune the last agent of an island")]
+    IslandDepleted,
+    #[msg("Island layout can only change before agents register")]
+    IslandsLocked,
+    #[msg("Invalid island configuration")]
+    InvalidIslandConfig,
+    #[msg("Migration is not due yet")]
+    MigrationNotDue,
+    #[msg("More migrants than the islands send")]
+    TooManyMigrants,
+    #[msg("Species registry account is required while speciation is enabled")]
+    SpeciesRequired,
+    #[msg("Species registry does not hold this round's assignments")]
+    SpeciesRoundMismatch,
+    #[msg("Novelty archive account is required for novelty scoring")]
+    NoveltyArchiveRequired,
+    #[msg("Invalid novelty archive configuration")]
+    InvalidNoveltyConfig,
+    #[msg("Pareto batch must list every agent scored in the round, 1-16 distinct writable agents")]
+    InvalidParetoBatch,
+    #[msg("Agent is not listed in the population registry")]
+    AgentNotInPopulation,
+    #[msg("Too few eligible agents were scored for the arena's completion policy")]
+    RoundQuorumNotMet,
+    #[msg("Completion quorum must be between 1 and 100 percent")]
+    InvalidCompletionQuorum,
+    #[msg("Hall of fame account is required once the arena has one")]
+    HallOfFameRequired,
+    #[msg("Round account is required for this arena configuration")]
+    RoundRequired,
+    #[msg("Migration must list every migrant the islands send")]
+    MigrationAgentsIncomplete,
+    #[msg("Migrants must be the fittest agents of their island")]
+    MigrantNotFittest,
+    #[msg("Pareto selection needs seeded or novelty fitness components")]
+    ParetoNeedsObjectives,
+    #[msg("Agent registered after the round started")]
+    AgentNotEligible,
+    #[msg("Pareto selection needs an arena of at most 16 agents, the most one batch can rank")]
+    ParetoArenaTooLarge,
+    #[msg("Round can no longer be rated: a later round started or a scored agent was pruned")]
+    RatingsClosed,
+    #[msg("Fitness can only be shared for the latest round, before the next round starts")]
+    FitnessSharingClosed,
+    #[msg("Genomes change only through mutate_genome while the arena self-adapts")]
+    SelfAdaptationEnabled,
 }
@@ -1,3 +1,6 @@
+use crate::state::{
+    FitnessBreakdown, FitnessMode, SelectionMetric, BEHAVIOR_DIMENSIONS, HISTOGRAM_BUCKETS,
+};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -31,6 +34,14 @@ pub struct RoundCompleted {
     pub participants: u64,
     pub best_fitness: u64,
     pub average_fitness: u64,
+    pub worst_fitness: u64,
+    pub fitness_variance: u64,
+    pub lower_quartile_fitness: u64,
+    pub median_fitness: u64,
+    pub upper_quartile_fitness: u64,
+    pub histogram: [u32; HISTOGRAM_BUCKETS],
+    pub genome_diversity: u64,
+    pub agents_skipped: u64, // eligible agents left unscored
 }
 
 #[event]
@@ -39,6 +50,8 @@ pub struct AgentScored {
     pub round_number: u64,
     pub fitness: u64,
     pub genome_hash: [u8; 32],
+    pub species: u32,
+    pub breakdown: FitnessBreakdown,
 }
 
 #[event]
@@ -54,3 +67,89 @@ pub struct GenerationAdvanced {
     pub new_generation: u64,
     pub active_agents: u64,
 }
+
+#[event]
+pub struct BenchmarkFrozen {
+    pub benchmark: Pubkey,
+    pub case_count: u32,
+    pub data_hash: [u8; 32],
+}
+
+#[event]
+pub struct FitnessModeChanged {
+    pub old_mode: FitnessMode,
+    pub new_mode: FitnessMode,
+}
+
+#[event]
+pub struct SelectionConfigured {
+    pub metric: SelectionMetric,
+    pub half_life: u16,
+    pub window: u8,
+}
+
+#[event]
+pub struct MatchPlayed {
+    pub round_number: u64,
+    pub match_index: u16,
+    pub agent_a: Pubkey,
+    pub agent_b: Pubkey,
+    pub payoff_a: u64,
+    pub payoff_b: u64,
+    pub winner: Pubkey, // Pubkey::default() on a draw
+}
+
+#[event]
+pub struct RatingUpdated {
+    pub agent: Pubkey,
+    pub round_number: u64,
+    pub old_rating: u64,
+    pub new_rating: u64,
+}
+
+#[event]
+pub struct HistogramConfigured {
+    pub base: u64,
+    pub bucket_width: u64,
+}
+
+#[event]
+pub struct HallOfFameUpdated {
+    pub agent: Pubkey,
+    pub fitness: u64,
+    pub round_number: u64,
+    pub generation: u64,
+    pub rank: u8,
+}
+
+#[event]
+pub struct MutationRateAdjusted {
+    pub round_number: u64,
+    pub old_rate_bps: u16,
+    pub new_rate_bps: u16,
+    pub best_fitness: u64,
+}
+
+#[event]
+pub struct AgentMigrated {
+    pub agent: Pubkey,
+    pub from_island: u8,
+    pub to_island: u8,
+    pub generation: u64,
+}
+
+#[event]
+pub struct NoveltyArchived {
+    pub agent: Pubkey,
+    pub round_number: u64,
+    pub novelty: u64,
+    pub behavior: [u8; BEHAVIOR_DIMENSIONS],
+}
+
+#[event]
+pub struct ParetoRanked {
+    pub agent: Pubkey,
+    pub round_number: u64,
+    pub front: u8,
+    pub crowding_distance: u64,
+}
@@ -1,6 +1,6 @@
 use crate::errors::SaeaError;
 use crate::events::GenerationAdvanced;
-use crate::state::Arena;
+use crate::state::{Arena, Generation, GenerationTally};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -13,14 +13,50 @@ pub struct AdvanceGeneration<'info> {
     )]
     pub arena: Account<'info, Arena>,
     #[account(
+        init,
+        payer = authority,
+        space = 8 + Generation::INIT_SPACE,
+        seeds = [Generation::SEED, arena.key().as_ref(), &arena.current_generation.to_le_bytes()],
+        bump,
+    )]
+    pub generation: Account<'info, Generation>,
+    #[account(
+        mut,
         constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
     )]
     pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 pub fn handle_advance_generation(ctx: Context<AdvanceGeneration>) -> Result<()> {
     let arena = &mut ctx.accounts.arena;
+    let clock = Clock::get()?;
     let old_generation = arena.current_generation;
+
+    // Close out the finished generation into its summary account.
+    let tally = arena.generation_tally;
+    let summary = &mut ctx.accounts.generation;
+    summary.arena = arena.key();
+    summary.generation_number = old_generation;
+    summary.rounds_run = tally.rounds_run;
+    summary.agents_born = tally.agents_born;
+    summary.genomes_submitted = tally.genomes_submitted;
+    summary.agents_pruned = tally.agents_pruned;
+    summary.best_fitness = tally.best_fitness;
+    summary.average_fitness = if tally.rounds_run > 0 {
+        tally.round_average_sum / tally.rounds_run
+    } else {
+        0
+    };
+    summary.champion = tally.champion;
+    summary.champion_genome_hash = tally.champion_genome_hash;
+    summary.previous_hash = arena.last_generation_hash;
+    summary.summary_hash = summary.compute_summary_hash();
+    summary.closed_at = clock.unix_timestamp;
+    summary.bump = ctx.bumps.generation;
+
+    arena.last_generation_hash = summary.summary_hash;
+    arena.generation_tally = GenerationTally::default();
     arena.current_generation = old_generation
         .checked_add(1)
         .ok_or(SaeaError::ArithmeticOverflow)?;
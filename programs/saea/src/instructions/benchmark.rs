@@ -0,0 +1,169 @@
+use crate::errors::SaeaError;
+use crate::events::BenchmarkFrozen;
+use crate::state::{Arena, Benchmark, BenchmarkModel, MAX_BENCHMARK_BYTES, MAX_BENCHMARK_CLASSES};
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hash;
+
+#[derive(Accounts)]
+pub struct CreateBenchmark<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Benchmark::INIT_SPACE,
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump,
+    )]
+    pub benchmark: Account<'info, Benchmark>,
+    #[account(
+        mut,
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle_create_benchmark(
+    ctx: Context<CreateBenchmark>,
+    model: BenchmarkModel,
+    feature_count: u8,
+    class_count: u8,
+    case_count: u32,
+) -> Result<()> {
+    require!(
+        feature_count > 0
+            && (2..=MAX_BENCHMARK_CLASSES).contains(&class_count)
+            && Benchmark::fits_genome(model, feature_count, class_count),
+        SaeaError::InvalidBenchmarkShape
+    );
+    let total_len = (case_count as usize)
+        .checked_mul(feature_count as usize + 1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+    require!(
+        case_count > 0 && total_len <= MAX_BENCHMARK_BYTES,
+        SaeaError::InvalidBenchmarkShape
+    );
+
+    let benchmark = &mut ctx.accounts.benchmark;
+    benchmark.arena = ctx.accounts.arena.key();
+    benchmark.model = model;
+    benchmark.feature_count = feature_count;
+    benchmark.class_count = class_count;
+    benchmark.case_count = case_count;
+    benchmark.cases = Vec::new();
+    benchmark.data_hash = [0u8; 32];
+    benchmark.is_frozen = false;
+    benchmark.bump = ctx.bumps.benchmark;
+
+    msg!(
+        "Benchmark created: features={}, classes={}, cases={}",
+        feature_count,
+        class_count,
+        case_count
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UploadBenchmarkChunk<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump = benchmark.bump,
+        constraint = !benchmark.is_frozen @ SaeaError::BenchmarkFrozen,
+    )]
+    pub benchmark: Account<'info, Benchmark>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_upload_benchmark_chunk(
+    ctx: Context<UploadBenchmarkChunk>,
+    offset: u32,
+    data: Vec<u8>,
+) -> Result<()> {
+    let benchmark = &mut ctx.accounts.benchmark;
+
+    // Chunks must be appended in order so a retried transaction cannot
+    // silently duplicate or reorder cases.
+    let end = (offset as usize)
+        .checked_add(data.len())
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+    require!(
+        !data.is_empty()
+            && offset as usize == benchmark.cases.len()
+            && end <= benchmark.expected_len(),
+        SaeaError::InvalidBenchmarkChunk
+    );
+
+    benchmark.cases.extend_from_slice(&data);
+
+    msg!(
+        "Benchmark chunk uploaded: {}/{} bytes",
+        benchmark.cases.len(),
+        benchmark.expected_len()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FreezeBenchmark<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump = benchmark.bump,
+        constraint = !benchmark.is_frozen @ SaeaError::BenchmarkFrozen,
+    )]
+    pub benchmark: Account<'info, Benchmark>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_freeze_benchmark(ctx: Context<FreezeBenchmark>) -> Result<()> {
+    let benchmark = &mut ctx.accounts.benchmark;
+
+    require!(
+        benchmark.cases.len() == benchmark.expected_len(),
+        SaeaError::InvalidBenchmarkData
+    );
+    let case_len = benchmark.case_len();
+    let class_count = benchmark.class_count;
+    require!(
+        benchmark
+            .cases
+            .chunks_exact(case_len)
+            .all(|case| case[case_len - 1] < class_count),
+        SaeaError::InvalidBenchmarkData
+    );
+
+    benchmark.data_hash = hash(&benchmark.cases).to_bytes();
+    benchmark.is_frozen = true;
+
+    emit!(BenchmarkFrozen {
+        benchmark: benchmark.key(),
+        case_count: benchmark.case_count,
+        data_hash: benchmark.data_hash,
+    });
+
+    msg!("Benchmark frozen: cases={}", benchmark.case_count);
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::errors::SaeaError;
+use crate::state::{Arena, CompletionPolicy};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureCompletion<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Set how many eligible agents must be scored before a round can complete.
+/// Rounds keep the policy in force when they started.
+pub fn handle_configure_completion(
+    ctx: Context<ConfigureCompletion>,
+    policy: CompletionPolicy,
+    quorum_pct: u8,
+) -> Result<()> {
+    require!(
+        (1..=100).contains(&quorum_pct),
+        SaeaError::InvalidCompletionQuorum
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    arena.completion_policy = policy;
+    arena.completion_quorum_pct = quorum_pct;
+
+    msg!(
+        "Completion policy configured: {:?}, quorum={}%",
+        policy,
+        quorum_pct
+    );
+    Ok(())
+}
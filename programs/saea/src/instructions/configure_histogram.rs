@@ -0,0 +1,48 @@
+use crate::errors::SaeaError;
+use crate::events::HistogramConfigured;
+use crate::state::{Arena, HISTOGRAM_BUCKETS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureHistogram<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Set the fitness histogram layout used by rounds started from now on.
+/// Every bucket must start within the fitness range.
+pub fn handle_configure_histogram(
+    ctx: Context<ConfigureHistogram>,
+    base: u64,
+    bucket_width: u64,
+) -> Result<()> {
+    let span = bucket_width
+        .checked_mul(HISTOGRAM_BUCKETS as u64 - 1)
+        .and_then(|span| base.checked_add(span));
+    require!(
+        bucket_width > 0 && span.is_some(),
+        SaeaError::InvalidHistogramConfig
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    arena.histogram_base = base;
+    arena.histogram_bucket_width = bucket_width;
+
+    emit!(HistogramConfigured { base, bucket_width });
+
+    msg!(
+        "Histogram configured: base={}, bucket_width={}",
+        base,
+        bucket_width
+    );
+    Ok(())
+}
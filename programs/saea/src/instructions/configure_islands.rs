@@ -0,0 +1,56 @@
+use crate::errors::SaeaError;
+use crate::state::{Arena, MigrationTopology, MAX_ISLANDS};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureIslands<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Split the population into `island_count` islands that exchange up to
+/// `migrants_per_island` agents every `migration_interval` generations.
+pub fn handle_configure_islands(
+    ctx: Context<ConfigureIslands>,
+    island_count: u8,
+    migration_interval: u64,
+    topology: MigrationTopology,
+    migrants_per_island: u8,
+) -> Result<()> {
+    require!(
+        (1..=MAX_ISLANDS as u8).contains(&island_count)
+            && migration_interval > 0
+            && migrants_per_island > 0,
+        SaeaError::InvalidIslandConfig
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    // Agents are assigned an island when they register, so the layout is
+    // fixed once the first one has.
+    require!(
+        island_count == arena.island_count || arena.total_agents == 0,
+        SaeaError::IslandsLocked
+    );
+    arena.island_count = island_count;
+    arena.migration_interval = migration_interval;
+    arena.migration_topology = topology;
+    arena.migrants_per_island = migrants_per_island;
+
+    msg!(
+        "Islands configured: count={}, interval={}, topology={:?}, migrants={}",
+        island_count,
+        migration_interval,
+        topology,
+        migrants_per_island
+    );
+    Ok(())
+}
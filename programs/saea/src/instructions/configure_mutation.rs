@@ -0,0 +1,69 @@
+use crate::errors::SaeaError;
+use crate::state::{Arena, MAX_GENOME_LEN};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureMutation<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Set the bounds of the stagnation rule and turn it on or off; new arenas
+/// start with a fixed mutation rate.
+pub fn handle_configure_mutation(
+    ctx: Context<ConfigureMutation>,
+    adaptive: bool,
+    min_rate_bps: u16,
+    max_rate_bps: u16,
+    stagnation_window: u16,
+) -> Result<()> {
+    require!(
+        min_rate_bps <= max_rate_bps && max_rate_bps <= 10_000 && stagnation_window > 0,
+        SaeaError::InvalidMutationBounds
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    arena.adaptive_mutation = adaptive;
+    arena.mutation_rate_min_bps = min_rate_bps;
+    arena.mutation_rate_max_bps = max_rate_bps;
+    arena.stagnation_window = stagnation_window;
+    arena.stagnant_rounds = 0;
+    arena.mutation_rate_bps = arena.mutation_rate_bps.clamp(min_rate_bps, max_rate_bps);
+
+    msg!(
+        "Mutation configured: adaptive={}, bounds=[{}, {}] bps, window={}, rate={} bps",
+        adaptive,
+        min_rate_bps,
+        max_rate_bps,
+        stagnation_window,
+        arena.mutation_rate_bps
+    );
+    Ok(())
+}
+
+/// Reserve a genome prefix for self-adaptive step sizes (0 disables it).
+/// Prefix genes are excluded from scoring and drive `mutate_genome`.
+pub fn handle_configure_strategy_genes(
+    ctx: Context<ConfigureMutation>,
+    strategy_genes: u8,
+) -> Result<()> {
+    require!(
+        (strategy_genes as usize) < MAX_GENOME_LEN,
+        SaeaError::InvalidStrategyGenes
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    arena.strategy_genes = strategy_genes;
+
+    msg!("Strategy genes configured: prefix={}", strategy_genes);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::errors::SaeaError;
+use crate::instructions::MAX_PARETO_BATCH;
+use crate::state::{Arena, FitnessMode};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigurePareto<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Switch pruning between the scalar fitness threshold and Pareto front
+/// rank; agents ranked on front `prune_front` or later become prunable.
+/// Benchmark scores have no components, so that mode cannot rank by front.
+/// Fronts are ranked in a single batch per round, so the arena must never
+/// hold more agents than `MAX_PARETO_BATCH`; otherwise no agent would ever
+/// carry a current rank and none could be pruned.
+pub fn handle_configure_pareto(
+    ctx: Context<ConfigurePareto>,
+    enabled: bool,
+    prune_front: u8,
+) -> Result<()> {
+    let arena = &mut ctx.accounts.arena;
+    require!(
+        !enabled || arena.fitness_mode != FitnessMode::Benchmark,
+        SaeaError::ParetoNeedsObjectives
+    );
+    require!(
+        !enabled || arena.max_agents <= MAX_PARETO_BATCH as u64,
+        SaeaError::ParetoArenaTooLarge
+    );
+    arena.pareto_selection = enabled;
+    arena.pareto_prune_front = prune_front;
+
+    msg!(
+        "Pareto selection configured: enabled={}, prune_front={}",
+        enabled,
+        prune_front
+    );
+    Ok(())
+}
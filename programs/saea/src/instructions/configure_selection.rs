@@ -0,0 +1,51 @@
+use crate::errors::SaeaError;
+use crate::events::SelectionConfigured;
+use crate::state::{Arena, SelectionMetric, MAX_FITNESS_WINDOW};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ConfigureSelection<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_configure_selection(
+    ctx: Context<ConfigureSelection>,
+    metric: SelectionMetric,
+    half_life: u16,
+    window: u8,
+) -> Result<()> {
+    require!(
+        half_life > 0 && window > 0 && window as usize <= MAX_FITNESS_WINDOW,
+        SaeaError::InvalidSelectionConfig
+    );
+
+    let arena = &mut ctx.accounts.arena;
+    arena.selection_metric = metric;
+    arena.fitness_half_life = half_life;
+    arena.fitness_decay_retention = Arena::decay_retention(half_life);
+    arena.fitness_window = window;
+
+    emit!(SelectionConfigured {
+        metric,
+        half_life,
+        window,
+    });
+
+    msg!(
+        "Selection configured: metric={:?}, half_life={}, window={}",
+        metric,
+        half_life,
+        window
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::errors::SaeaError;
 use crate::events::ArenaInitialized;
-use crate::state::Arena;
+use crate::state::{Arena, Population};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -13,6 +13,14 @@ pub struct InitializeArena<'info> {
         bump,
     )]
     pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = Population::space(0),
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
     #[account(mut)]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -27,18 +35,15 @@ pub fn handle_initialize_arena(
     require!(max_agents >= 2, SaeaError::InvalidMaxAgents);
     require!(mutation_rate_bps <= 10_000, SaeaError::InvalidMutationRate);
 
+    let authority = ctx.accounts.authority.key();
     let arena = &mut ctx.accounts.arena;
-    arena.authority = ctx.accounts.authority.key();
-    arena.current_round = 0;
-    arena.current_generation = 1;
-    arena.total_agents = 0;
-    arena.active_agents = 0;
-    arena.max_agents = max_agents;
-    arena.min_fitness_threshold = min_fitness_threshold;
-    arena.mutation_rate_bps = mutation_rate_bps;
-    arena.is_active = true;
-    arena.reward_pool = 0;
-    arena.bump = ctx.bumps.arena;
+    arena.set_inner(Arena::new(
+        authority,
+        max_agents,
+        min_fitness_threshold,
+        mutation_rate_bps,
+        ctx.bumps.arena,
+    ));
 
     emit!(ArenaInitialized {
         authority: arena.authority,
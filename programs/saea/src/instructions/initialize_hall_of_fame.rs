@@ -0,0 +1,40 @@
+use crate::errors::SaeaError;
+use crate::state::{Arena, HallOfFame};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeHallOfFame<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + HallOfFame::INIT_SPACE,
+        seeds = [HallOfFame::SEED, arena.key().as_ref()],
+        bump,
+    )]
+    pub hall_of_fame: Account<'info, HallOfFame>,
+    #[account(
+        mut,
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the hall of fame; from now on every score must be offered to it.
+pub fn handle_initialize_hall_of_fame(ctx: Context<InitializeHallOfFame>) -> Result<()> {
+    let hall_of_fame = &mut ctx.accounts.hall_of_fame;
+    hall_of_fame.arena = ctx.accounts.arena.key();
+    hall_of_fame.entries = Vec::new();
+    hall_of_fame.bump = ctx.bumps.hall_of_fame;
+
+    ctx.accounts.arena.hall_of_fame_enabled = true;
+
+    msg!("Hall of fame initialized");
+    Ok(())
+}
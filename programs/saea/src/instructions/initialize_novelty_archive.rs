@@ -0,0 +1,59 @@
+use crate::errors::SaeaError;
+use crate::state::{Arena, NoveltyArchive, MAX_NOVELTY_ARCHIVE, MAX_NOVELTY_WEIGHT};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeNoveltyArchive<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NoveltyArchive::INIT_SPACE,
+        seeds = [NoveltyArchive::SEED, arena.key().as_ref()],
+        bump,
+    )]
+    pub novelty_archive: Account<'info, NoveltyArchive>,
+    #[account(
+        mut,
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the novelty archive scored against in `FitnessMode::Novelty`,
+/// with a `novelty_weight` of at most `MAX_NOVELTY_WEIGHT`.
+pub fn handle_initialize_novelty_archive(
+    ctx: Context<InitializeNoveltyArchive>,
+    k_nearest: u8,
+    threshold: u64,
+    novelty_weight: u64,
+) -> Result<()> {
+    require!(
+        (1..=MAX_NOVELTY_ARCHIVE).contains(&(k_nearest as usize))
+            && novelty_weight <= MAX_NOVELTY_WEIGHT,
+        SaeaError::InvalidNoveltyConfig
+    );
+
+    let archive = &mut ctx.accounts.novelty_archive;
+    archive.arena = ctx.accounts.arena.key();
+    archive.k_nearest = k_nearest;
+    archive.threshold = threshold;
+    archive.novelty_weight = novelty_weight;
+    archive.entries = Vec::new();
+    archive.next_slot = 0;
+    archive.total_archived = 0;
+    archive.bump = ctx.bumps.novelty_archive;
+
+    msg!(
+        "Novelty archive initialized: k={}, threshold={}, weight={}",
+        k_nearest,
+        threshold,
+        novelty_weight
+    );
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use crate::errors::SaeaError;
+use crate::events::AgentMigrated;
+use crate::state::{
+    AgentAccount, Arena, MigrationTopology, Population, PopulationEntry, Round, MAX_ISLANDS,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+        constraint = arena.current_generation >= arena.last_migration_generation + arena.migration_interval
+            @ SaeaError::MigrationNotDue,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    /// Latest round; its seed draws the random topology, so it is only
+    /// required under `MigrationTopology::Random`.
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &arena.current_round.to_le_bytes()],
+        bump = round.bump,
+    )]
+    pub round: Option<Account<'info, Round>>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    // remaining_accounts: the migrants (writable), exactly those `migrants`
+    // picks from the registry
+}
+
+/// Agents a migration sends: the `per_island` fittest registry entries of
+/// each island by selection fitness, ties going to the lower key.
+pub fn migrants(entries: &[PopulationEntry], per_island: u8) -> Vec<Pubkey> {
+    let mut ranked: Vec<&PopulationEntry> = entries.iter().collect();
+    ranked.sort_by(|a, b| {
+        b.selection_fitness
+            .cmp(&a.selection_fitness)
+            .then(a.agent.cmp(&b.agent))
+    });
+    let mut departures = [0u8; MAX_ISLANDS];
+    ranked
+        .into_iter()
+        .filter(|entry| {
+            let departed = &mut departures[entry.island as usize];
+            if *departed == per_island {
+                return false;
+            }
+            *departed += 1;
+            true
+        })
+        .map(|entry| entry.agent)
+        .collect()
+}
+
+pub fn handle_migrate<'info>(ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>) -> Result<()> {
+    let arena = &mut ctx.accounts.arena;
+    let seed = match arena.migration_topology {
+        MigrationTopology::Ring => [0u8; 32],
+        MigrationTopology::Random => {
+            ctx.accounts
+                .round
+                .as_ref()
+                .ok_or(SaeaError::RoundRequired)?
+                .seed
+        }
+    };
+
+    // Resolve every destination up front so an agent arriving on an island
+    // is never sent on again in the same migration.
+    let destinations: Vec<u8> = (0..arena.island_count)
+        .map(|island| arena.migration_destination(island, &seed))
+        .collect();
+
+    // The registry ranks every island, so only the migrants are passed in.
+    let mut expected = {
+        let info = ctx.accounts.population.to_account_info();
+        let data = info.try_borrow_data()?;
+        migrants(Population::entries(&data), arena.migrants_per_island)
+    };
+    expected.sort();
+    require!(
+        ctx.remaining_accounts.len() <= expected.len(),
+        SaeaError::TooManyMigrants
+    );
+    require!(
+        ctx.remaining_accounts.len() == expected.len(),
+        SaeaError::MigrationAgentsIncomplete
+    );
+    let mut keys: Vec<Pubkey> = ctx
+        .remaining_accounts
+        .iter()
+        .map(|info| info.key())
+        .collect();
+    keys.sort();
+    require!(keys == expected, SaeaError::MigrantNotFittest);
+
+    let mut migrants = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        migrants.push(Account::<AgentAccount>::try_from(info)?);
+    }
+
+    for agent in &mut migrants {
+        let from = agent.island;
+        let to = destinations[from as usize];
+        if to == from {
+            continue;
+        }
+
+        arena.island_populations[from as usize] = arena.island_populations[from as usize]
+            .checked_sub(1)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+        arena.island_populations[to as usize] = arena.island_populations[to as usize]
+            .checked_add(1)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+        agent.island = to;
+        agent.exit(&crate::ID)?;
+        Population::update(&ctx.accounts.population, &agent.key(), |entry| {
+            entry.island = to;
+        })?;
+
+        emit!(AgentMigrated {
+            agent: agent.key(),
+            from_island: from,
+            to_island: to,
+            generation: arena.current_generation,
+        });
+    }
+    require!(
+        arena.island_populations[..arena.island_count as usize]
+            .iter()
+            .all(|&population| population > 0),
+        SaeaError::IslandDepleted
+    );
+    arena.last_migration_generation = arena.current_generation;
+
+    msg!(
+        "Migration at generation {}: migrants={}",
+        arena.current_generation,
+        migrants.len()
+    );
+    Ok(())
+}
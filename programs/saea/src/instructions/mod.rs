@@ -1,13 +1,47 @@
 pub mod advance_generation;
+pub mod benchmark;
+pub mod configure_completion;
+pub mod configure_histogram;
+pub mod configure_islands;
+pub mod configure_mutation;
+pub mod configure_pareto;
+pub mod configure_selection;
 pub mod initialize_arena;
+pub mod initialize_hall_of_fame;
+pub mod initialize_novelty_archive;
+pub mod migrate;
+pub mod mutate_genome;
 pub mod prune_agent;
+pub mod rank_pareto;
 pub mod register_agent;
 pub mod run_round;
+pub mod set_fitness_mode;
+pub mod simulate_fitness;
+pub mod speciation;
 pub mod submit_genome;
+pub mod tournament;
+pub mod update_ratings;
 
 pub use advance_generation::*;
+pub use benchmark::*;
+pub use configure_completion::*;
+pub use configure_histogram::*;
+pub use configure_islands::*;
+pub use configure_mutation::*;
+pub use configure_pareto::*;
+pub use configure_selection::*;
 pub use initialize_arena::*;
+pub use initialize_hall_of_fame::*;
+pub use initialize_novelty_archive::*;
+pub use migrate::*;
+pub use mutate_genome::*;
 pub use prune_agent::*;
+pub use rank_pareto::*;
 pub use register_agent::*;
 pub use run_round::*;
+pub use set_fitness_mode::*;
+pub use simulate_fitness::*;
+pub use speciation::*;
 pub use submit_genome::*;
+pub use tournament::*;
+pub use update_ratings::*;
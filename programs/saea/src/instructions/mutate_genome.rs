@@ -0,0 +1,121 @@
+use crate::errors::SaeaError;
+use crate::events::GenomeSubmitted;
+use crate::state::{AgentAccount, Arena, Round};
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::{hash, hashv};
+
+/// Smallest step size a strategy gene can shrink to, so it can still grow.
+pub const MIN_STEP_SIZE: u8 = 4;
+
+/// Log-normal step-size multipliers in percent, symmetric in log space.
+const STEP_MULTIPLIERS: [u32; 8] = [70, 78, 87, 100, 100, 115, 128, 143];
+
+/// Scales the Irwin-Hall noise (std ~148) so a gene moves with a standard
+/// deviation of about a quarter of its step size.
+const NOISE_DIVISOR: i32 = 592;
+
+#[derive(Accounts)]
+pub struct MutateGenome<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+        constraint = arena.strategy_genes > 0 @ SaeaError::SelfAdaptationDisabled,
+        constraint = arena.current_round > 0 @ SaeaError::RoundRequired,
+    )]
+    pub arena: Account<'info, Arena>,
+    /// Latest round, whose seed drives the mutation so the owner cannot
+    /// choose its outcome.
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &arena.current_round.to_le_bytes()],
+        bump = round.bump,
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        constraint = agent.owner == owner.key() @ SaeaError::Unauthorized,
+        constraint = agent.is_active @ SaeaError::AgentNotActive,
+    )]
+    pub agent: Account<'info, AgentAccount>,
+    pub owner: Signer<'info>,
+}
+
+/// Evolution-strategy style self-adaptive mutation.
+///
+/// The first `strategy_genes` bytes are per-gene step sizes. Each is first
+/// perturbed by a log-normal factor, then every object gene `j` takes a
+/// near-normal step scaled by step size `j % strategy_genes`. All randomness
+/// comes from `seed`, so the result is reproducible.
+pub fn self_adaptive_mutate(genome: &[u8], strategy_genes: usize, seed: &[u8; 32]) -> Vec<u8> {
+    let mut child = genome.to_vec();
+    let draw = |i: usize| hashv(&[seed.as_ref(), &(i as u32).to_le_bytes()]).to_bytes();
+
+    for (i, step) in child.iter_mut().take(strategy_genes).enumerate() {
+        let multiplier = STEP_MULTIPLIERS[(draw(i)[0] & 7) as usize];
+        *step = (*step as u32 * multiplier / 100).clamp(MIN_STEP_SIZE as u32, 255) as u8;
+    }
+
+    for j in strategy_genes..child.len() {
+        let bytes = draw(j);
+        // Sum of four uniform bytes approximates a normal around 510.
+        let noise = bytes[..4].iter().map(|&b| b as i32).sum::<i32>() - 510;
+        let step = child[(j - strategy_genes) % strategy_genes] as i32;
+        child[j] = (child[j] as i32 + noise * step / NOISE_DIVISOR).clamp(0, 255) as u8;
+    }
+
+    child
+}
+
+/// Seed of an agent's next self-adaptive mutation: fixed by the latest
+/// round's seed, the agent and its mutation count.
+pub fn mutation_seed(round_seed: &[u8; 32], agent: &Pubkey, mutation_count: u64) -> [u8; 32] {
+    hashv(&[
+        round_seed.as_ref(),
+        agent.as_ref(),
+        &mutation_count.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub fn handle_mutate_genome(ctx: Context<MutateGenome>) -> Result<()> {
+    let arena = &mut ctx.accounts.arena;
+    let agent = &mut ctx.accounts.agent;
+    let strategy_genes = arena.strategy_genes as usize;
+    require!(
+        agent.genome.len() > strategy_genes,
+        SaeaError::InvalidGenomeLength
+    );
+
+    let seed = mutation_seed(&ctx.accounts.round.seed, &agent.key(), agent.mutation_count);
+    let child = self_adaptive_mutate(&agent.genome, strategy_genes, &seed);
+
+    agent.parent_genome_hash = hash(&agent.genome).to_bytes();
+    agent.parent = agent.key();
+    agent.genome = child;
+    agent.mutation_count = agent
+        .mutation_count
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+    agent.generation = arena.current_generation;
+    arena.generation_tally.genomes_submitted = arena
+        .generation_tally
+        .genomes_submitted
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+
+    emit!(GenomeSubmitted {
+        agent: agent.key(),
+        genome_hash: hash(&agent.genome).to_bytes(),
+        generation: agent.generation,
+        mutation_count: agent.mutation_count,
+        parent: agent.parent,
+    });
+
+    msg!(
+        "Genome self-mutated: mutation_count={}, step_sizes={:?}",
+        agent.mutation_count,
+        &agent.genome[..strategy_genes]
+    );
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::errors::SaeaError;
 use crate::events::AgentPruned;
-use crate::state::{AgentAccount, Arena};
+use crate::state::{AgentAccount, Arena, Population, Round};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -15,9 +15,30 @@ pub struct PruneAgent<'info> {
     #[account(
         mut,
         constraint = agent.is_active @ SaeaError::AgentNotActive,
-        constraint = agent.fitness < arena.min_fitness_threshold @ SaeaError::AgentAboveThreshold,
+        constraint = agent.is_prunable(&arena) @ SaeaError::AgentAboveThreshold,
+        constraint = arena.island_count == 1 || arena.island_populations[agent.island as usize] > 1
+            @ SaeaError::IslandDepleted,
     )]
     pub agent: Account<'info, AgentAccount>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    /// Latest round, required once the arena has one: pruning an agent the
+    /// open round still expects lowers its `eligible_agents`, and pruning
+    /// one it scored closes it to `update_ratings`.
+    #[account(
+        mut,
+        seeds = [Round::SEED, arena.key().as_ref(), &arena.current_round.to_le_bytes()],
+        bump = round.bump,
+    )]
+    pub round: Option<Account<'info, Round>>,
+    /// CHECK: the agent's owner, who paid for its registry entry and
+    /// receives the rent freed from it.
+    #[account(mut, address = agent.owner)]
+    pub owner: UncheckedAccount<'info>,
     #[account(
         constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
     )]
@@ -28,21 +49,52 @@ pub fn handle_prune_agent(ctx: Context<PruneAgent>) -> Result<()> {
     let arena = &mut ctx.accounts.arena;
     let agent = &mut ctx.accounts.agent;
 
+    require!(
+        arena.current_round == 0 || ctx.accounts.round.is_some(),
+        SaeaError::RoundRequired
+    );
+    if let Some(round) = ctx.accounts.round.as_mut() {
+        if !round.is_complete && round.expects(&agent.key(), agent) {
+            round.eligible_agents = round.eligible_agents.saturating_sub(1);
+        }
+        if agent.last_round == round.round_number {
+            round.agents_removed = round
+                .agents_removed
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+        }
+    }
+
+    let fitness = agent.selection_fitness(arena);
     agent.is_active = false;
     arena.active_agents = arena
         .active_agents
         .checked_sub(1)
         .ok_or(SaeaError::ArithmeticOverflow)?;
+    arena.island_populations[agent.island as usize] = arena.island_populations
+        [agent.island as usize]
+        .checked_sub(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+    Population::remove(
+        &ctx.accounts.population,
+        &agent.key(),
+        &ctx.accounts.owner.to_account_info(),
+    )?;
+    arena.generation_tally.agents_pruned = arena
+        .generation_tally
+        .agents_pruned
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
 
     emit!(AgentPruned {
         agent: agent.key(),
-        fitness: agent.fitness,
+        fitness,
         generation: agent.generation,
     });
 
     msg!(
         "Agent pruned: fitness={}, gen={}",
-        agent.fitness,
+        fitness,
         agent.generation
     );
     Ok(())
@@ -0,0 +1,174 @@
+use crate::errors::SaeaError;
+use crate::events::ParetoRanked;
+use crate::state::{
+    AgentAccount, Arena, FitnessMode, Population, Round, RoundKind, FITNESS_OBJECTIVES,
+};
+use anchor_lang::prelude::*;
+
+/// Maximum number of agents sorted into fronts in one instruction, and so
+/// the largest `max_agents` an arena with Pareto selection may have.
+pub const MAX_PARETO_BATCH: usize = 16;
+
+/// Crowding contribution of an objective whose neighbours span its whole range.
+pub const CROWDING_SCALE: u64 = 1000;
+
+#[derive(Accounts)]
+pub struct RankPareto<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &round.round_number.to_le_bytes()],
+        bump = round.bump,
+        constraint = round.is_complete @ SaeaError::RoundNotComplete,
+        constraint = round.kind == RoundKind::Solo @ SaeaError::WrongRoundKind,
+        constraint = round.fitness_mode != FitnessMode::Benchmark @ SaeaError::ParetoNeedsObjectives,
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    // remaining_accounts: every AgentAccount scored in `round`, writable
+}
+
+/// `a` is at least as good as `b` on every objective and better on one.
+pub fn dominates(a: &[u64; FITNESS_OBJECTIVES], b: &[u64; FITNESS_OBJECTIVES]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+}
+
+/// NSGA-II non-dominated sorting: the front index of each point, where
+/// front 0 is dominated by nothing and front f only by earlier fronts.
+pub fn non_dominated_fronts(points: &[[u64; FITNESS_OBJECTIVES]]) -> Vec<u8> {
+    let n = points.len();
+    let mut dominated_by = vec![0usize; n];
+    let mut dominates_list = vec![Vec::new(); n];
+    for i in 0..n {
+        for j in 0..n {
+            if i != j && dominates(&points[i], &points[j]) {
+                dominates_list[i].push(j);
+                dominated_by[j] += 1;
+            }
+        }
+    }
+
+    let mut fronts = vec![0u8; n];
+    let mut current: Vec<usize> = (0..n).filter(|&i| dominated_by[i] == 0).collect();
+    let mut front = 0u8;
+    while !current.is_empty() {
+        let mut next = Vec::new();
+        for &i in &current {
+            fronts[i] = front;
+            for &j in &dominates_list[i] {
+                dominated_by[j] -= 1;
+                if dominated_by[j] == 0 {
+                    next.push(j);
+                }
+            }
+        }
+        current = next;
+        front += 1;
+    }
+    fronts
+}
+
+/// NSGA-II crowding distance of each point within its front, each
+/// objective contributing up to `CROWDING_SCALE`; boundary points get
+/// `u64::MAX` so they are always preferred.
+pub fn crowding_distances(points: &[[u64; FITNESS_OBJECTIVES]], fronts: &[u8]) -> Vec<u64> {
+    let mut distances = vec![0u64; points.len()];
+    let last_front = fronts.iter().copied().max().unwrap_or(0);
+    for front in 0..=last_front {
+        let members: Vec<usize> = (0..points.len()).filter(|&i| fronts[i] == front).collect();
+        for objective in 0..FITNESS_OBJECTIVES {
+            let mut sorted = members.clone();
+            sorted.sort_by_key(|&i| points[i][objective]);
+            let (Some(&low), Some(&high)) = (sorted.first(), sorted.last()) else {
+                continue;
+            };
+            distances[low] = u64::MAX;
+            distances[high] = u64::MAX;
+            let range = points[high][objective] - points[low][objective];
+            if range == 0 {
+                continue;
+            }
+            for w in sorted.windows(3) {
+                let gap = points[w[2]][objective] - points[w[0]][objective];
+                distances[w[1]] = distances[w[1]].saturating_add(gap * CROWDING_SCALE / range);
+            }
+        }
+    }
+    distances
+}
+
+/// Rank every agent scored in a completed solo round by Pareto front over
+/// its recorded objectives. Fronts are relative to the round, so all of its
+/// agents are ranked in one batch of at most `MAX_PARETO_BATCH`.
+pub fn handle_rank_pareto<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RankPareto<'info>>,
+) -> Result<()> {
+    let round = &ctx.accounts.round;
+    let scored: Vec<Pubkey> = {
+        let info = ctx.accounts.population.to_account_info();
+        let data = info.try_borrow_data()?;
+        Population::entries(&data)
+            .iter()
+            .filter(|e| e.last_round == round.round_number)
+            .map(|e| e.agent)
+            .collect()
+    };
+    require!(
+        (1..=MAX_PARETO_BATCH).contains(&scored.len())
+            && ctx.remaining_accounts.len() == scored.len(),
+        SaeaError::InvalidParetoBatch
+    );
+
+    let mut agents = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts {
+        require!(info.is_writable, SaeaError::InvalidParetoBatch);
+        require!(scored.contains(info.key), SaeaError::AgentNotInRound);
+        let agent = Account::<AgentAccount>::try_from(info)?;
+        require!(
+            agent.last_round == round.round_number,
+            SaeaError::AgentNotInRound
+        );
+        agents.push(agent);
+    }
+    let mut keys: Vec<Pubkey> = agents.iter().map(|a| a.key()).collect();
+    keys.sort();
+    keys.dedup();
+    require!(keys.len() == agents.len(), SaeaError::InvalidParetoBatch);
+
+    let points: Vec<[u64; FITNESS_OBJECTIVES]> =
+        agents.iter().map(|a| a.breakdown.objectives()).collect();
+    let fronts = non_dominated_fronts(&points);
+    let distances = crowding_distances(&points, &fronts);
+
+    for ((agent, front), crowding_distance) in agents.iter_mut().zip(fronts).zip(distances) {
+        agent.pareto_front = front;
+        agent.crowding_distance = crowding_distance;
+        agent.pareto_round = round.round_number;
+        agent.exit(&crate::ID)?;
+
+        emit!(ParetoRanked {
+            agent: agent.key(),
+            round_number: round.round_number,
+            front,
+            crowding_distance,
+        });
+    }
+
+    msg!(
+        "Pareto ranked: round={}, agents={}",
+        round.round_number,
+        agents.len()
+    );
+    Ok(())
+}
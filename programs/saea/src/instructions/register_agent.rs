@@ -1,6 +1,9 @@
 use crate::errors::SaeaError;
 use crate::events::AgentRegistered;
-use crate::state::{AgentAccount, Arena, MAX_GENOME_LEN};
+use crate::state::{
+    AgentAccount, Arena, FitnessBreakdown, Population, INITIAL_RATING, MAX_FITNESS_WINDOW,
+    MAX_GENOME_LEN,
+};
 use anchor_lang::prelude::*;
 use solana_sha256_hasher::hash;
 
@@ -22,6 +25,15 @@ pub struct RegisterAgent<'info> {
         bump,
     )]
     pub agent: Account<'info, AgentAccount>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+        realloc = Population::space(arena.active_agents as usize + 1),
+        realloc::payer = owner,
+        realloc::zero = false,
+    )]
+    pub population: AccountLoader<'info, Population>,
     #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
@@ -50,9 +62,28 @@ pub fn handle_register_agent(ctx: Context<RegisterAgent>, genome: Vec<u8>) -> Re
     agent.total_fitness = 0;
     agent.is_active = true;
     agent.registered_at = clock.unix_timestamp;
+    agent.registered_round = arena.current_round;
     agent.last_round = 0;
+    agent.decayed_fitness = 0;
+    agent.recent_fitness = [0u64; MAX_FITNESS_WINDOW];
+    agent.matches_won = 0;
+    agent.matches_lost = 0;
+    agent.matches_drawn = 0;
+    agent.rating = INITIAL_RATING;
+    agent.rated_round = 0;
+    agent.previous_rating = INITIAL_RATING;
+    agent.island = arena.next_island();
+    agent.species = 0;
+    agent.shared_fitness = 0;
+    agent.shared_round = 0;
+    agent.breakdown = FitnessBreakdown::default();
+    agent.pareto_front = 0;
+    agent.crowding_distance = 0;
+    agent.pareto_round = 0;
     agent.bump = ctx.bumps.agent;
 
+    Population::push(&ctx.accounts.population, agent.key(), agent.island)?;
+
     arena.total_agents = arena
         .total_agents
         .checked_add(1)
@@ -61,6 +92,15 @@ pub fn handle_register_agent(ctx: Context<RegisterAgent>, genome: Vec<u8>) -> Re
         .active_agents
         .checked_add(1)
         .ok_or(SaeaError::ArithmeticOverflow)?;
+    arena.island_populations[agent.island as usize] = arena.island_populations
+        [agent.island as usize]
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
+    arena.generation_tally.agents_born = arena
+        .generation_tally
+        .agents_born
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
 
     emit!(AgentRegistered {
         agent: agent.key(),
@@ -70,8 +110,9 @@ pub fn handle_register_agent(ctx: Context<RegisterAgent>, genome: Vec<u8>) -> Re
     });
 
     msg!(
-        "Agent registered: gen={}, genome_len={}",
+        "Agent registered: gen={}, island={}, genome_len={}",
         agent.generation,
+        agent.island,
         agent.genome.len()
     );
     Ok(())
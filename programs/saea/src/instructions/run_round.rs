@@ -1,6 +1,13 @@
 use crate::errors::SaeaError;
-use crate::events::{AgentScored, RoundCompleted};
-use crate::state::{AgentAccount, Arena, Round};
+use crate::events::{
+    AgentScored, HallOfFameUpdated, MutationRateAdjusted, NoveltyArchived, RoundCompleted,
+};
+use crate::instructions::simulate_fitness::FitnessSimulation;
+use crate::state::{
+    AgentAccount, Arena, Benchmark, CompletionPolicy, FitnessBreakdown, FitnessMode,
+    FitnessTargets, HallOfFame, HallOfFameEntry, NoveltyArchive, Population, Round, RoundKind,
+    SpeciesRegistry, MAX_GENOME_LEN,
+};
 use anchor_lang::prelude::*;
 use solana_sha256_hasher::hashv;
 
@@ -29,9 +36,16 @@ pub struct RunRound<'info> {
     pub system_program: Program<'info, System>,
 }
 
-/// Deterministic scoring function (v2 — improved with sequence bonus).
-///
-/// The fitness of a genome is computed from five components:
+/// Deterministic scoring function (v2 — improved with sequence bonus):
+/// the sum of the `compute_fitness_breakdown` components.
+pub fn compute_fitness(genome: &[u8], round_seed: &[u8; 32]) -> u64 {
+    compute_fitness_breakdown(genome, round_seed)
+        .objectives()
+        .iter()
+        .sum()
+}
+
+/// The five fitness components, separately:
 /// 1. Target proximity: closeness of each gene to a hash-derived target
 /// 2. Diversity bonus: reward for genome variance
 /// 3. Balance bonus: reward for centered gene distributions
@@ -42,19 +56,64 @@ pub struct RunRound<'info> {
 /// seed-derived ordering (ascending or descending). This creates a
 /// richer fitness landscape that rewards structural patterns, not
 /// just individual gene values.
-pub fn compute_fitness(genome: &[u8], round_seed: &[u8; 32]) -> u64 {
+///
+/// Genomes longer than `MAX_GENOME_LEN` are never stored on-chain but are
+/// still scored in full, hashing each target that `FitnessTargets` has no
+/// room for.
+pub fn compute_fitness_breakdown(genome: &[u8], round_seed: &[u8; 32]) -> FitnessBreakdown {
+    if genome.len() <= MAX_GENOME_LEN {
+        return compute_fitness_breakdown_with_targets(
+            genome,
+            round_seed,
+            &FitnessTargets::derive(round_seed),
+        );
+    }
+    breakdown(
+        genome,
+        round_seed,
+        |i| hashv(&[round_seed.as_ref(), &[i as u8]]).to_bytes()[0],
+        |i| hashv(&[round_seed.as_ref(), &[i as u8], &[0xFF]]).to_bytes()[0] > 127,
+    )
+}
+
+/// `compute_fitness_breakdown` with the seed's targets already derived, as
+/// stored in `Round::targets`; performs no hashing. Only genomes of at most
+/// `MAX_GENOME_LEN` genes have targets.
+pub fn compute_fitness_breakdown_with_targets(
+    genome: &[u8],
+    round_seed: &[u8; 32],
+    targets: &FitnessTargets,
+) -> FitnessBreakdown {
+    debug_assert!(
+        genome.len() <= MAX_GENOME_LEN,
+        "genome exceeds MAX_GENOME_LEN"
+    );
+    breakdown(
+        genome,
+        round_seed,
+        |i| targets.gene_targets[i],
+        |i| targets.should_ascend(i),
+    )
+}
+
+/// The scoring itself, given the proximity target of each gene and whether
+/// each consecutive pair should ascend.
+fn breakdown(
+    genome: &[u8],
+    round_seed: &[u8; 32],
+    gene_target: impl Fn(usize) -> u8,
+    should_ascend: impl Fn(usize) -> bool,
+) -> FitnessBreakdown {
     let genome_len = genome.len() as u64;
     if genome_len == 0 {
-        return 0;
+        return FitnessBreakdown::default();
     }
 
     // Component 1: Target proximity score
     let mut proximity_score: u64 = 0;
     for (i, &gene) in genome.iter().enumerate() {
-        let target_hash = hashv(&[round_seed.as_ref(), &[i as u8]]);
-        let target = target_hash.to_bytes()[0];
-        let diff = gene.abs_diff(target);
-        proximity_score += (255 - diff as u64) as u64;
+        let diff = gene.abs_diff(gene_target(i));
+        proximity_score += 255 - diff as u64;
     }
 
     // Component 2: Diversity bonus
@@ -95,8 +154,7 @@ pub fn compute_fitness(genome: &[u8], round_seed: &[u8; 32]) -> u64 {
     let mut sequence_score: u64 = 0;
     if genome.len() > 1 {
         for i in 0..genome.len() - 1 {
-            let direction_hash = hashv(&[round_seed.as_ref(), &[i as u8], &[0xFF]]);
-            let should_ascend = direction_hash.to_bytes()[0] > 127;
+            let should_ascend = should_ascend(i);
             let is_ascending = genome[i + 1] >= genome[i];
             if should_ascend == is_ascending {
                 // Bonus proportional to how strongly the ordering holds
@@ -110,11 +168,55 @@ pub fn compute_fitness(genome: &[u8], round_seed: &[u8; 32]) -> u64 {
         }
     }
 
-    proximity_score + diversity_bonus + balance_bonus + pattern_score + sequence_score
+    FitnessBreakdown {
+        proximity: proximity_score,
+        diversity: diversity_bonus,
+        balance: balance_bonus,
+        pattern: pattern_score,
+        sequence: sequence_score,
+        novelty: 0,
+    }
 }
 
 pub fn handle_run_round(ctx: Context<RunRound>) -> Result<()> {
-    let arena = &mut ctx.accounts.arena;
+    start_round(
+        &mut ctx.accounts.arena,
+        &mut ctx.accounts.round,
+        ctx.bumps.round,
+        RoundKind::Solo,
+    )
+}
+
+/// Start a round in which only agents living on `island` can be scored.
+pub fn handle_run_island_round(ctx: Context<RunRound>, island: u8) -> Result<()> {
+    require!(
+        island < ctx.accounts.arena.island_count,
+        SaeaError::InvalidIsland
+    );
+    start_round(
+        &mut ctx.accounts.arena,
+        &mut ctx.accounts.round,
+        ctx.bumps.round,
+        RoundKind::Solo,
+    )?;
+    let round = &mut ctx.accounts.round;
+    round.island = Some(island);
+    round.eligible_agents = ctx.accounts.arena.island_populations[island as usize];
+    msg!(
+        "Round {} scoped to island {}",
+        ctx.accounts.round.round_number,
+        island
+    );
+    Ok(())
+}
+
+/// Initialize the next round account and advance `Arena::current_round`.
+pub(crate) fn start_round(
+    arena: &mut Account<Arena>,
+    round: &mut Round,
+    bump: u8,
+    kind: RoundKind,
+) -> Result<()> {
     let clock = Clock::get()?;
 
     let new_round_number = arena
@@ -122,34 +224,27 @@ pub fn handle_run_round(ctx: Context<RunRound>) -> Result<()> {
         .checked_add(1)
         .ok_or(SaeaError::ArithmeticOverflow)?;
 
-    // Generate deterministic round seed
-    let round_seed = hashv(&[
-        arena.key().as_ref(),
-        &new_round_number.to_le_bytes(),
-        &arena.current_generation.to_le_bytes(),
-        &clock.unix_timestamp.to_le_bytes(),
-    ])
-    .to_bytes();
-
-    let round = &mut ctx.accounts.round;
-    round.arena = arena.key();
-    round.round_number = new_round_number;
-    round.generation = arena.current_generation;
-    round.participants = 0;
-    round.best_fitness = 0;
-    round.worst_fitness = u64::MAX;
-    round.average_fitness = 0;
-    round.total_fitness = 0;
-    round.seed = round_seed;
-    round.started_at = clock.unix_timestamp;
-    round.completed_at = 0;
-    round.is_complete = false;
-    round.bump = ctx.bumps.round;
+    let round_seed = Round::derive_seed(
+        &arena.key(),
+        new_round_number,
+        arena.current_generation,
+        clock.unix_timestamp,
+    );
+    *round = Round::new(
+        arena.key(),
+        arena,
+        new_round_number,
+        round_seed,
+        clock.unix_timestamp,
+        kind,
+        bump,
+    );
 
     arena.current_round = new_round_number;
 
     msg!(
-        "Round {} started for generation {}",
+        "{:?} round {} started for generation {}",
+        kind,
         new_round_number,
         arena.current_generation
     );
@@ -168,14 +263,51 @@ pub struct ScoreAgent<'info> {
         seeds = [Round::SEED, arena.key().as_ref(), &round.round_number.to_le_bytes()],
         bump = round.bump,
         constraint = !round.is_complete @ SaeaError::RoundAlreadyComplete,
+        constraint = round.kind == RoundKind::Solo @ SaeaError::WrongRoundKind,
     )]
     pub round: Account<'info, Round>,
     #[account(
         mut,
         constraint = agent.is_active @ SaeaError::AgentNotActive,
         constraint = agent.last_round < round.round_number @ SaeaError::AlreadyParticipated,
+        constraint = agent.registered_round < round.round_number @ SaeaError::AgentNotEligible,
+        constraint = round.island.is_none_or(|island| island == agent.island) @ SaeaError::WrongIsland,
     )]
     pub agent: Account<'info, AgentAccount>,
+    /// Required when the arena scores in `FitnessMode::Benchmark`.
+    #[account(
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump = benchmark.bump,
+        constraint = benchmark.is_frozen @ SaeaError::BenchmarkNotFrozen,
+    )]
+    pub benchmark: Option<Account<'info, Benchmark>>,
+    /// Required once the arena has a hall of fame; keeps record-breaking scores.
+    #[account(
+        mut,
+        seeds = [HallOfFame::SEED, arena.key().as_ref()],
+        bump = hall_of_fame.bump,
+    )]
+    pub hall_of_fame: Option<Account<'info, HallOfFame>>,
+    /// Required while the arena speciates; assigns the agent its species.
+    #[account(
+        mut,
+        seeds = [SpeciesRegistry::SEED, arena.key().as_ref()],
+        bump = species.bump,
+    )]
+    pub species: Option<Account<'info, SpeciesRegistry>>,
+    /// Required when the arena scores in `FitnessMode::Novelty`.
+    #[account(
+        mut,
+        seeds = [NoveltyArchive::SEED, arena.key().as_ref()],
+        bump = novelty_archive.bump,
+    )]
+    pub novelty_archive: Option<Account<'info, NoveltyArchive>>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
     #[account(
         constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
     )]
@@ -183,12 +315,143 @@ pub struct ScoreAgent<'info> {
 }
 
 pub fn score_agent_handler(ctx: Context<ScoreAgent>) -> Result<()> {
+    let arena = &ctx.accounts.arena;
     let round = &mut ctx.accounts.round;
     let agent = &mut ctx.accounts.agent;
+    require!(
+        !arena.hall_of_fame_enabled || ctx.accounts.hall_of_fame.is_some(),
+        SaeaError::HallOfFameRequired
+    );
+
+    let genes = agent.object_genes(arena);
+    let FitnessSimulation {
+        fitness,
+        breakdown,
+        novelty,
+    } = evaluate_genes(
+        round,
+        genes,
+        ctx.accounts.benchmark.as_deref(),
+        ctx.accounts.novelty_archive.as_deref(),
+    )?;
+
+    if let Some(archive) = ctx.accounts.novelty_archive.as_mut() {
+        if round.fitness_mode == FitnessMode::Novelty && novelty > archive.threshold {
+            let behavior = NoveltyArchive::behavior(genes);
+            archive.archive(behavior);
+            emit!(NoveltyArchived {
+                agent: agent.key(),
+                round_number: round.round_number,
+                novelty,
+                behavior,
+            });
+        }
+    }
 
-    let fitness = compute_fitness(&agent.genome, &round.seed);
+    record_score(
+        arena,
+        round,
+        agent,
+        ctx.accounts.species.as_deref_mut(),
+        fitness,
+        breakdown,
+    )?;
+    Population::record_score(&ctx.accounts.population, &agent.key(), agent, arena)?;
+
+    if let Some(hall_of_fame) = ctx.accounts.hall_of_fame.as_mut() {
+        record_hall_of_fame(hall_of_fame, agent, fitness, round.round_number);
+    }
+
+    msg!("Agent scored: fitness={}", fitness);
+    Ok(())
+}
+
+/// Score object genes under the round's fitness mode without touching any
+/// account; shared by `score_agent` and `simulate_fitness`. Seeded and
+/// novelty scores read the targets stored on the round rather than hashing
+/// the seed per gene.
+pub fn evaluate_genes(
+    round: &Round,
+    genes: &[u8],
+    benchmark: Option<&Benchmark>,
+    novelty_archive: Option<&NoveltyArchive>,
+) -> Result<FitnessSimulation> {
+    // Benchmark accuracy has no components, so it records an empty breakdown.
+    let mut breakdown = match round.fitness_mode {
+        FitnessMode::Seeded | FitnessMode::Novelty => {
+            compute_fitness_breakdown_with_targets(genes, &round.seed, &round.targets)
+        }
+        FitnessMode::Benchmark => FitnessBreakdown::default(),
+    };
+    let mut novelty = 0;
+    let fitness = match round.fitness_mode {
+        FitnessMode::Seeded => breakdown.total().ok_or(SaeaError::ArithmeticOverflow)?,
+        FitnessMode::Benchmark => benchmark
+            .ok_or(SaeaError::BenchmarkRequired)?
+            .evaluate(genes),
+        FitnessMode::Novelty => {
+            let archive = novelty_archive.ok_or(SaeaError::NoveltyArchiveRequired)?;
+            novelty = archive.novelty(&NoveltyArchive::behavior(genes));
+            breakdown.novelty = novelty
+                .checked_mul(archive.novelty_weight)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            breakdown.total().ok_or(SaeaError::ArithmeticOverflow)?
+        }
+    };
+
+    Ok(FitnessSimulation {
+        fitness,
+        breakdown,
+        novelty,
+    })
+}
 
+/// Enter a round score into the hall of fame if it ranks there.
+pub(crate) fn record_hall_of_fame(
+    hall_of_fame: &mut HallOfFame,
+    agent: &Account<AgentAccount>,
+    fitness: u64,
+    round_number: u64,
+) {
+    if fitness <= hall_of_fame.min_fitness() {
+        return;
+    }
+    let entry = HallOfFameEntry {
+        agent: agent.key(),
+        genome: agent.genome.clone(),
+        fitness,
+        round_number,
+        generation: agent.generation,
+    };
+    if let Some(rank) = hall_of_fame.try_insert(entry) {
+        emit!(HallOfFameUpdated {
+            agent: agent.key(),
+            fitness,
+            round_number,
+            generation: agent.generation,
+            rank: rank as u8,
+        });
+    }
+}
+
+/// Apply a round score to the agent's history and the round statistics,
+/// assign its species when the arena speciates, then emit `AgentScored`.
+pub(crate) fn record_score(
+    arena: &Arena,
+    round: &mut Round,
+    agent: &mut Account<AgentAccount>,
+    species: Option<&mut SpeciesRegistry>,
+    fitness: u64,
+    breakdown: FitnessBreakdown,
+) -> Result<()> {
+    if arena.speciation_enabled {
+        let species = species.ok_or(SaeaError::SpeciesRequired)?;
+        agent.species = species.assign(agent.object_genes(arena), round.round_number);
+    }
+
+    agent.record_fitness(fitness, arena.fitness_decay_retention);
     agent.fitness = fitness;
+    agent.breakdown = breakdown;
     agent.last_round = round.round_number;
     agent.rounds_participated = agent
         .rounds_participated
@@ -199,38 +462,23 @@ pub fn score_agent_handler(ctx: Context<ScoreAgent>) -> Result<()> {
         .checked_add(fitness)
         .ok_or(SaeaError::ArithmeticOverflow)?;
 
-    round.participants = round
-        .participants
-        .checked_add(1)
-        .ok_or(SaeaError::ArithmeticOverflow)?;
-    round.total_fitness = round
-        .total_fitness
-        .checked_add(fitness)
-        .ok_or(SaeaError::ArithmeticOverflow)?;
-
-    if fitness > round.best_fitness {
-        round.best_fitness = fitness;
-    }
-    if fitness < round.worst_fitness {
-        round.worst_fitness = fitness;
-    }
-
-    let genome_hash = solana_sha256_hasher::hash(&agent.genome).to_bytes();
+    let genome_hash = round.record_score(agent.key(), fitness, &agent.genome)?;
 
     emit!(AgentScored {
         agent: agent.key(),
         round_number: round.round_number,
         fitness,
         genome_hash,
+        species: agent.species,
+        breakdown,
     });
-
-    msg!("Agent scored: fitness={}", fitness);
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct CompleteRound<'info> {
     #[account(
+        mut,
         seeds = [Arena::SEED],
         bump = arena.bump,
     )]
@@ -242,6 +490,12 @@ pub struct CompleteRound<'info> {
         constraint = !round.is_complete @ SaeaError::RoundAlreadyComplete,
     )]
     pub round: Account<'info, Round>,
+    /// Whose `last_round` entries show which active agents were scored.
+    #[account(
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
     #[account(
         constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
     )]
@@ -249,28 +503,72 @@ pub struct CompleteRound<'info> {
 }
 
 pub fn complete_round_handler(ctx: Context<CompleteRound>) -> Result<()> {
+    let arena = &mut ctx.accounts.arena;
     let round = &mut ctx.accounts.round;
     let clock = Clock::get()?;
 
-    round.average_fitness = if round.participants > 0 {
-        round.total_fitness / round.participants
-    } else {
-        0
+    // Count scored agents from the registry rather than trusting the
+    // round's own tally.
+    let scored = {
+        let info = ctx.accounts.population.to_account_info();
+        let data = info.try_borrow_data()?;
+        Population::entries(&data)
+            .iter()
+            .filter(|e| e.last_round == round.round_number)
+            .count() as u64
+    };
+    let agents_skipped = round.eligible_agents.saturating_sub(scored);
+    let complete_enough = match round.completion_policy {
+        CompletionPolicy::Strict => agents_skipped == 0,
+        CompletionPolicy::Quorum => {
+            scored as u128 * 100
+                >= round.eligible_agents as u128 * round.completion_quorum_pct as u128
+        }
+        CompletionPolicy::Lenient => true,
     };
+    require!(complete_enough, SaeaError::RoundQuorumNotMet);
+
     round.completed_at = clock.unix_timestamp;
-    round.is_complete = true;
+    let completed = RoundCompleted {
+        agents_skipped,
+        ..round.finalize()
+    };
 
-    if round.worst_fitness == u64::MAX {
-        round.worst_fitness = 0;
+    // Rounds straddling advance_generation stay out of the new generation's summary.
+    if round.generation == arena.current_generation {
+        let tally = &mut arena.generation_tally;
+        tally.rounds_run = tally
+            .rounds_run
+            .checked_add(1)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+        tally.round_average_sum = tally
+            .round_average_sum
+            .checked_add(round.average_fitness)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+        if round.best_fitness > tally.best_fitness {
+            tally.best_fitness = round.best_fitness;
+            tally.champion = round.best_agent;
+            tally.champion_genome_hash = round.best_genome_hash;
+        }
     }
 
-    emit!(RoundCompleted {
-        round_number: round.round_number,
-        generation: round.generation,
-        participants: round.participants,
-        best_fitness: round.best_fitness,
-        average_fitness: round.average_fitness,
-    });
+    if round.participants > 0 {
+        if let Some(old_rate_bps) = arena.adapt_mutation_rate(round.best_fitness) {
+            emit!(MutationRateAdjusted {
+                round_number: round.round_number,
+                old_rate_bps,
+                new_rate_bps: arena.mutation_rate_bps,
+                best_fitness: round.best_fitness,
+            });
+            msg!(
+                "Mutation rate adjusted: {} -> {} bps",
+                old_rate_bps,
+                arena.mutation_rate_bps
+            );
+        }
+    }
+
+    emit!(completed);
 
     msg!(
         "Round {} completed: best={}, avg={}, participants={}",
@@ -0,0 +1,66 @@
+use crate::errors::SaeaError;
+use crate::events::FitnessModeChanged;
+use crate::state::{Arena, Benchmark, FitnessMode, NoveltyArchive};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFitnessMode<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    /// Required when switching to `FitnessMode::Benchmark`.
+    #[account(
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump = benchmark.bump,
+    )]
+    pub benchmark: Option<Account<'info, Benchmark>>,
+    /// Required when switching to `FitnessMode::Novelty`.
+    #[account(
+        seeds = [NoveltyArchive::SEED, arena.key().as_ref()],
+        bump = novelty_archive.bump,
+    )]
+    pub novelty_archive: Option<Account<'info, NoveltyArchive>>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+/// Change how agents are scored from the next round on; a round already
+/// started keeps the mode it started with.
+pub fn handle_set_fitness_mode(ctx: Context<SetFitnessMode>, mode: FitnessMode) -> Result<()> {
+    if mode == FitnessMode::Benchmark {
+        require!(
+            !ctx.accounts.arena.pareto_selection,
+            SaeaError::ParetoNeedsObjectives
+        );
+        let benchmark = ctx
+            .accounts
+            .benchmark
+            .as_ref()
+            .ok_or(SaeaError::BenchmarkRequired)?;
+        require!(benchmark.is_frozen, SaeaError::BenchmarkNotFrozen);
+    }
+    if mode == FitnessMode::Novelty {
+        require!(
+            ctx.accounts.novelty_archive.is_some(),
+            SaeaError::NoveltyArchiveRequired
+        );
+    }
+
+    let arena = &mut ctx.accounts.arena;
+    let old_mode = arena.fitness_mode;
+    arena.fitness_mode = mode;
+
+    emit!(FitnessModeChanged {
+        old_mode,
+        new_mode: mode,
+    });
+
+    msg!("Fitness mode changed: {:?} -> {:?}", old_mode, mode);
+    Ok(())
+}
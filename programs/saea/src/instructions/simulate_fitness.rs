@@ -0,0 +1,72 @@
+use crate::errors::SaeaError;
+use crate::instructions::run_round::evaluate_genes;
+use crate::state::{
+    Arena, Benchmark, FitnessBreakdown, NoveltyArchive, Round, RoundKind, MAX_GENOME_LEN,
+};
+use anchor_lang::prelude::*;
+
+/// Returned by `simulate_fitness` through the transaction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FitnessSimulation {
+    pub fitness: u64,
+    pub breakdown: FitnessBreakdown,
+    pub novelty: u64, // unweighted novelty; 0 outside FitnessMode::Novelty
+}
+
+#[derive(Accounts)]
+#[instruction(genome: Vec<u8>, round_number: u64)]
+pub struct SimulateFitness<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &round_number.to_le_bytes()],
+        bump = round.bump,
+        constraint = round.kind == RoundKind::Solo @ SaeaError::WrongRoundKind,
+    )]
+    pub round: Account<'info, Round>,
+    /// Required when the arena scores in `FitnessMode::Benchmark`.
+    #[account(
+        seeds = [Benchmark::SEED, arena.key().as_ref()],
+        bump = benchmark.bump,
+        constraint = benchmark.is_frozen @ SaeaError::BenchmarkNotFrozen,
+    )]
+    pub benchmark: Option<Account<'info, Benchmark>>,
+    /// Required when the arena scores in `FitnessMode::Novelty`; read only,
+    /// so a simulated genome is never archived.
+    #[account(
+        seeds = [NoveltyArchive::SEED, arena.key().as_ref()],
+        bump = novelty_archive.bump,
+    )]
+    pub novelty_archive: Option<Account<'info, NoveltyArchive>>,
+}
+
+/// Score `genome` as `score_agent` would in round `round_number`, without
+/// writing any account. Intended for `simulateTransaction`.
+pub fn handle_simulate_fitness(
+    ctx: Context<SimulateFitness>,
+    genome: Vec<u8>,
+    round_number: u64,
+) -> Result<FitnessSimulation> {
+    require!(
+        !genome.is_empty() && genome.len() <= MAX_GENOME_LEN,
+        SaeaError::InvalidGenomeLength
+    );
+
+    let arena = &ctx.accounts.arena;
+    let simulation = evaluate_genes(
+        &ctx.accounts.round,
+        arena.object_genes(&genome),
+        ctx.accounts.benchmark.as_deref(),
+        ctx.accounts.novelty_archive.as_deref(),
+    )?;
+
+    msg!(
+        "Simulated fitness for round {}: {}",
+        round_number,
+        simulation.fitness
+    );
+    Ok(simulation)
+}
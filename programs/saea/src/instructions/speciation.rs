@@ -0,0 +1,119 @@
+use crate::errors::SaeaError;
+use crate::state::{AgentAccount, Arena, GenomeDistance, Population, Round, SpeciesRegistry};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeSpecies<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SpeciesRegistry::INIT_SPACE,
+        seeds = [SpeciesRegistry::SEED, arena.key().as_ref()],
+        bump,
+    )]
+    pub species: Account<'info, SpeciesRegistry>,
+    #[account(
+        mut,
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Turn on speciation: from now on every score assigns a species and
+/// pruning compares shared fitness.
+pub fn handle_initialize_species(
+    ctx: Context<InitializeSpecies>,
+    distance: GenomeDistance,
+    threshold: u32,
+) -> Result<()> {
+    let species = &mut ctx.accounts.species;
+    species.arena = ctx.accounts.arena.key();
+    species.distance = distance;
+    species.threshold = threshold;
+    species.round_number = 0;
+    species.next_species_id = 1;
+    species.entries = Vec::new();
+    species.bump = ctx.bumps.species;
+
+    ctx.accounts.arena.speciation_enabled = true;
+
+    msg!(
+        "Speciation enabled: distance={:?}, threshold={}",
+        distance,
+        threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ShareFitness<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    /// The latest round: species sizes are those of the registry's round,
+    /// which moves on as soon as the next round scores an agent.
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &round.round_number.to_le_bytes()],
+        bump = round.bump,
+        constraint = round.is_complete @ SaeaError::RoundNotComplete,
+        constraint = round.round_number == arena.current_round @ SaeaError::FitnessSharingClosed,
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        seeds = [SpeciesRegistry::SEED, arena.key().as_ref()],
+        bump = species.bump,
+        constraint = species.round_number == round.round_number @ SaeaError::SpeciesRoundMismatch,
+    )]
+    pub species: Account<'info, SpeciesRegistry>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    // remaining_accounts: AgentAccounts scored in `round` (writable)
+}
+
+/// Divide each agent's metric fitness by the size of its species in the
+/// completed round (NEAT-style fitness sharing). Safe to repeat per batch,
+/// until the next round starts.
+pub fn handle_share_fitness<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ShareFitness<'info>>,
+) -> Result<()> {
+    let arena = &ctx.accounts.arena;
+    let round_number = ctx.accounts.round.round_number;
+    let species = &ctx.accounts.species;
+
+    for info in ctx.remaining_accounts {
+        let mut agent = Account::<AgentAccount>::try_from(info)?;
+        require!(agent.last_round == round_number, SaeaError::AgentNotInRound);
+        let members = species.members(agent.species).max(1) as u64;
+        agent.shared_fitness = agent.metric_fitness(arena) / members;
+        agent.shared_round = round_number;
+        agent.exit(&crate::ID)?;
+        Population::update(&ctx.accounts.population, &agent.key(), |entry| {
+            entry.selection_fitness = agent.shared_fitness;
+        })?;
+    }
+
+    msg!(
+        "Fitness shared for round {}: agents={}, species={}",
+        round_number,
+        ctx.remaining_accounts.len(),
+        species.entries.len()
+    );
+    Ok(())
+}
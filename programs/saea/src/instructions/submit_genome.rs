@@ -7,9 +7,11 @@ use solana_sha256_hasher::hash;
 #[derive(Accounts)]
 pub struct SubmitGenome<'info> {
     #[account(
+        mut,
         seeds = [Arena::SEED],
         bump = arena.bump,
         constraint = arena.is_active @ SaeaError::ArenaNotActive,
+        constraint = arena.strategy_genes == 0 @ SaeaError::SelfAdaptationEnabled,
     )]
     pub arena: Account<'info, Arena>,
     #[account(
@@ -21,6 +23,9 @@ pub struct SubmitGenome<'info> {
     pub owner: Signer<'info>,
 }
 
+/// Replace an agent's genome with one bred off-chain. Closed while the
+/// arena self-adapts: genomes then change only through `mutate_genome`, so
+/// owners cannot set their own step sizes.
 pub fn handle_submit_genome(
     ctx: Context<SubmitGenome>,
     new_genome: Vec<u8>,
@@ -31,6 +36,7 @@ pub fn handle_submit_genome(
         SaeaError::InvalidGenomeLength
     );
 
+    let arena = &mut ctx.accounts.arena;
     let agent = &mut ctx.accounts.agent;
 
     // Store the hash of the old genome as parent lineage
@@ -44,7 +50,12 @@ pub fn handle_submit_genome(
         .mutation_count
         .checked_add(1)
         .ok_or(SaeaError::ArithmeticOverflow)?;
-    agent.generation = ctx.accounts.arena.current_generation;
+    agent.generation = arena.current_generation;
+    arena.generation_tally.genomes_submitted = arena
+        .generation_tally
+        .genomes_submitted
+        .checked_add(1)
+        .ok_or(SaeaError::ArithmeticOverflow)?;
 
     emit!(GenomeSubmitted {
         agent: agent.key(),
@@ -0,0 +1,329 @@
+use crate::errors::SaeaError;
+use crate::events::{MatchPlayed, RatingUpdated};
+use crate::instructions::run_round::{record_hall_of_fame, record_score, start_round};
+use crate::instructions::update_ratings::rating_after;
+use crate::state::{
+    AgentAccount, Arena, FitnessBreakdown, HallOfFame, Population, Round, RoundKind,
+    SpeciesRegistry, MAX_TOURNAMENT_ENTRANTS,
+};
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// Number of moves in each iterated prisoner's dilemma match.
+pub const IPD_ITERATIONS: usize = 24;
+
+/// Classic payoffs: temptation, reward, punishment, sucker.
+pub const IPD_TEMPTATION: u64 = 5;
+pub const IPD_REWARD: u64 = 3;
+pub const IPD_PUNISHMENT: u64 = 1;
+pub const IPD_SUCKER: u64 = 0;
+
+/// Fitness awarded for the match outcome, on top of the agent's own payoff.
+pub const MATCH_WIN_FITNESS: u64 = 1000;
+pub const MATCH_DRAW_FITNESS: u64 = 500;
+
+#[derive(Accounts)]
+pub struct RunTournamentRound<'info> {
+    #[account(
+        mut,
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+        constraint = arena.is_active @ SaeaError::ArenaNotActive,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Round::INIT_SPACE,
+        seeds = [Round::SEED, arena.key().as_ref(), &(arena.current_round + 1).to_le_bytes()],
+        bump,
+    )]
+    pub round: Account<'info, Round>,
+    /// Every registered active agent is entered into the draw; at most
+    /// `MAX_TOURNAMENT_ENTRANTS` of them are drawn.
+    #[account(
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    #[account(
+        mut,
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Move chosen by a genome-encoded memory-two strategy table.
+///
+/// Genes are read modulo the genome length; a gene >= 128 cooperates and
+/// an empty genome always defects.
+/// - gene 16: opening move
+/// - genes 17..=20: second move, indexed by (own, opponent) first move
+/// - genes 0..=15: later moves, indexed by both players' last two moves
+fn ipd_move(genome: &[u8], own: &[bool], opponent: &[bool]) -> bool {
+    if genome.is_empty() {
+        return false;
+    }
+    let bit = |b: bool| b as usize;
+    let index = match own.len() {
+        0 => 16,
+        1 => 17 + (bit(own[0]) << 1 | bit(opponent[0])),
+        n => {
+            bit(own[n - 2]) << 3
+                | bit(opponent[n - 2]) << 2
+                | bit(own[n - 1]) << 1
+                | bit(opponent[n - 1])
+        }
+    };
+    genome[index % genome.len()] >= 128
+}
+
+/// Play an iterated prisoner's dilemma between two genomes and return
+/// each side's total payoff.
+pub fn play_prisoners_dilemma(genome_a: &[u8], genome_b: &[u8]) -> (u64, u64) {
+    let mut moves_a = Vec::with_capacity(IPD_ITERATIONS);
+    let mut moves_b = Vec::with_capacity(IPD_ITERATIONS);
+    let (mut payoff_a, mut payoff_b) = (0u64, 0u64);
+
+    for _ in 0..IPD_ITERATIONS {
+        let a = ipd_move(genome_a, &moves_a, &moves_b);
+        let b = ipd_move(genome_b, &moves_b, &moves_a);
+        let (pa, pb) = match (a, b) {
+            (true, true) => (IPD_REWARD, IPD_REWARD),
+            (true, false) => (IPD_SUCKER, IPD_TEMPTATION),
+            (false, true) => (IPD_TEMPTATION, IPD_SUCKER),
+            (false, false) => (IPD_PUNISHMENT, IPD_PUNISHMENT),
+        };
+        payoff_a += pa;
+        payoff_b += pb;
+        moves_a.push(a);
+        moves_b.push(b);
+    }
+
+    (payoff_a, payoff_b)
+}
+
+/// Pairings for a tournament over `entrants`: a seeded draw of at most
+/// `MAX_TOURNAMENT_ENTRANTS` of them, consecutive entrants paired and an
+/// odd entrant out left out.
+pub fn draw_pairings(mut entrants: Vec<Pubkey>, seed: &[u8; 32]) -> Vec<Pubkey> {
+    // Fisher-Yates shuffle run from the back only as far as the drawn
+    // entrants.
+    let len = entrants.len();
+    let drawn = len.min(MAX_TOURNAMENT_ENTRANTS);
+    for i in ((len - drawn).max(1)..len).rev() {
+        let draw = hashv(&[seed.as_ref(), b"pairing", &(i as u32).to_le_bytes()]).to_bytes();
+        let j = u64::from_le_bytes(draw[..8].try_into().unwrap()) % (i as u64 + 1);
+        entrants.swap(i, j as usize);
+    }
+    entrants.drain(..len - drawn);
+    entrants.truncate(drawn & !1);
+    entrants
+}
+
+pub fn handle_run_tournament_round(ctx: Context<RunTournamentRound>) -> Result<()> {
+    let registered: Vec<Pubkey> = {
+        let info = ctx.accounts.population.to_account_info();
+        let data = info.try_borrow_data()?;
+        Population::entries(&data).iter().map(|e| e.agent).collect()
+    };
+    let entrants = registered.len();
+    require!(entrants >= 2, SaeaError::InvalidTournamentEntrants);
+
+    start_round(
+        &mut ctx.accounts.arena,
+        &mut ctx.accounts.round,
+        ctx.bumps.round,
+        RoundKind::Tournament,
+    )?;
+
+    let round = &mut ctx.accounts.round;
+    let pairings = draw_pairings(registered, &round.seed);
+    // Only agents drawn into a match are expected to play.
+    round.eligible_agents = pairings.len() as u64;
+    round.pairings = pairings;
+
+    msg!(
+        "Tournament drawn: entrants={}, matches={}",
+        entrants,
+        round.pairings.len() / 2
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlayMatch<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    #[account(
+        mut,
+        seeds = [Round::SEED, arena.key().as_ref(), &round.round_number.to_le_bytes()],
+        bump = round.bump,
+        constraint = !round.is_complete @ SaeaError::RoundAlreadyComplete,
+        constraint = round.kind == RoundKind::Tournament @ SaeaError::WrongRoundKind,
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        constraint = agent_a.is_active @ SaeaError::AgentNotActive,
+        constraint = agent_a.last_round < round.round_number @ SaeaError::AlreadyParticipated,
+    )]
+    pub agent_a: Account<'info, AgentAccount>,
+    #[account(
+        mut,
+        constraint = agent_b.is_active @ SaeaError::AgentNotActive,
+        constraint = agent_b.last_round < round.round_number @ SaeaError::AlreadyParticipated,
+    )]
+    pub agent_b: Account<'info, AgentAccount>,
+    /// Required while the arena speciates; assigns both agents their species.
+    #[account(
+        mut,
+        seeds = [SpeciesRegistry::SEED, arena.key().as_ref()],
+        bump = species.bump,
+    )]
+    pub species: Option<Account<'info, SpeciesRegistry>>,
+    /// Required while the arena keeps a hall of fame; both match scores are
+    /// entered into it.
+    #[account(
+        mut,
+        seeds = [HallOfFame::SEED, arena.key().as_ref()],
+        bump = hall_of_fame.bump,
+    )]
+    pub hall_of_fame: Option<Account<'info, HallOfFame>>,
+    #[account(
+        mut,
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+}
+
+pub fn handle_play_match(ctx: Context<PlayMatch>, match_index: u16) -> Result<()> {
+    let arena = &ctx.accounts.arena;
+    let round = &mut ctx.accounts.round;
+    let agent_a = &mut ctx.accounts.agent_a;
+    let agent_b = &mut ctx.accounts.agent_b;
+
+    require!(
+        round.pairing(match_index) == Some((agent_a.key(), agent_b.key())),
+        SaeaError::PairingMismatch
+    );
+    require!(
+        !arena.hall_of_fame_enabled || ctx.accounts.hall_of_fame.is_some(),
+        SaeaError::HallOfFameRequired
+    );
+
+    let (payoff_a, payoff_b) =
+        play_prisoners_dilemma(agent_a.object_genes(arena), agent_b.object_genes(arena));
+    let (bonus_a, bonus_b, winner) = match payoff_a.cmp(&payoff_b) {
+        std::cmp::Ordering::Greater => {
+            agent_a.matches_won = agent_a
+                .matches_won
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            agent_b.matches_lost = agent_b
+                .matches_lost
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            (MATCH_WIN_FITNESS, 0, agent_a.key())
+        }
+        std::cmp::Ordering::Less => {
+            agent_a.matches_lost = agent_a
+                .matches_lost
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            agent_b.matches_won = agent_b
+                .matches_won
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            (0, MATCH_WIN_FITNESS, agent_b.key())
+        }
+        std::cmp::Ordering::Equal => {
+            agent_a.matches_drawn = agent_a
+                .matches_drawn
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            agent_b.matches_drawn = agent_b
+                .matches_drawn
+                .checked_add(1)
+                .ok_or(SaeaError::ArithmeticOverflow)?;
+            (MATCH_DRAW_FITNESS, MATCH_DRAW_FITNESS, Pubkey::default())
+        }
+    };
+
+    // Both ratings move on this match's result, from pre-match ratings.
+    let fitness_a = bonus_a + payoff_a;
+    let fitness_b = bonus_b + payoff_b;
+    let ratings = [
+        (
+            agent_a.rating,
+            rating_after(agent_a.rating, fitness_a, &[(agent_b.rating, fitness_b)]),
+        ),
+        (
+            agent_b.rating,
+            rating_after(agent_b.rating, fitness_b, &[(agent_a.rating, fitness_a)]),
+        ),
+    ];
+
+    let mut species = ctx.accounts.species.as_deref_mut();
+    record_score(
+        arena,
+        round,
+        agent_a,
+        species.as_deref_mut(),
+        fitness_a,
+        FitnessBreakdown::default(),
+    )?;
+    record_score(
+        arena,
+        round,
+        agent_b,
+        species,
+        fitness_b,
+        FitnessBreakdown::default(),
+    )?;
+    let population = &ctx.accounts.population;
+    Population::record_score(population, &agent_a.key(), agent_a, arena)?;
+    Population::record_score(population, &agent_b.key(), agent_b, arena)?;
+    if let Some(hall_of_fame) = ctx.accounts.hall_of_fame.as_mut() {
+        record_hall_of_fame(hall_of_fame, agent_a, fitness_a, round.round_number);
+        record_hall_of_fame(hall_of_fame, agent_b, fitness_b, round.round_number);
+    }
+
+    for (agent, (old_rating, new_rating)) in [&mut *agent_a, &mut *agent_b].into_iter().zip(ratings)
+    {
+        agent.set_rating(new_rating, round.round_number);
+        emit!(RatingUpdated {
+            agent: agent.key(),
+            round_number: round.round_number,
+            old_rating,
+            new_rating,
+        });
+    }
+
+    emit!(MatchPlayed {
+        round_number: round.round_number,
+        match_index,
+        agent_a: agent_a.key(),
+        agent_b: agent_b.key(),
+        payoff_a,
+        payoff_b,
+        winner,
+    });
+
+    msg!(
+        "Match {} played: payoffs {}-{}",
+        match_index,
+        payoff_a,
+        payoff_b
+    );
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use crate::errors::SaeaError;
+use crate::events::RatingUpdated;
+use crate::state::{AgentAccount, Arena, Population, Round, RoundKind, RATING_SCALE};
+use anchor_lang::prelude::*;
+
+/// Largest rating change from one round, in whole Elo points.
+pub const ELO_K_FACTOR: u64 = 32;
+
+const PPM: i128 = 1_000_000;
+
+/// Rating gap covered by each step of `EXPECTED_SCORE_PPM`.
+const EXPECTED_SCORE_STEP: u64 = 25 * RATING_SCALE;
+
+/// Expected score `1 / (1 + 10^(d / 400))` in parts per million for rating
+/// gaps d = 0, 25, ..., 800; gaps beyond 800 use the last entry.
+const EXPECTED_SCORE_PPM: [u64; 33] = [
+    500000, 464084, 428537, 393712, 359935, 327490, 296615, 267493, 240253, 214973, 191682, 170367,
+    150980, 133443, 117662, 103523, 90909, 79695, 69758, 60978, 53240, 46435, 40463, 35231, 30653,
+    26654, 23164, 20122, 17472, 15166, 13160, 11416, 9901,
+];
+
+#[derive(Accounts)]
+pub struct UpdateRatings<'info> {
+    #[account(
+        seeds = [Arena::SEED],
+        bump = arena.bump,
+    )]
+    pub arena: Account<'info, Arena>,
+    /// Tournament rounds are rated by `play_match` from their results.
+    /// Opponents are read from the registry, so only the latest round can
+    /// be rated, and only until one of its scored agents is pruned: either
+    /// would change the neighbours of agents still to be rated.
+    #[account(
+        seeds = [Round::SEED, arena.key().as_ref(), &round.round_number.to_le_bytes()],
+        bump = round.bump,
+        constraint = round.is_complete @ SaeaError::RoundNotComplete,
+        constraint = round.kind == RoundKind::Solo @ SaeaError::WrongRoundKind,
+        constraint = round.round_number == arena.current_round @ SaeaError::RatingsClosed,
+        constraint = round.agents_removed == 0 @ SaeaError::RatingsClosed,
+    )]
+    pub round: Account<'info, Round>,
+    #[account(
+        seeds = [Population::SEED, arena.key().as_ref()],
+        bump = population.load()?.bump,
+    )]
+    pub population: AccountLoader<'info, Population>,
+    #[account(
+        mut,
+        constraint = agent.last_round == round.round_number @ SaeaError::AgentNotInRound,
+        constraint = agent.rated_round < round.round_number @ SaeaError::AlreadyRated,
+    )]
+    pub agent: Account<'info, AgentAccount>,
+    #[account(
+        constraint = authority.key() == arena.authority @ SaeaError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+    // remaining_accounts: the agent's `rating_opponents`, read-only
+}
+
+/// Elo expected score of a player rated `own` against `opponent`, in
+/// parts per million, linearly interpolated from `EXPECTED_SCORE_PPM`.
+pub fn expected_score_ppm(own: u64, opponent: u64) -> u64 {
+    let gap = own.abs_diff(opponent);
+    let step = (gap / EXPECTED_SCORE_STEP) as usize;
+    let favourite = if step + 1 >= EXPECTED_SCORE_PPM.len() {
+        1_000_000 - EXPECTED_SCORE_PPM[EXPECTED_SCORE_PPM.len() - 1]
+    } else {
+        let (hi, lo) = (EXPECTED_SCORE_PPM[step], EXPECTED_SCORE_PPM[step + 1]);
+        1_000_000 - (hi - (hi - lo) * (gap % EXPECTED_SCORE_STEP) / EXPECTED_SCORE_STEP)
+    };
+    if own >= opponent {
+        favourite
+    } else {
+        1_000_000 - favourite
+    }
+}
+
+/// New rating of a player rated `rating` after comparing its `fitness`
+/// with each `(rating, fitness)` opponent, where the higher fitness wins.
+/// Opponent ratings are taken from before the round.
+pub fn rating_after(rating: u64, fitness: u64, opponents: &[(u64, u64)]) -> u64 {
+    if opponents.is_empty() {
+        return rating;
+    }
+    let (mut actual, mut expected) = (0i128, 0i128);
+    for &(other_rating, other_fitness) in opponents {
+        actual += match fitness.cmp(&other_fitness) {
+            std::cmp::Ordering::Greater => PPM,
+            std::cmp::Ordering::Equal => PPM / 2,
+            std::cmp::Ordering::Less => 0,
+        };
+        expected += expected_score_ppm(rating, other_rating) as i128;
+    }
+    let delta = (ELO_K_FACTOR * RATING_SCALE) as i128 * (actual - expected)
+        / (opponents.len() as i128 * PPM);
+    (rating as i128 + delta).max(0) as u64
+}
+
+/// Agents `agent` is rated against after a solo round: its neighbours when
+/// the round's `scored` agents are ordered by `key ^ seed`, wrapping around.
+/// None if `agent` was not scored or was the only agent scored.
+pub fn rating_opponents(scored: &[Pubkey], agent: &Pubkey, seed: &[u8; 32]) -> Option<Vec<Pubkey>> {
+    let mut ordered = scored.to_vec();
+    ordered.sort_by_cached_key(|key| {
+        let mut order = key.to_bytes();
+        for (byte, s) in order.iter_mut().zip(seed) {
+            *byte ^= s;
+        }
+        order
+    });
+    let i = ordered.iter().position(|key| key == agent)?;
+    let n = ordered.len();
+    match n {
+        1 => None,
+        2 => Some(vec![ordered[1 - i]]),
+        _ => Some(vec![ordered[(i + n - 1) % n], ordered[(i + 1) % n]]),
+    }
+}
+
+pub fn handle_update_ratings<'info>(
+    ctx: Context<'_, '_, 'info, 'info, UpdateRatings<'info>>,
+) -> Result<()> {
+    let round = &ctx.accounts.round;
+    let scored: Vec<Pubkey> = {
+        let info = ctx.accounts.population.to_account_info();
+        let data = info.try_borrow_data()?;
+        Population::entries(&data)
+            .iter()
+            .filter(|e| e.last_round == round.round_number)
+            .map(|e| e.agent)
+            .collect()
+    };
+    let mut expected = rating_opponents(&scored, &ctx.accounts.agent.key(), &round.seed)
+        .ok_or(SaeaError::InvalidRatingOpponents)?;
+    require!(
+        ctx.remaining_accounts.len() == expected.len(),
+        SaeaError::InvalidRatingOpponents
+    );
+
+    let mut opponents = Vec::with_capacity(expected.len());
+    for info in ctx.remaining_accounts {
+        let index = expected
+            .iter()
+            .position(|key| *key == info.key())
+            .ok_or(SaeaError::InvalidRatingOpponents)?;
+        expected.swap_remove(index);
+        let opponent = Account::<AgentAccount>::try_from(info)?;
+        require!(
+            opponent.last_round == round.round_number,
+            SaeaError::AgentNotInRound
+        );
+        opponents.push((opponent.rating_before(round.round_number), opponent.fitness));
+    }
+
+    let agent = &mut ctx.accounts.agent;
+    let old_rating = agent.rating;
+    let new_rating = rating_after(old_rating, agent.fitness, &opponents);
+    agent.set_rating(new_rating, round.round_number);
+
+    emit!(RatingUpdated {
+        agent: agent.key(),
+        round_number: round.round_number,
+        old_rating,
+        new_rating,
+    });
+
+    msg!(
+        "Rating updated: round={}, opponents={}, rating={}",
+        round.round_number,
+        opponents.len(),
+        new_rating
+    );
+    Ok(())
+}
@@ -6,6 +6,10 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{
+    BenchmarkModel, CompletionPolicy, FitnessMode, GenomeDistance, MigrationTopology,
+    SelectionMetric,
+};
 
 declare_id!("6tqMXifGhxp5WXY1XMdjHnhUguzgcLvTMuE3ijfdRJ4R");
 
@@ -58,4 +62,180 @@ pub mod saea {
     pub fn advance_generation(ctx: Context<AdvanceGeneration>) -> Result<()> {
         instructions::advance_generation::handle_advance_generation(ctx)
     }
+
+    pub fn create_benchmark(
+        ctx: Context<CreateBenchmark>,
+        model: BenchmarkModel,
+        feature_count: u8,
+        class_count: u8,
+        case_count: u32,
+    ) -> Result<()> {
+        instructions::benchmark::handle_create_benchmark(
+            ctx,
+            model,
+            feature_count,
+            class_count,
+            case_count,
+        )
+    }
+
+    pub fn upload_benchmark_chunk(
+        ctx: Context<UploadBenchmarkChunk>,
+        offset: u32,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::benchmark::handle_upload_benchmark_chunk(ctx, offset, data)
+    }
+
+    pub fn freeze_benchmark(ctx: Context<FreezeBenchmark>) -> Result<()> {
+        instructions::benchmark::handle_freeze_benchmark(ctx)
+    }
+
+    pub fn set_fitness_mode(ctx: Context<SetFitnessMode>, mode: FitnessMode) -> Result<()> {
+        instructions::set_fitness_mode::handle_set_fitness_mode(ctx, mode)
+    }
+
+    pub fn configure_selection(
+        ctx: Context<ConfigureSelection>,
+        metric: SelectionMetric,
+        half_life: u16,
+        window: u8,
+    ) -> Result<()> {
+        instructions::configure_selection::handle_configure_selection(
+            ctx, metric, half_life, window,
+        )
+    }
+
+    pub fn run_tournament_round(ctx: Context<RunTournamentRound>) -> Result<()> {
+        instructions::tournament::handle_run_tournament_round(ctx)
+    }
+
+    pub fn play_match(ctx: Context<PlayMatch>, match_index: u16) -> Result<()> {
+        instructions::tournament::handle_play_match(ctx, match_index)
+    }
+
+    pub fn update_ratings<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateRatings<'info>>,
+    ) -> Result<()> {
+        instructions::update_ratings::handle_update_ratings(ctx)
+    }
+
+    pub fn configure_histogram(
+        ctx: Context<ConfigureHistogram>,
+        base: u64,
+        bucket_width: u64,
+    ) -> Result<()> {
+        instructions::configure_histogram::handle_configure_histogram(ctx, base, bucket_width)
+    }
+
+    pub fn initialize_hall_of_fame(ctx: Context<InitializeHallOfFame>) -> Result<()> {
+        instructions::initialize_hall_of_fame::handle_initialize_hall_of_fame(ctx)
+    }
+
+    pub fn configure_mutation(
+        ctx: Context<ConfigureMutation>,
+        adaptive: bool,
+        min_rate_bps: u16,
+        max_rate_bps: u16,
+        stagnation_window: u16,
+    ) -> Result<()> {
+        instructions::configure_mutation::handle_configure_mutation(
+            ctx,
+            adaptive,
+            min_rate_bps,
+            max_rate_bps,
+            stagnation_window,
+        )
+    }
+
+    pub fn configure_strategy_genes(
+        ctx: Context<ConfigureMutation>,
+        strategy_genes: u8,
+    ) -> Result<()> {
+        instructions::configure_mutation::handle_configure_strategy_genes(ctx, strategy_genes)
+    }
+
+    pub fn mutate_genome(ctx: Context<MutateGenome>) -> Result<()> {
+        instructions::mutate_genome::handle_mutate_genome(ctx)
+    }
+
+    pub fn configure_islands(
+        ctx: Context<ConfigureIslands>,
+        island_count: u8,
+        migration_interval: u64,
+        topology: MigrationTopology,
+        migrants_per_island: u8,
+    ) -> Result<()> {
+        instructions::configure_islands::handle_configure_islands(
+            ctx,
+            island_count,
+            migration_interval,
+            topology,
+            migrants_per_island,
+        )
+    }
+
+    pub fn run_island_round(ctx: Context<RunRound>, island: u8) -> Result<()> {
+        instructions::run_round::handle_run_island_round(ctx, island)
+    }
+
+    pub fn migrate<'info>(ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>) -> Result<()> {
+        instructions::migrate::handle_migrate(ctx)
+    }
+
+    pub fn initialize_species(
+        ctx: Context<InitializeSpecies>,
+        distance: GenomeDistance,
+        threshold: u32,
+    ) -> Result<()> {
+        instructions::speciation::handle_initialize_species(ctx, distance, threshold)
+    }
+
+    pub fn share_fitness<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ShareFitness<'info>>,
+    ) -> Result<()> {
+        instructions::speciation::handle_share_fitness(ctx)
+    }
+
+    pub fn initialize_novelty_archive(
+        ctx: Context<InitializeNoveltyArchive>,
+        k_nearest: u8,
+        threshold: u64,
+        novelty_weight: u64,
+    ) -> Result<()> {
+        instructions::initialize_novelty_archive::handle_initialize_novelty_archive(
+            ctx,
+            k_nearest,
+            threshold,
+            novelty_weight,
+        )
+    }
+
+    pub fn configure_pareto(
+        ctx: Context<ConfigurePareto>,
+        enabled: bool,
+        prune_front: u8,
+    ) -> Result<()> {
+        instructions::configure_pareto::handle_configure_pareto(ctx, enabled, prune_front)
+    }
+
+    pub fn rank_pareto<'info>(ctx: Context<'_, '_, 'info, 'info, RankPareto<'info>>) -> Result<()> {
+        instructions::rank_pareto::handle_rank_pareto(ctx)
+    }
+
+    pub fn simulate_fitness(
+        ctx: Context<SimulateFitness>,
+        genome: Vec<u8>,
+        round_number: u64,
+    ) -> Result<FitnessSimulation> {
+        instructions::simulate_fitness::handle_simulate_fitness(ctx, genome, round_number)
+    }
+
+    pub fn configure_completion(
+        ctx: Context<ConfigureCompletion>,
+        policy: CompletionPolicy,
+        quorum_pct: u8,
+    ) -> Result<()> {
+        instructions::configure_completion::handle_configure_completion(ctx, policy, quorum_pct)
+    }
 }
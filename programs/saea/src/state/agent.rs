@@ -1,9 +1,54 @@
+use super::{Arena, SelectionMetric, FITNESS_DECAY_SCALE};
 use anchor_lang::prelude::*;
 
 /// Maximum genome length: 32 parameters encoded as u8 values.
 /// Each parameter represents a strategy weight in range [0, 255].
 pub const MAX_GENOME_LEN: usize = 32;
 
+/// Number of recent round scores kept per agent for windowed averages.
+pub const MAX_FITNESS_WINDOW: usize = 8;
+
+/// Ratings are stored in thousandths of an Elo point.
+pub const RATING_SCALE: u64 = 1000;
+pub const INITIAL_RATING: u64 = 1500 * RATING_SCALE;
+
+/// Number of seeded fitness components ranked as Pareto objectives.
+pub const FITNESS_OBJECTIVES: usize = 5;
+
+/// Per-component contributions to a score (see `compute_fitness_breakdown`).
+/// Benchmark and tournament scores have no components and record all zeros.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub struct FitnessBreakdown {
+    pub proximity: u64,
+    pub diversity: u64,
+    pub balance: u64,
+    pub pattern: u64,
+    pub sequence: u64,
+    pub novelty: u64, // weighted novelty bonus under FitnessMode::Novelty
+}
+
+impl FitnessBreakdown {
+    /// The five seeded components, as compared by Pareto ranking.
+    pub fn objectives(&self) -> [u64; FITNESS_OBJECTIVES] {
+        [
+            self.proximity,
+            self.diversity,
+            self.balance,
+            self.pattern,
+            self.sequence,
+        ]
+    }
+
+    /// Sum of every component, or None on overflow.
+    pub fn total(&self) -> Option<u64> {
+        self.objectives()
+            .iter()
+            .try_fold(self.novelty, |sum, &component| sum.checked_add(component))
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct AgentAccount {
@@ -19,7 +64,24 @@ pub struct AgentAccount {
     pub total_fitness: u64, // cumulative fitness across rounds
     pub is_active: bool,
     pub registered_at: i64,
+    pub registered_round: u64, // latest round at registration; only later rounds score the agent
     pub last_round: u64,
+    pub decayed_fitness: u64, // exponentially decayed across rounds
+    pub recent_fitness: [u64; MAX_FITNESS_WINDOW], // ring buffer indexed by rounds_participated
+    pub matches_won: u64,
+    pub matches_lost: u64,
+    pub matches_drawn: u64,
+    pub rating: u64,                 // Elo rating scaled by RATING_SCALE
+    pub rated_round: u64,            // last round folded into `rating`
+    pub previous_rating: u64,        // rating before `rated_round` was folded in
+    pub island: u8,                  // sub-population the agent competes and breeds in
+    pub species: u32,                // species from the latest scoring; 0 = unassigned
+    pub shared_fitness: u64,         // metric fitness divided by species size
+    pub shared_round: u64,           // round `shared_fitness` was computed for
+    pub breakdown: FitnessBreakdown, // latest score's components
+    pub pareto_front: u8,            // 0 = non-dominated within the ranked batch
+    pub crowding_distance: u64,      // u64::MAX on a front boundary
+    pub pareto_round: u64,           // round the Pareto rank was computed for
     pub bump: u8,
 }
 
@@ -32,4 +94,87 @@ impl AgentAccount {
         }
         self.total_fitness / self.rounds_participated
     }
+
+    /// Fold a new round score into the decayed fitness and recent-score ring.
+    /// Must be called before `rounds_participated` is incremented.
+    pub fn record_fitness(&mut self, fitness: u64, retention: u64) {
+        self.decayed_fitness = if self.rounds_participated == 0 {
+            fitness
+        } else {
+            let scale = FITNESS_DECAY_SCALE as u128;
+            let retention = retention as u128;
+            ((self.decayed_fitness as u128 * retention + fitness as u128 * (scale - retention))
+                / scale) as u64
+        };
+        let slot = (self.rounds_participated % MAX_FITNESS_WINDOW as u64) as usize;
+        self.recent_fitness[slot] = fitness;
+    }
+
+    /// Mean of the last `window` recorded scores (fewer if the agent is new).
+    pub fn windowed_fitness(&self, window: u8) -> u64 {
+        let count = (window as u64).min(self.rounds_participated);
+        if count == 0 {
+            return 0;
+        }
+        let newest = self.rounds_participated - 1;
+        let sum: u64 = (0..count)
+            .map(|i| {
+                let slot = ((newest - i) % MAX_FITNESS_WINDOW as u64) as usize;
+                self.recent_fitness[slot]
+            })
+            .sum();
+        sum / count
+    }
+
+    /// Genes that are scored: everything after the arena's strategy prefix.
+    pub fn object_genes(&self, arena: &Arena) -> &[u8] {
+        arena.object_genes(&self.genome)
+    }
+
+    /// Fitness under the arena's selection metric, before any sharing.
+    pub fn metric_fitness(&self, arena: &Arena) -> u64 {
+        match arena.selection_metric {
+            SelectionMetric::LastRound => self.fitness,
+            SelectionMetric::Decayed => self.decayed_fitness,
+            SelectionMetric::Windowed => self.windowed_fitness(arena.fitness_window),
+        }
+    }
+
+    /// Fold round `round_number` into the rating, keeping the pre-round
+    /// rating for opponents rated against this agent later in the round.
+    pub fn set_rating(&mut self, rating: u64, round_number: u64) {
+        self.previous_rating = self.rating;
+        self.rating = rating;
+        self.rated_round = round_number;
+    }
+
+    /// Rating before round `round_number` was folded in.
+    pub fn rating_before(&self, round_number: u64) -> u64 {
+        if self.rated_round == round_number {
+            self.previous_rating
+        } else {
+            self.rating
+        }
+    }
+
+    /// Whether `prune_agent` may remove this agent: by Pareto front when the
+    /// arena ranks by objectives, otherwise by the fitness threshold.
+    pub fn is_prunable(&self, arena: &Arena) -> bool {
+        if arena.pareto_selection {
+            self.pareto_round == self.last_round && self.pareto_front >= arena.pareto_prune_front
+        } else {
+            self.selection_fitness(arena) < arena.min_fitness_threshold
+        }
+    }
+
+    /// Fitness used for pruning and reward eligibility: the metric fitness,
+    /// or its species-shared value when the arena speciates and the shared
+    /// value is from the agent's latest round.
+    pub fn selection_fitness(&self, arena: &Arena) -> u64 {
+        if arena.speciation_enabled && self.shared_round == self.last_round {
+            self.shared_fitness
+        } else {
+            self.metric_fitness(arena)
+        }
+    }
 }
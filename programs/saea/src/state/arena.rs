@@ -1,4 +1,54 @@
+use super::{GenerationTally, MAX_FITNESS_WINDOW};
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum FitnessMode {
+    /// Genes are scored against seed-derived targets (`compute_fitness`).
+    Seeded,
+    /// Genes are model weights evaluated against the arena's frozen `Benchmark`.
+    Benchmark,
+    /// `compute_fitness` plus a bonus for distance from the `NoveltyArchive`.
+    Novelty,
+}
+
+/// Fixed-point scale for `Arena::fitness_decay_retention`.
+pub const FITNESS_DECAY_SCALE: u64 = 1_000_000_000;
+
+/// Maximum number of islands an arena population can be split into.
+pub const MAX_ISLANDS: usize = 8;
+
+/// How `migrate` picks each island's destination.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum MigrationTopology {
+    /// Island `i` sends migrants to island `i + 1` (wrapping).
+    Ring,
+    /// Each island sends to another island drawn from the latest round seed.
+    Random,
+}
+
+/// How many of a round's eligible agents must be scored before
+/// `complete_round` accepts it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum CompletionPolicy {
+    /// Every eligible agent.
+    Strict,
+    /// At least `completion_quorum_pct` percent of them.
+    Quorum,
+    /// Any number, including none.
+    Lenient,
+}
+
+/// Which per-agent fitness value pruning and rewards are based on.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum SelectionMetric {
+    /// The score from the agent's most recent round.
+    LastRound,
+    /// Exponentially decayed fitness with `fitness_half_life` rounds.
+    Decayed,
+    /// Mean of the agent's last `fitness_window` round scores.
+    Windowed,
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -13,9 +63,178 @@ pub struct Arena {
     pub mutation_rate_bps: u16, // basis points (0-10000)
     pub is_active: bool,
     pub reward_pool: u64,
+    pub fitness_mode: FitnessMode,
+    pub selection_metric: SelectionMetric,
+    pub fitness_half_life: u16, // rounds until a score's weight halves
+    pub fitness_decay_retention: u64, // 2^(-1/half_life), scaled by FITNESS_DECAY_SCALE
+    pub fitness_window: u8,     // rounds averaged by SelectionMetric::Windowed
+    pub histogram_base: u64,    // lower edge of the first round fitness bucket
+    pub histogram_bucket_width: u64,
+    pub generation_tally: GenerationTally, // current generation, reset on advance
+    pub last_generation_hash: [u8; 32],    // summary_hash of the latest Generation
+    pub adaptive_mutation: bool,
+    pub mutation_rate_min_bps: u16,
+    pub mutation_rate_max_bps: u16,
+    pub stagnation_window: u16, // rounds without a new best before the rate rises
+    pub stagnant_rounds: u16,
+    pub recent_best_fitness: u64, // best round fitness since the last improvement
+    pub strategy_genes: u8,       // genome prefix holding per-agent step sizes; 0 = off
+    pub island_count: u8,
+    pub island_populations: [u64; MAX_ISLANDS], // active agents per island
+    pub migration_interval: u64,                // generations between migrations
+    pub migration_topology: MigrationTopology,
+    pub migrants_per_island: u8,
+    pub last_migration_generation: u64,
+    pub completion_policy: CompletionPolicy,
+    pub completion_quorum_pct: u8,  // used by CompletionPolicy::Quorum
+    pub pareto_selection: bool,     // prune by Pareto front instead of fitness threshold
+    pub pareto_prune_front: u8,     // fronts at or beyond this index are prunable
+    pub speciation_enabled: bool, // scores require the species registry; pruning uses shared fitness
+    pub hall_of_fame_enabled: bool, // scores require the hall of fame account
     pub bump: u8,
 }
 
 impl Arena {
     pub const SEED: &'static [u8] = b"arena";
+    pub const DEFAULT_HALF_LIFE: u16 = 4;
+    pub const DEFAULT_BUCKET_WIDTH: u64 = 1000;
+    pub const DEFAULT_MUTATION_MIN_BPS: u16 = 1000;
+    pub const DEFAULT_MUTATION_MAX_BPS: u16 = 8000;
+    pub const DEFAULT_STAGNATION_WINDOW: u16 = 3;
+
+    /// An arena as `initialize_arena` creates it: seeded fitness, one
+    /// island, a fixed mutation rate and lenient round completion.
+    pub fn new(
+        authority: Pubkey,
+        max_agents: u64,
+        min_fitness_threshold: u64,
+        mutation_rate_bps: u16,
+        bump: u8,
+    ) -> Self {
+        Self {
+            authority,
+            current_round: 0,
+            current_generation: 1,
+            total_agents: 0,
+            active_agents: 0,
+            max_agents,
+            min_fitness_threshold,
+            mutation_rate_bps,
+            is_active: true,
+            reward_pool: 0,
+            fitness_mode: FitnessMode::Seeded,
+            selection_metric: SelectionMetric::LastRound,
+            fitness_half_life: Self::DEFAULT_HALF_LIFE,
+            fitness_decay_retention: Self::decay_retention(Self::DEFAULT_HALF_LIFE),
+            fitness_window: MAX_FITNESS_WINDOW as u8,
+            histogram_base: 0,
+            histogram_bucket_width: Self::DEFAULT_BUCKET_WIDTH,
+            generation_tally: GenerationTally::default(),
+            last_generation_hash: [0u8; 32],
+            adaptive_mutation: false,
+            mutation_rate_min_bps: mutation_rate_bps.min(Self::DEFAULT_MUTATION_MIN_BPS),
+            mutation_rate_max_bps: mutation_rate_bps.max(Self::DEFAULT_MUTATION_MAX_BPS),
+            stagnation_window: Self::DEFAULT_STAGNATION_WINDOW,
+            stagnant_rounds: 0,
+            recent_best_fitness: 0,
+            strategy_genes: 0,
+            island_count: 1,
+            island_populations: [0u64; MAX_ISLANDS],
+            migration_interval: 1,
+            migration_topology: MigrationTopology::Ring,
+            migrants_per_island: 1,
+            last_migration_generation: 0,
+            completion_policy: CompletionPolicy::Lenient,
+            completion_quorum_pct: 100,
+            pareto_selection: false,
+            pareto_prune_front: 1,
+            speciation_enabled: false,
+            hall_of_fame_enabled: false,
+            bump,
+        }
+    }
+
+    /// Per-round retention factor for a given half-life, i.e. the smallest
+    /// `r` (scaled by `FITNESS_DECAY_SCALE`) with `r^half_life >= 1/2`.
+    /// Found by bisection so the result is exact integer math.
+    pub fn decay_retention(half_life: u16) -> u64 {
+        let scale = FITNESS_DECAY_SCALE as u128;
+        let pow = |base: u128, mut exp: u16| {
+            let (mut acc, mut base) = (scale, base);
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    acc = acc * base / scale;
+                }
+                base = base * base / scale;
+                exp >>= 1;
+            }
+            acc
+        };
+        let (mut lo, mut hi) = (0u128, scale);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if pow(mid, half_life) * 2 >= scale {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        lo as u64
+    }
+
+    /// Genes of `genome` that are scored: everything after the strategy prefix.
+    pub fn object_genes<'a>(&self, genome: &'a [u8]) -> &'a [u8] {
+        let prefix = (self.strategy_genes as usize).min(genome.len());
+        &genome[prefix..]
+    }
+
+    /// Island for the next registered agent; agents are dealt round-robin.
+    pub fn next_island(&self) -> u8 {
+        (self.total_agents % self.island_count.max(1) as u64) as u8
+    }
+
+    /// Island receiving migrants from `island` under the arena topology.
+    pub fn migration_destination(&self, island: u8, seed: &[u8; 32]) -> u8 {
+        let count = self.island_count.max(1);
+        match self.migration_topology {
+            MigrationTopology::Ring => (island + 1) % count,
+            MigrationTopology::Random => {
+                if count == 1 {
+                    return 0;
+                }
+                let draw = hashv(&[seed.as_ref(), b"migration", &[island]]).to_bytes()[0];
+                (island + 1 + draw % (count - 1)) % count
+            }
+        }
+    }
+
+    /// Stagnation rule applied after each completed round: a new best
+    /// fitness shrinks the mutation rate by 20%, while `stagnation_window`
+    /// rounds without one grow it by 30%, both within the configured bounds.
+    /// Returns the previous rate if it changed.
+    pub fn adapt_mutation_rate(&mut self, round_best_fitness: u64) -> Option<u16> {
+        if !self.adaptive_mutation {
+            return None;
+        }
+        let old_rate = self.mutation_rate_bps;
+        let rate = old_rate as u32;
+        if round_best_fitness > self.recent_best_fitness {
+            self.recent_best_fitness = round_best_fitness;
+            self.stagnant_rounds = 0;
+            self.mutation_rate_bps = (rate * 80 / 100) as u16;
+        } else {
+            self.stagnant_rounds = self.stagnant_rounds.saturating_add(1);
+            if self.stagnant_rounds < self.stagnation_window {
+                return None;
+            }
+            self.stagnant_rounds = 0;
+            // Reset the baseline so the next round can count as progress again.
+            self.recent_best_fitness = round_best_fitness;
+            self.mutation_rate_bps = (rate * 130 / 100).max(rate + 1).min(10_000) as u16;
+        }
+        self.mutation_rate_bps = self
+            .mutation_rate_bps
+            .clamp(self.mutation_rate_min_bps, self.mutation_rate_max_bps);
+        (self.mutation_rate_bps != old_rate).then_some(old_rate)
+    }
 }
@@ -0,0 +1,110 @@
+use super::MAX_GENOME_LEN;
+use anchor_lang::prelude::*;
+
+/// Maximum size of the uploaded case table in bytes.
+/// Each case is `feature_count` feature bytes followed by one label byte.
+pub const MAX_BENCHMARK_BYTES: usize = 8192;
+
+/// Maximum number of classes a benchmark may label its cases with.
+pub const MAX_BENCHMARK_CLASSES: u8 = 16;
+
+/// Feature values at or above this cut-off set the corresponding bit of a
+/// decision-table index.
+pub const DECISION_TABLE_CUTOFF: u8 = 128;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BenchmarkModel {
+    /// One signed weight per feature plus a bias for each class; the class
+    /// with the highest activation is predicted.
+    Linear,
+    /// Features are thresholded into a bit index; the gene at that index
+    /// (modulo `class_count`) is the predicted class.
+    DecisionTable,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Benchmark {
+    pub arena: Pubkey,
+    pub model: BenchmarkModel,
+    pub feature_count: u8,
+    pub class_count: u8,
+    pub case_count: u32,
+    #[max_len(MAX_BENCHMARK_BYTES)]
+    pub cases: Vec<u8>, // uploaded in chunks, immutable once frozen
+    pub data_hash: [u8; 32], // SHA256 of `cases`, set on freeze
+    pub is_frozen: bool,
+    pub bump: u8,
+}
+
+impl Benchmark {
+    pub const SEED: &'static [u8] = b"benchmark";
+
+    pub fn case_len(&self) -> usize {
+        self.feature_count as usize + 1
+    }
+
+    pub fn expected_len(&self) -> usize {
+        self.case_count as usize * self.case_len()
+    }
+
+    /// Number of genes the model reads; genes beyond the genome are treated
+    /// as neutral (weight 0 / class 0).
+    pub fn genes_required(model: BenchmarkModel, feature_count: u8, class_count: u8) -> usize {
+        match model {
+            BenchmarkModel::Linear => class_count as usize * (feature_count as usize + 1),
+            BenchmarkModel::DecisionTable => 1usize << feature_count.min(8),
+        }
+    }
+
+    pub fn fits_genome(model: BenchmarkModel, feature_count: u8, class_count: u8) -> bool {
+        Self::genes_required(model, feature_count, class_count) <= MAX_GENOME_LEN
+    }
+
+    /// Count the cases whose label the genome-encoded model predicts correctly.
+    pub fn evaluate(&self, genome: &[u8]) -> u64 {
+        let mut correct: u64 = 0;
+        for case in self.cases.chunks_exact(self.case_len()) {
+            let (features, label) = case.split_at(self.feature_count as usize);
+            if self.predict(genome, features) == label[0] {
+                correct += 1;
+            }
+        }
+        correct
+    }
+
+    fn predict(&self, genome: &[u8], features: &[u8]) -> u8 {
+        let gene = |i: usize| genome.get(i).copied();
+        match self.model {
+            BenchmarkModel::Linear => {
+                // Genes are signed weights centered on 128: [w_0..w_n, bias] per class.
+                let stride = features.len() + 1;
+                let mut best_class = 0u8;
+                let mut best_score = i64::MIN;
+                for class in 0..self.class_count {
+                    let base = class as usize * stride;
+                    let weight = |i: usize| gene(base + i).map_or(0, |g| g as i64 - 128);
+                    let score = features
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &x)| weight(j) * x as i64)
+                        .sum::<i64>()
+                        + weight(features.len()) * 128;
+                    if score > best_score {
+                        best_score = score;
+                        best_class = class;
+                    }
+                }
+                best_class
+            }
+            BenchmarkModel::DecisionTable => {
+                let index = features
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &x)| x >= DECISION_TABLE_CUTOFF)
+                    .fold(0usize, |acc, (j, _)| acc | (1 << j));
+                gene(index).map_or(0, |g| g % self.class_count)
+            }
+        }
+    }
+}
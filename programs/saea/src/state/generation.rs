@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
+
+/// Running totals for the arena's current generation, folded into a
+/// `Generation` summary and reset by `advance_generation`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct GenerationTally {
+    pub rounds_run: u64,
+    pub agents_born: u64,
+    pub genomes_submitted: u64,
+    pub agents_pruned: u64,
+    pub best_fitness: u64,
+    pub round_average_sum: u64, // sum of completed rounds' average fitness
+    pub champion: Pubkey,
+    pub champion_genome_hash: [u8; 32],
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Generation {
+    pub arena: Pubkey,
+    pub generation_number: u64,
+    pub rounds_run: u64,
+    pub agents_born: u64,
+    pub genomes_submitted: u64,
+    pub agents_pruned: u64,
+    pub best_fitness: u64,
+    pub average_fitness: u64, // mean of the generation's round averages
+    pub champion: Pubkey,     // agent holding best_fitness, Pubkey::default() if no rounds
+    pub champion_genome_hash: [u8; 32],
+    pub previous_hash: [u8; 32], // summary_hash of the previous generation
+    pub summary_hash: [u8; 32],  // hash chain link over this summary and previous_hash
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+impl Generation {
+    pub const SEED: &'static [u8] = b"generation";
+
+    pub fn compute_summary_hash(&self) -> [u8; 32] {
+        hashv(&[
+            self.previous_hash.as_ref(),
+            &self.generation_number.to_le_bytes(),
+            &self.rounds_run.to_le_bytes(),
+            &self.agents_born.to_le_bytes(),
+            &self.genomes_submitted.to_le_bytes(),
+            &self.agents_pruned.to_le_bytes(),
+            &self.best_fitness.to_le_bytes(),
+            &self.average_fitness.to_le_bytes(),
+            self.champion.as_ref(),
+            self.champion_genome_hash.as_ref(),
+        ])
+        .to_bytes()
+    }
+}
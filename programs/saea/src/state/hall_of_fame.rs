@@ -0,0 +1,59 @@
+use super::MAX_GENOME_LEN;
+use anchor_lang::prelude::*;
+
+/// Number of best-ever genomes kept per arena.
+pub const HALL_OF_FAME_SIZE: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct HallOfFameEntry {
+    pub agent: Pubkey,
+    #[max_len(MAX_GENOME_LEN)]
+    pub genome: Vec<u8>,
+    pub fitness: u64,
+    pub round_number: u64,
+    pub generation: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct HallOfFame {
+    pub arena: Pubkey,
+    #[max_len(HALL_OF_FAME_SIZE)]
+    pub entries: Vec<HallOfFameEntry>, // sorted by fitness, best first
+    pub bump: u8,
+}
+
+impl HallOfFame {
+    pub const SEED: &'static [u8] = b"hall_of_fame";
+
+    /// Lowest fitness that still holds a place, or 0 while there is room.
+    pub fn min_fitness(&self) -> u64 {
+        if self.entries.len() < HALL_OF_FAME_SIZE {
+            return 0;
+        }
+        self.entries.last().map_or(0, |e| e.fitness)
+    }
+
+    /// Insert `entry` if it earns a place and return its rank (0 = best).
+    /// A genome already in the hall keeps a single entry at its best score.
+    pub fn try_insert(&mut self, entry: HallOfFameEntry) -> Option<usize> {
+        if let Some(i) = self.entries.iter().position(|e| e.genome == entry.genome) {
+            if entry.fitness <= self.entries[i].fitness {
+                return None;
+            }
+            self.entries.remove(i);
+        } else if self.entries.len() >= HALL_OF_FAME_SIZE && entry.fitness <= self.min_fitness() {
+            return None;
+        }
+
+        // Ties keep the earlier record ahead.
+        let rank = self
+            .entries
+            .iter()
+            .position(|e| e.fitness < entry.fitness)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(rank, entry);
+        self.entries.truncate(HALL_OF_FAME_SIZE);
+        Some(rank)
+    }
+}
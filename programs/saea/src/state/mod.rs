@@ -1,7 +1,19 @@
 pub mod agent;
 pub mod arena;
+pub mod benchmark;
+pub mod generation;
+pub mod hall_of_fame;
+pub mod novelty;
+pub mod population;
 pub mod round;
+pub mod species;
 
 pub use agent::*;
 pub use arena::*;
+pub use benchmark::*;
+pub use generation::*;
+pub use hall_of_fame::*;
+pub use novelty::*;
+pub use population::*;
 pub use round::*;
+pub use species::*;
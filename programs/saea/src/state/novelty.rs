@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+/// Dimensions of the behavior descriptor compared in novelty search.
+pub const BEHAVIOR_DIMENSIONS: usize = 8;
+
+/// Archived descriptors kept per arena; once full the oldest is replaced.
+pub const MAX_NOVELTY_ARCHIVE: usize = 64;
+
+/// Largest possible distance between two descriptors.
+pub const MAX_BEHAVIOR_DISTANCE: u64 = 255 * BEHAVIOR_DIMENSIONS as u64;
+
+/// Largest `novelty_weight`, capping the novelty bonus at
+/// `MAX_BEHAVIOR_DISTANCE * MAX_NOVELTY_WEIGHT` fitness points.
+pub const MAX_NOVELTY_WEIGHT: u64 = 100;
+
+#[account]
+#[derive(InitSpace)]
+pub struct NoveltyArchive {
+    pub arena: Pubkey,
+    pub k_nearest: u8,       // neighbours averaged into the novelty score
+    pub threshold: u64,      // novelty needed to enter the archive
+    pub novelty_weight: u64, // fitness points per unit of novelty
+    #[max_len(MAX_NOVELTY_ARCHIVE)]
+    pub entries: Vec<[u8; BEHAVIOR_DIMENSIONS]>,
+    pub next_slot: u16, // entry overwritten next once the archive is full
+    pub total_archived: u64,
+    pub bump: u8,
+}
+
+impl NoveltyArchive {
+    pub const SEED: &'static [u8] = b"novelty";
+
+    /// Behavior descriptor of a genome: the mean gene of each of
+    /// `BEHAVIOR_DIMENSIONS` equal segments (empty segments read as 0).
+    pub fn behavior(genes: &[u8]) -> [u8; BEHAVIOR_DIMENSIONS] {
+        let mut descriptor = [0u8; BEHAVIOR_DIMENSIONS];
+        for (d, slot) in descriptor.iter_mut().enumerate() {
+            let start = d * genes.len() / BEHAVIOR_DIMENSIONS;
+            let end = (d + 1) * genes.len() / BEHAVIOR_DIMENSIONS;
+            let segment = &genes[start..end];
+            if !segment.is_empty() {
+                let sum: u64 = segment.iter().map(|&g| g as u64).sum();
+                *slot = (sum / segment.len() as u64) as u8;
+            }
+        }
+        descriptor
+    }
+
+    /// Mean L1 distance from `behavior` to its `k_nearest` archived
+    /// neighbours; an empty archive makes any behavior maximally novel.
+    pub fn novelty(&self, behavior: &[u8; BEHAVIOR_DIMENSIONS]) -> u64 {
+        if self.entries.is_empty() {
+            return MAX_BEHAVIOR_DISTANCE;
+        }
+        let mut distances: Vec<u64> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                entry
+                    .iter()
+                    .zip(behavior)
+                    .map(|(&a, &b)| a.abs_diff(b) as u64)
+                    .sum()
+            })
+            .collect();
+        distances.sort_unstable();
+        let k = (self.k_nearest as usize).clamp(1, distances.len());
+        distances[..k].iter().sum::<u64>() / k as u64
+    }
+
+    /// Archive `behavior`, replacing the oldest entry when full.
+    pub fn archive(&mut self, behavior: [u8; BEHAVIOR_DIMENSIONS]) {
+        if self.entries.len() < MAX_NOVELTY_ARCHIVE {
+            self.entries.push(behavior);
+        } else {
+            self.entries[self.next_slot as usize] = behavior;
+            self.next_slot = (self.next_slot + 1) % MAX_NOVELTY_ARCHIVE as u16;
+        }
+        self.total_archived += 1;
+    }
+}
@@ -0,0 +1,130 @@
+use crate::errors::SaeaError;
+use crate::state::{AgentAccount, Arena};
+use anchor_lang::prelude::*;
+use std::mem::size_of;
+
+/// Registry of an arena's active agents. The fixed header is followed by
+/// exactly `len` `PopulationEntry` records; the account grows by one entry
+/// in `register_agent` and shrinks by one in `prune_agent`. Entries mirror
+/// each agent's island and selection fitness so `migrate` can rank islands
+/// without loading every agent.
+#[account(zero_copy)]
+pub struct Population {
+    pub arena: Pubkey,
+    pub len: u32,
+    pub bump: u8,
+    pub _padding: [u8; 3],
+}
+
+#[zero_copy]
+pub struct PopulationEntry {
+    pub agent: Pubkey,
+    pub fitness: u64,           // latest round score
+    pub last_round: u64,        // 0 until first scored
+    pub selection_fitness: u64, // selection fitness as of the latest score or share
+    pub island: u8,
+    pub _padding: [u8; 7],
+}
+
+/// Offset of the first entry: discriminator plus header.
+pub const POPULATION_HEADER_LEN: usize = 8 + size_of::<Population>();
+
+impl Population {
+    pub const SEED: &'static [u8] = b"population";
+
+    /// Account size holding `len` entries.
+    pub fn space(len: usize) -> usize {
+        POPULATION_HEADER_LEN + len * size_of::<PopulationEntry>()
+    }
+
+    /// Entries of a population account's raw data.
+    pub fn entries(data: &[u8]) -> &[PopulationEntry] {
+        bytemuck::cast_slice(&data[POPULATION_HEADER_LEN..])
+    }
+
+    pub fn entries_mut(data: &mut [u8]) -> &mut [PopulationEntry] {
+        bytemuck::cast_slice_mut(&mut data[POPULATION_HEADER_LEN..])
+    }
+
+    /// Append `agent` to the last slot, which `register_agent` has just
+    /// reallocated.
+    pub fn push(loader: &AccountLoader<Population>, agent: Pubkey, island: u8) -> Result<()> {
+        let index = {
+            let mut population = loader.load_mut()?;
+            population.len += 1;
+            population.len as usize - 1
+        };
+        let info = loader.to_account_info();
+        let mut data = info.try_borrow_mut_data()?;
+        Self::entries_mut(&mut data)[index] = PopulationEntry {
+            agent,
+            fitness: 0,
+            last_round: 0,
+            selection_fitness: 0,
+            island,
+            _padding: [0; 7],
+        };
+        Ok(())
+    }
+
+    /// Mirror `agent`'s latest score and selection fitness, once the score
+    /// has been applied to the account.
+    pub fn record_score(
+        loader: &AccountLoader<Population>,
+        key: &Pubkey,
+        agent: &AgentAccount,
+        arena: &Arena,
+    ) -> Result<()> {
+        Self::update(loader, key, |entry| {
+            entry.fitness = agent.fitness;
+            entry.last_round = agent.last_round;
+            entry.selection_fitness = agent.selection_fitness(arena);
+        })
+    }
+
+    /// Apply `f` to `agent`'s entry.
+    pub fn update(
+        loader: &AccountLoader<Population>,
+        agent: &Pubkey,
+        f: impl FnOnce(&mut PopulationEntry),
+    ) -> Result<()> {
+        let info = loader.to_account_info();
+        let mut data = info.try_borrow_mut_data()?;
+        let entry = Self::entries_mut(&mut data)
+            .iter_mut()
+            .find(|e| e.agent == *agent)
+            .ok_or(SaeaError::AgentNotInPopulation)?;
+        f(entry);
+        Ok(())
+    }
+
+    /// Swap-remove `agent` and shrink the account by one entry, refunding
+    /// the freed rent to `recipient`.
+    pub fn remove<'info>(
+        loader: &AccountLoader<'info, Population>,
+        agent: &Pubkey,
+        recipient: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let len = loader.load()?.len as usize;
+        let info = loader.to_account_info();
+        {
+            let mut data = info.try_borrow_mut_data()?;
+            let entries = Self::entries_mut(&mut data);
+            let index = entries
+                .iter()
+                .position(|e| e.agent == *agent)
+                .ok_or(SaeaError::AgentNotInPopulation)?;
+            entries.swap(index, len - 1);
+        }
+        loader.load_mut()?.len -= 1;
+
+        let new_space = Self::space(len - 1);
+        let refund = info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_space));
+        **info.try_borrow_mut_lamports()? -= refund;
+        **recipient.try_borrow_mut_lamports()? += refund;
+        info.resize(new_space)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,52 @@
+use super::{AgentAccount, Arena, CompletionPolicy, FitnessMode, MAX_GENOME_LEN};
+use crate::errors::SaeaError;
+use crate::events::RoundCompleted;
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::{hash, hashv};
+
+/// Maximum number of agents drawn into a single tournament round.
+pub const MAX_TOURNAMENT_ENTRANTS: usize = 32;
+
+/// Number of fitness histogram buckets; the last one is open-ended.
+pub const HISTOGRAM_BUCKETS: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RoundKind {
+    /// Each genome is scored in isolation with `score_agent`.
+    Solo,
+    /// Seed-drawn pairs of agents play each other with `play_match`.
+    Tournament,
+}
+
+/// Seed-derived values read by the fitness function, hashed once per round
+/// instead of once per gene for every scored agent.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace, Debug,
+)]
+pub struct FitnessTargets {
+    pub gene_targets: [u8; MAX_GENOME_LEN], // proximity target per gene position
+    pub ascending_pairs: u32,               // bit i: genes i and i + 1 should ascend
+}
+
+impl FitnessTargets {
+    pub fn derive(round_seed: &[u8; 32]) -> Self {
+        let mut targets = Self::default();
+        for i in 0..MAX_GENOME_LEN {
+            targets.gene_targets[i] = hashv(&[round_seed.as_ref(), &[i as u8]]).to_bytes()[0];
+        }
+        for i in 0..MAX_GENOME_LEN - 1 {
+            let direction = hashv(&[round_seed.as_ref(), &[i as u8], &[0xFF]]).to_bytes()[0];
+            if direction > 127 {
+                targets.ascending_pairs |= 1 << i;
+            }
+        }
+        targets
+    }
+
+    pub fn should_ascend(&self, pair: usize) -> bool {
+        self.ascending_pairs & (1 << pair) != 0
+    }
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -15,9 +63,260 @@ pub struct Round {
     pub started_at: i64,
     pub completed_at: i64,
     pub is_complete: bool,
+    pub kind: RoundKind,
+    #[max_len(MAX_TOURNAMENT_ENTRANTS)]
+    pub pairings: Vec<Pubkey>, // tournament only: match i is (pairings[2i], pairings[2i + 1])
+    pub fitness_sum_sq: u128,  // sum of squared scores, for variance
+    pub fitness_variance: u64, // population variance, set on completion
+    pub histogram_base: u64,   // lower edge of bucket 0, copied from the arena
+    pub histogram_bucket_width: u64,
+    pub histogram: [u32; HISTOGRAM_BUCKETS],
+    pub lower_quartile_fitness: u64, // histogram approximations, set on completion
+    pub median_fitness: u64,
+    pub upper_quartile_fitness: u64,
+    pub gene_counts: [u32; MAX_GENOME_LEN], // per-position tallies over scored genomes
+    pub gene_sums: [u32; MAX_GENOME_LEN],
+    pub gene_sq_sums: [u64; MAX_GENOME_LEN],
+    pub genome_diversity: u64, // mean per-position gene variance, set on completion
+    pub best_agent: Pubkey,    // agent holding best_fitness
+    pub best_genome_hash: [u8; 32],
+    pub targets: FitnessTargets, // derived from `seed` when a solo round starts
+    pub fitness_mode: FitnessMode, // arena mode when the round started; every score uses it
+    pub eligible_agents: u64,    // agents that could be scored, snapshotted when the round starts
+    pub island: Option<u8>,      // only agents of this island may be scored; None = whole arena
+    pub completion_policy: CompletionPolicy, // arena policy when the round started
+    pub completion_quorum_pct: u8,
+    pub agents_removed: u64, // scored agents pruned while this was the latest round
     pub bump: u8,
 }
 
 impl Round {
     pub const SEED: &'static [u8] = b"round";
+
+    /// Seed of round `round_number`, started in `generation` at
+    /// `unix_timestamp`.
+    pub fn derive_seed(
+        arena: &Pubkey,
+        round_number: u64,
+        generation: u64,
+        unix_timestamp: i64,
+    ) -> [u8; 32] {
+        hashv(&[
+            arena.as_ref(),
+            &round_number.to_le_bytes(),
+            &generation.to_le_bytes(),
+            &unix_timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Round `round_number` of `kind` as it starts: empty statistics, the
+    /// arena's current generation and configuration, and every active
+    /// agent eligible.
+    pub fn new(
+        arena_key: Pubkey,
+        arena: &Arena,
+        round_number: u64,
+        seed: [u8; 32],
+        started_at: i64,
+        kind: RoundKind,
+        bump: u8,
+    ) -> Self {
+        Self {
+            arena: arena_key,
+            round_number,
+            generation: arena.current_generation,
+            participants: 0,
+            best_fitness: 0,
+            worst_fitness: u64::MAX,
+            average_fitness: 0,
+            total_fitness: 0,
+            seed,
+            started_at,
+            completed_at: 0,
+            is_complete: false,
+            kind,
+            pairings: Vec::new(),
+            fitness_sum_sq: 0,
+            fitness_variance: 0,
+            histogram_base: arena.histogram_base,
+            histogram_bucket_width: arena.histogram_bucket_width,
+            histogram: [0; HISTOGRAM_BUCKETS],
+            lower_quartile_fitness: 0,
+            median_fitness: 0,
+            upper_quartile_fitness: 0,
+            gene_counts: [0; MAX_GENOME_LEN],
+            gene_sums: [0; MAX_GENOME_LEN],
+            gene_sq_sums: [0; MAX_GENOME_LEN],
+            genome_diversity: 0,
+            best_agent: Pubkey::default(),
+            best_genome_hash: [0; 32],
+            targets: match kind {
+                RoundKind::Solo => FitnessTargets::derive(&seed),
+                RoundKind::Tournament => FitnessTargets::default(),
+            },
+            fitness_mode: arena.fitness_mode,
+            eligible_agents: arena.active_agents,
+            island: None,
+            completion_policy: arena.completion_policy,
+            completion_quorum_pct: arena.completion_quorum_pct,
+            agents_removed: 0,
+            bump,
+        }
+    }
+
+    /// The two agents drawn to play match `match_index`, if it exists.
+    pub fn pairing(&self, match_index: u16) -> Option<(Pubkey, Pubkey)> {
+        let i = match_index as usize * 2;
+        Some((*self.pairings.get(i)?, *self.pairings.get(i + 1)?))
+    }
+
+    /// Whether `agent` is one of the round's `eligible_agents`: a tournament
+    /// entrant drawn into a match, or for solo rounds an agent registered
+    /// before the round started on the round's island.
+    pub fn expects(&self, key: &Pubkey, agent: &AgentAccount) -> bool {
+        match self.kind {
+            RoundKind::Solo => {
+                agent.registered_round < self.round_number
+                    && self.island.is_none_or(|island| island == agent.island)
+            }
+            RoundKind::Tournament => self.pairings.contains(key),
+        }
+    }
+
+    /// Mark the round complete and derive its summary statistics from the
+    /// scores accumulated so far. Returns the event `complete_round` emits,
+    /// counting unscored eligible agents as skipped.
+    pub fn finalize(&mut self) -> RoundCompleted {
+        self.average_fitness = self
+            .total_fitness
+            .checked_div(self.participants)
+            .unwrap_or(0);
+        self.is_complete = true;
+        if self.worst_fitness == u64::MAX {
+            self.worst_fitness = 0;
+        }
+        self.fitness_variance = self.compute_variance();
+        self.lower_quartile_fitness = self.fitness_percentile(25);
+        self.median_fitness = self.fitness_percentile(50);
+        self.upper_quartile_fitness = self.fitness_percentile(75);
+        self.genome_diversity = self.compute_genome_diversity();
+
+        RoundCompleted {
+            round_number: self.round_number,
+            generation: self.generation,
+            participants: self.participants,
+            best_fitness: self.best_fitness,
+            average_fitness: self.average_fitness,
+            worst_fitness: self.worst_fitness,
+            fitness_variance: self.fitness_variance,
+            lower_quartile_fitness: self.lower_quartile_fitness,
+            median_fitness: self.median_fitness,
+            upper_quartile_fitness: self.upper_quartile_fitness,
+            histogram: self.histogram,
+            genome_diversity: self.genome_diversity,
+            agents_skipped: self.eligible_agents.saturating_sub(self.participants),
+        }
+    }
+
+    /// Count one agent's score towards the round's totals, best and worst
+    /// and distribution statistics. Returns the genome's hash.
+    pub fn record_score(&mut self, agent: Pubkey, fitness: u64, genome: &[u8]) -> Result<[u8; 32]> {
+        self.participants = self
+            .participants
+            .checked_add(1)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+        self.total_fitness = self
+            .total_fitness
+            .checked_add(fitness)
+            .ok_or(SaeaError::ArithmeticOverflow)?;
+
+        let genome_hash = hash(genome).to_bytes();
+        if fitness > self.best_fitness {
+            self.best_fitness = fitness;
+            self.best_agent = agent;
+            self.best_genome_hash = genome_hash;
+        }
+        if fitness < self.worst_fitness {
+            self.worst_fitness = fitness;
+        }
+        self.accumulate_statistics(fitness, genome);
+        Ok(genome_hash)
+    }
+
+    /// Fold one score and its genome into the round's distribution statistics.
+    pub fn accumulate_statistics(&mut self, fitness: u64, genome: &[u8]) {
+        self.fitness_sum_sq += fitness as u128 * fitness as u128;
+
+        let bucket = fitness
+            .saturating_sub(self.histogram_base)
+            .checked_div(self.histogram_bucket_width)
+            .unwrap_or(0)
+            .min(HISTOGRAM_BUCKETS as u64 - 1) as usize;
+        self.histogram[bucket] = self.histogram[bucket].saturating_add(1);
+
+        for (i, &gene) in genome.iter().enumerate().take(MAX_GENOME_LEN) {
+            self.gene_counts[i] += 1;
+            self.gene_sums[i] += gene as u32;
+            self.gene_sq_sums[i] += gene as u64 * gene as u64;
+        }
+    }
+
+    /// Population variance of the recorded scores. `n * sum_sq` only
+    /// overflows for sums of squares near `u128::MAX`; those fall back to
+    /// subtracting the squared total over `n` first.
+    pub fn compute_variance(&self) -> u64 {
+        if self.participants == 0 {
+            return 0;
+        }
+        let n = self.participants as u128;
+        let total = self.total_fitness as u128;
+        let variance = match n.checked_mul(self.fitness_sum_sq) {
+            Some(scaled) => scaled.saturating_sub(total * total) / (n * n),
+            None => self.fitness_sum_sq.saturating_sub(total * total / n) / n,
+        };
+        variance.min(u64::MAX as u128) as u64
+    }
+
+    /// Approximate `pct`th percentile, interpolated within its histogram
+    /// bucket and clamped to the observed best/worst scores. Bucket bounds
+    /// are computed in u128, so no layout overflows.
+    pub fn fitness_percentile(&self, pct: u64) -> u64 {
+        let n: u128 = self.histogram.iter().map(|&c| c as u128).sum();
+        if n == 0 {
+            return 0;
+        }
+        let rank = (pct as u128 * n).div_ceil(100).max(1);
+        let width = self.histogram_bucket_width as u128;
+        let mut below = 0u128;
+        for (i, &count) in self.histogram.iter().enumerate() {
+            let count = count as u128;
+            if count > 0 && below + count >= rank {
+                let lower = self.histogram_base as u128 + i as u128 * width;
+                // Midpoint of the rank's slot within the bucket.
+                let offset = width * (2 * (rank - below) - 1) / (2 * count);
+                let (worst, best) = (self.worst_fitness as u128, self.best_fitness as u128);
+                return (lower + offset).clamp(worst.min(best), best) as u64;
+            }
+            below += count;
+        }
+        self.best_fitness
+    }
+
+    /// Mean over gene positions of the variance of that gene across all
+    /// scored genomes; 0 means every scored genome was identical.
+    pub fn compute_genome_diversity(&self) -> u64 {
+        let mut total = 0u64;
+        let mut positions = 0u64;
+        for i in 0..MAX_GENOME_LEN {
+            let n = self.gene_counts[i] as u64;
+            if n == 0 {
+                continue;
+            }
+            let sum = self.gene_sums[i] as u64;
+            total += (n * self.gene_sq_sums[i] - sum * sum) / (n * n);
+            positions += 1;
+        }
+        total.checked_div(positions).unwrap_or(0)
+    }
 }
@@ -0,0 +1,110 @@
+use super::MAX_GENOME_LEN;
+use anchor_lang::prelude::*;
+
+/// Maximum number of species tracked at once; further outliers join the
+/// nearest existing species.
+pub const MAX_SPECIES: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum GenomeDistance {
+    /// Number of gene positions that differ.
+    Hamming,
+    /// Sum of absolute gene differences.
+    L1,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct SpeciesEntry {
+    pub id: u32,
+    pub members: u32, // agents assigned during `SpeciesRegistry::round_number`
+    #[max_len(MAX_GENOME_LEN)]
+    pub representative: Vec<u8>, // first genome assigned in the latest round
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SpeciesRegistry {
+    pub arena: Pubkey,
+    pub distance: GenomeDistance,
+    pub threshold: u32, // maximum distance to a representative to join its species
+    pub round_number: u64,
+    pub next_species_id: u32, // ids start at 1; 0 marks an unassigned agent
+    #[max_len(MAX_SPECIES)]
+    pub entries: Vec<SpeciesEntry>,
+    pub bump: u8,
+}
+
+impl SpeciesRegistry {
+    pub const SEED: &'static [u8] = b"species";
+
+    /// Distance between two genomes; genes missing from the shorter one
+    /// count as zero.
+    pub fn distance(&self, a: &[u8], b: &[u8]) -> u32 {
+        let len = a.len().max(b.len());
+        (0..len)
+            .map(|i| {
+                let x = a.get(i).copied().unwrap_or(0);
+                let y = b.get(i).copied().unwrap_or(0);
+                match self.distance {
+                    GenomeDistance::Hamming => (x != y) as u32,
+                    GenomeDistance::L1 => x.abs_diff(y) as u32,
+                }
+            })
+            .sum()
+    }
+
+    /// Place `genes` in the first species whose representative is within
+    /// the threshold, founding a new species if none is, and return its id.
+    /// Counts restart on each new round, and species left empty by the
+    /// previous round go extinct. The first genome to join a species in a
+    /// round becomes its representative, so species follow their members
+    /// as they evolve.
+    pub fn assign(&mut self, genes: &[u8], round_number: u64) -> u32 {
+        if round_number != self.round_number {
+            self.entries.retain(|e| e.members > 0);
+            for entry in self.entries.iter_mut() {
+                entry.members = 0;
+            }
+            self.round_number = round_number;
+        }
+
+        let nearest = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (i, self.distance(genes, &e.representative)))
+            .min_by_key(|&(_, d)| d);
+        let index = match self
+            .entries
+            .iter()
+            .position(|e| self.distance(genes, &e.representative) <= self.threshold)
+        {
+            Some(i) => i,
+            None if self.entries.len() < MAX_SPECIES => {
+                self.entries.push(SpeciesEntry {
+                    id: self.next_species_id,
+                    members: 0,
+                    representative: genes.to_vec(),
+                });
+                self.next_species_id += 1;
+                self.entries.len() - 1
+            }
+            None => nearest.map_or(0, |(i, _)| i),
+        };
+
+        let entry = &mut self.entries[index];
+        if entry.members == 0 {
+            entry.representative = genes.to_vec();
+        }
+        entry.members += 1;
+        entry.id
+    }
+
+    /// Agents assigned to species `id` in the current round.
+    pub fn members(&self, id: u32) -> u32 {
+        self.entries
+            .iter()
+            .find(|e| e.id == id)
+            .map_or(0, |e| e.members)
+    }
+}
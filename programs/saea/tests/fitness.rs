@@ -0,0 +1,110 @@
+//! Invariants of the scoring function, checked over generated genomes and
+//! seeds. Exact scores are pinned by the golden vectors in `saea-vectors`.
+
+use proptest::prelude::*;
+use saea::instructions::{
+    compute_fitness, compute_fitness_breakdown, compute_fitness_breakdown_with_targets,
+};
+use saea::state::{FitnessBreakdown, FitnessTargets, MAX_GENOME_LEN};
+use solana_sha256_hasher::hashv;
+
+/// Largest score each component can reach for `genome_len` genes.
+fn component_bounds(genome_len: u64) -> FitnessBreakdown {
+    FitnessBreakdown {
+        proximity: 255 * genome_len,
+        diversity: 500,
+        balance: 500,
+        pattern: 80 * genome_len,
+        sequence: 30 * genome_len.saturating_sub(1),
+        novelty: 0,
+    }
+}
+
+fn genome() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 1..=MAX_GENOME_LEN)
+}
+
+proptest! {
+    #[test]
+    fn deterministic(genome in genome(), seed in any::<[u8; 32]>()) {
+        let breakdown = compute_fitness_breakdown(&genome, &seed);
+        prop_assert_eq!(compute_fitness_breakdown(&genome, &seed), breakdown);
+        prop_assert_eq!(
+            compute_fitness_breakdown_with_targets(&genome, &seed, &FitnessTargets::derive(&seed)),
+            breakdown
+        );
+        prop_assert_eq!(breakdown.novelty, 0);
+        prop_assert_eq!(Some(compute_fitness(&genome, &seed)), breakdown.total());
+    }
+
+    #[test]
+    fn bounded_by_genome_length(genome in genome(), seed in any::<[u8; 32]>()) {
+        let bounds = component_bounds(genome.len() as u64);
+        let breakdown = compute_fitness_breakdown(&genome, &seed);
+        for (component, bound) in breakdown.objectives().iter().zip(bounds.objectives()) {
+            prop_assert!(*component <= bound, "{breakdown:?} exceeds {bounds:?}");
+        }
+        prop_assert!(compute_fitness(&genome, &seed) <= bounds.total().unwrap());
+    }
+
+    #[test]
+    fn saturated_genome_does_not_overflow(seed in any::<[u8; 32]>()) {
+        let genome = [u8::MAX; MAX_GENOME_LEN];
+        let breakdown = compute_fitness_breakdown(&genome, &seed);
+        let fitness = breakdown.total();
+        prop_assert!(fitness.is_some());
+        prop_assert!(fitness.unwrap() <= component_bounds(MAX_GENOME_LEN as u64).total().unwrap());
+    }
+
+    #[test]
+    fn seed_sensitive(genome in genome(), seed in any::<[u8; 32]>(), bit in 0..8u8, position in any::<prop::sample::Index>()) {
+        let position = position.index(genome.len());
+        let mut flipped = seed;
+        flipped[position] ^= 1 << bit;
+
+        // One seed bit under a gene moves that gene's pattern score by
+        // exactly one bit's worth, whatever the derived targets do.
+        let before = compute_fitness_breakdown(&genome, &seed);
+        let after = compute_fitness_breakdown(&genome, &flipped);
+        prop_assert_eq!(before.pattern.abs_diff(after.pattern), 10);
+        prop_assert_ne!(FitnessTargets::derive(&seed), FitnessTargets::derive(&flipped));
+    }
+}
+
+#[test]
+fn empty_genome_scores_zero() {
+    assert_eq!(compute_fitness(&[], &[0xAB; 32]), 0);
+    assert_eq!(
+        compute_fitness_breakdown(&[], &[0xAB; 32]),
+        FitnessBreakdown::default()
+    );
+}
+
+#[test]
+fn long_genome_scores_every_gene() {
+    let seed = [0x5A; 32];
+    let genome: Vec<u8> = (0..=MAX_GENOME_LEN as u8).map(|i| i * 7).collect();
+    let breakdown = compute_fitness_breakdown(&genome, &seed);
+    let head = compute_fitness_breakdown(&genome[..MAX_GENOME_LEN], &seed);
+
+    // The 33rd gene adds its own proximity, pattern and sequence terms,
+    // with its target hashed the same way `FitnessTargets` derives the rest.
+    let (last, prev) = (genome[MAX_GENOME_LEN], genome[MAX_GENOME_LEN - 1]);
+    let target = hashv(&[seed.as_ref(), &[MAX_GENOME_LEN as u8]]).to_bytes()[0];
+    assert_eq!(
+        breakdown.proximity,
+        head.proximity + 255 - last.abs_diff(target) as u64
+    );
+    assert_eq!(
+        breakdown.pattern,
+        head.pattern + (last ^ seed[0]).count_ones() as u64 * 10
+    );
+    let pair = MAX_GENOME_LEN as u8 - 1;
+    let ascend = hashv(&[seed.as_ref(), &[pair], &[0xFF]]).to_bytes()[0] > 127;
+    let step = if ascend == (last >= prev) {
+        last.abs_diff(prev).min(30) as u64
+    } else {
+        0
+    };
+    assert_eq!(breakdown.sequence, head.sequence + step);
+}
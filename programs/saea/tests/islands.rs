@@ -0,0 +1,90 @@
+//! Island state: where each island sends its migrants and which registry
+//! entries a migration picks.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use saea::instructions::migrants;
+use saea::state::{Arena, MigrationTopology, PopulationEntry, MAX_ISLANDS};
+
+fn arena(island_count: u8, topology: MigrationTopology) -> Arena {
+    let mut arena = Arena::new(Pubkey::default(), 64, 0, 500, 0);
+    arena.island_count = island_count;
+    arena.migration_topology = topology;
+    arena
+}
+
+/// Registry agents as (key, selection fitness, island).
+fn agents() -> impl Strategy<Value = Vec<([u8; 32], u64, u8)>> {
+    prop::collection::hash_map(any::<[u8; 32]>(), (0..4u64, 0..MAX_ISLANDS as u8), 0..=32)
+        .prop_map(|agents| agents.into_iter().map(|(k, (f, i))| (k, f, i)).collect())
+}
+
+fn entry((key, selection_fitness, island): ([u8; 32], u64, u8)) -> PopulationEntry {
+    PopulationEntry {
+        agent: Pubkey::new_from_array(key),
+        fitness: 0,
+        last_round: 0,
+        selection_fitness,
+        island,
+        _padding: [0; 7],
+    }
+}
+
+proptest! {
+    #[test]
+    fn migrants_are_the_fittest_of_each_island(agents in agents(), per_island in 1..4u8) {
+        let entries: Vec<PopulationEntry> = agents.into_iter().map(entry).collect();
+        let chosen = migrants(&entries, per_island);
+        for island in 0..MAX_ISLANDS as u8 {
+            let (sent, kept): (Vec<&PopulationEntry>, Vec<_>) = entries
+                .iter()
+                .filter(|e| e.island == island)
+                .partition(|e| chosen.contains(&e.agent));
+            prop_assert_eq!(sent.len(), (sent.len() + kept.len()).min(per_island as usize));
+            // Ties go to the lower key.
+            for migrant in &sent {
+                for resident in &kept {
+                    prop_assert!(
+                        (migrant.selection_fitness, std::cmp::Reverse(migrant.agent))
+                            > (resident.selection_fitness, std::cmp::Reverse(resident.agent))
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn migrants_leave_their_island(
+        island_count in 2..=MAX_ISLANDS as u8,
+        random in any::<bool>(),
+        seed in any::<[u8; 32]>(),
+    ) {
+        let topology = if random {
+            MigrationTopology::Random
+        } else {
+            MigrationTopology::Ring
+        };
+        let arena = arena(island_count, topology);
+        for island in 0..island_count {
+            let destination = arena.migration_destination(island, &seed);
+            prop_assert!(destination < island_count);
+            prop_assert_ne!(destination, island);
+        }
+    }
+}
+
+#[test]
+fn ring_sends_to_the_next_island() {
+    let arena = arena(3, MigrationTopology::Ring);
+    let destinations: Vec<u8> = (0..3)
+        .map(|island| arena.migration_destination(island, &[0; 32]))
+        .collect();
+    assert_eq!(destinations, [1, 2, 0]);
+}
+
+#[test]
+fn single_island_keeps_its_agents() {
+    for topology in [MigrationTopology::Ring, MigrationTopology::Random] {
+        assert_eq!(arena(1, topology).migration_destination(0, &[7; 32]), 0);
+    }
+}
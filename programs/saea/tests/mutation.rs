@@ -0,0 +1,74 @@
+//! The arena's stagnation rule for its mutation rate.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use saea::state::Arena;
+
+/// An adaptive arena at `rate_bps` within `[min_bps, max_bps]`, as
+/// `configure_mutation` leaves it.
+fn arena(rate_bps: u16, min_bps: u16, max_bps: u16, stagnation_window: u16) -> Arena {
+    let mut arena = Arena::new(Pubkey::default(), 64, 0, rate_bps, 0);
+    arena.adaptive_mutation = true;
+    arena.mutation_rate_min_bps = min_bps;
+    arena.mutation_rate_max_bps = max_bps;
+    arena.stagnation_window = stagnation_window;
+    arena.mutation_rate_bps = rate_bps.clamp(min_bps, max_bps);
+    arena
+}
+
+proptest! {
+    #[test]
+    fn rate_stays_within_its_bounds(
+        (min_bps, max_bps) in (0..=10_000u16, 0..=10_000u16)
+            .prop_map(|(a, b)| (a.min(b), a.max(b))),
+        rate_bps in 0..=10_000u16,
+        stagnation_window in 1..8u16,
+        bests in prop::collection::vec(0..64u64, 0..64),
+    ) {
+        let mut arena = arena(rate_bps, min_bps, max_bps, stagnation_window);
+        for best in bests {
+            let before = arena.mutation_rate_bps;
+            let changed = arena.adapt_mutation_rate(best);
+            prop_assert!((min_bps..=max_bps).contains(&arena.mutation_rate_bps));
+            prop_assert_eq!(changed, (arena.mutation_rate_bps != before).then_some(before));
+        }
+    }
+}
+
+#[test]
+fn a_new_best_shrinks_the_rate() {
+    let mut arena = arena(1000, 0, 10_000, 3);
+    assert_eq!(arena.adapt_mutation_rate(10), Some(1000));
+    assert_eq!(arena.mutation_rate_bps, 800);
+    assert_eq!(arena.recent_best_fitness, 10);
+}
+
+#[test]
+fn stagnation_grows_the_rate_after_the_window() {
+    let mut arena = arena(1000, 0, 10_000, 3);
+    arena.adapt_mutation_rate(10);
+    assert_eq!(arena.adapt_mutation_rate(10), None);
+    assert_eq!(arena.adapt_mutation_rate(5), None);
+    assert_eq!(arena.adapt_mutation_rate(8), Some(800));
+    assert_eq!(arena.mutation_rate_bps, 1040);
+    // The stagnant round's best is the new baseline.
+    assert_eq!(arena.recent_best_fitness, 8);
+    assert_eq!(arena.adapt_mutation_rate(9), Some(1040));
+}
+
+#[test]
+fn small_rates_still_grow() {
+    let mut arena = arena(1, 0, 10_000, 1);
+    assert_eq!(arena.adapt_mutation_rate(0), Some(1));
+    assert_eq!(arena.mutation_rate_bps, 2);
+}
+
+#[test]
+fn fixed_rates_do_not_adapt() {
+    let mut arena = arena(1000, 0, 10_000, 1);
+    arena.adaptive_mutation = false;
+    for best in [10, 0, 0, 20] {
+        assert_eq!(arena.adapt_mutation_rate(best), None);
+    }
+    assert_eq!(arena.mutation_rate_bps, 1000);
+}
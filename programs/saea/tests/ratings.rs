@@ -0,0 +1,75 @@
+//! Invariants of rating updates and of the opponents each agent is rated
+//! against after a solo round.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use saea::instructions::{rating_after, rating_opponents};
+use saea::state::{INITIAL_RATING, RATING_SCALE};
+
+fn scored() -> impl Strategy<Value = Vec<Pubkey>> {
+    prop::collection::hash_set(any::<[u8; 32]>(), 2..=24)
+        .prop_map(|keys| keys.into_iter().map(Pubkey::new_from_array).collect())
+}
+
+proptest! {
+    #[test]
+    fn opponents_are_distinct_neighbours(scored in scored(), seed in any::<[u8; 32]>()) {
+        for agent in &scored {
+            let opponents = rating_opponents(&scored, agent, &seed).unwrap();
+            prop_assert_eq!(opponents.len(), (scored.len() - 1).min(2));
+            prop_assert!(!opponents.contains(agent));
+            prop_assert!(opponents.iter().all(|o| scored.contains(o)));
+            // Every comparison is rated from both sides.
+            for opponent in &opponents {
+                let back = rating_opponents(&scored, opponent, &seed).unwrap();
+                prop_assert!(back.contains(agent));
+            }
+        }
+    }
+
+    #[test]
+    fn opponents_ignore_registry_order(scored in scored(), seed in any::<[u8; 32]>()) {
+        let mut reversed = scored.clone();
+        reversed.reverse();
+        for agent in &scored {
+            let mut forward = rating_opponents(&scored, agent, &seed).unwrap();
+            let mut backward = rating_opponents(&reversed, agent, &seed).unwrap();
+            forward.sort();
+            backward.sort();
+            prop_assert_eq!(forward, backward);
+        }
+    }
+
+    #[test]
+    fn match_is_zero_sum(
+        rating_a in 0..3000 * RATING_SCALE,
+        rating_b in 0..3000 * RATING_SCALE,
+        fitness_a in any::<u64>(),
+        fitness_b in any::<u64>(),
+    ) {
+        let a = rating_after(rating_a, fitness_a, &[(rating_b, fitness_b)]);
+        let b = rating_after(rating_b, fitness_b, &[(rating_a, fitness_a)]);
+        // Only flooring at zero or integer truncation can break the balance.
+        prop_assume!(a > 0 && b > 0);
+        prop_assert!((a + b).abs_diff(rating_a + rating_b) <= 1);
+    }
+}
+
+#[test]
+fn lone_or_unscored_agents_have_no_opponents() {
+    let agent = Pubkey::new_unique();
+    assert_eq!(rating_opponents(&[agent], &agent, &[0; 32]), None);
+    assert_eq!(
+        rating_opponents(&[Pubkey::new_unique()], &agent, &[0; 32]),
+        None
+    );
+}
+
+#[test]
+fn winner_gains_rating() {
+    let rating = INITIAL_RATING;
+    assert!(rating_after(rating, 2, &[(rating, 1)]) > rating);
+    assert!(rating_after(rating, 1, &[(rating, 2)]) < rating);
+    assert_eq!(rating_after(rating, 1, &[(rating, 1)]), rating);
+    assert_eq!(rating_after(rating, 1, &[]), rating);
+}
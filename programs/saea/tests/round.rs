@@ -0,0 +1,144 @@
+//! Round state as the instructions leave it: scoring against the stored
+//! targets, the agents a round expects and the statistics derived when it
+//! completes.
+
+use anchor_lang::prelude::*;
+use proptest::prelude::*;
+use saea::instructions::{compute_fitness_breakdown_with_targets, evaluate_genes};
+use saea::state::{AgentAccount, Arena, FitnessTargets, Round, RoundKind, MAX_GENOME_LEN};
+
+/// A solo round as `run_round` starts it.
+fn round(seed: [u8; 32]) -> Round {
+    let arena = Arena::new(Pubkey::default(), 64, 0, 500, 0);
+    Round::new(Pubkey::default(), &arena, 1, seed, 0, RoundKind::Solo, 0)
+}
+
+/// Apply `scores` to `round` as `record_score` does, then finalize it.
+fn play(mut round: Round, scores: &[u64]) -> Round {
+    for &fitness in scores {
+        round
+            .record_score(Pubkey::default(), fitness, &[0])
+            .unwrap();
+    }
+    round.finalize();
+    round
+}
+
+/// Population variance computed in floating point.
+fn reference_variance(scores: &[u64]) -> f64 {
+    let n = scores.len() as f64;
+    let mean = scores.iter().map(|&x| x as f64).sum::<f64>() / n;
+    scores
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n
+}
+
+fn genome() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 1..=MAX_GENOME_LEN)
+}
+
+proptest! {
+    /// Scoring cost per gene is a table lookup: `score_agent` reads the
+    /// targets `run_round` stored instead of hashing the seed again.
+    #[test]
+    fn scoring_reads_the_stored_targets(
+        genome in genome(),
+        seed in any::<[u8; 32]>(),
+        other in any::<[u8; 32]>(),
+    ) {
+        let mut round = round(seed);
+        round.targets = FitnessTargets::derive(&other);
+        let scored = evaluate_genes(&round, &genome, None, None).unwrap();
+        let expected = compute_fitness_breakdown_with_targets(&genome, &seed, &round.targets);
+        prop_assert_eq!(scored.breakdown, expected);
+        prop_assert_eq!(Some(scored.fitness), expected.total());
+    }
+
+    #[test]
+    fn variance_matches_the_definition(scores in prop::collection::vec(0..1u64 << 31, 1..64)) {
+        let round = play(round([0; 32]), &scores);
+        let expected = reference_variance(&scores);
+        prop_assert!((round.fitness_variance as f64 - expected).abs() <= expected * 1e-9 + 1.0);
+    }
+
+    #[test]
+    fn percentiles_stay_within_the_scores(
+        scores in prop::collection::vec(any::<u64>().prop_map(|x| x >> 8), 1..64),
+        base in any::<u64>(),
+        bucket_width in 1..=u64::MAX,
+    ) {
+        let mut round = round([0; 32]);
+        round.histogram_base = base;
+        round.histogram_bucket_width = bucket_width;
+        let round = play(round, &scores);
+        let (worst, best) = (round.worst_fitness, round.best_fitness);
+        prop_assert!(worst <= round.lower_quartile_fitness);
+        prop_assert!(round.lower_quartile_fitness <= round.median_fitness);
+        prop_assert!(round.median_fitness <= round.upper_quartile_fitness);
+        prop_assert!(round.upper_quartile_fitness <= best);
+    }
+}
+
+#[test]
+fn variance_of_extreme_scores_does_not_overflow() {
+    // 2^32 scores of 2^32 - 1 +- 2^20: `n * sum_sq` exceeds u128, so the
+    // fallback path runs.
+    let n = 1u128 << 32;
+    let mean = (1u128 << 32) - 1;
+    let mut round = round([0; 32]);
+    round.participants = n as u64;
+    round.total_fitness = (n * mean) as u64;
+    round.fitness_sum_sq = n * (mean * mean + (1 << 40));
+    assert_eq!(round.compute_variance(), 1 << 40);
+}
+
+#[test]
+fn percentiles_interpolate_within_buckets() {
+    let scores: Vec<u64> = (0..100).map(|i| i * 100).collect();
+    let round = play(round([0; 32]), &scores);
+    assert_eq!(round.lower_quartile_fitness, 2450);
+    assert_eq!(round.median_fitness, 4950);
+    assert_eq!(round.upper_quartile_fitness, 7450);
+}
+
+/// An agent registered when round `registered_round` was the latest, on
+/// `island`.
+fn agent(registered_round: u64, island: u8) -> AgentAccount {
+    let mut agent = AgentAccount::deserialize(&mut &[0u8; AgentAccount::INIT_SPACE][..]).unwrap();
+    agent.registered_round = registered_round;
+    agent.island = island;
+    agent
+}
+
+#[test]
+fn solo_rounds_expect_earlier_agents_of_their_island() {
+    let mut round = round([0; 32]);
+    round.round_number = 3;
+    let key = Pubkey::new_unique();
+    assert!(round.expects(&key, &agent(2, 1)));
+    assert!(!round.expects(&key, &agent(3, 1)));
+
+    round.island = Some(1);
+    assert!(round.expects(&key, &agent(2, 1)));
+    assert!(!round.expects(&key, &agent(2, 0)));
+}
+
+#[test]
+fn tournaments_expect_only_drawn_entrants() {
+    let arena = Arena::new(Pubkey::default(), 64, 0, 500, 0);
+    let mut round = Round::new(
+        Pubkey::default(),
+        &arena,
+        3,
+        [0; 32],
+        0,
+        RoundKind::Tournament,
+        0,
+    );
+    let (drawn, left_out) = (Pubkey::new_unique(), Pubkey::new_unique());
+    round.pairings = vec![drawn, Pubkey::new_unique()];
+    assert!(round.expects(&drawn, &agent(0, 0)));
+    assert!(!round.expects(&left_out, &agent(0, 0)));
+}
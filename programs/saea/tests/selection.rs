@@ -0,0 +1,66 @@
+//! Decayed fitness: the per-round retention derived from a half-life and
+//! the scores it folds together.
+
+use anchor_lang::prelude::*;
+use proptest::prelude::*;
+use saea::state::{AgentAccount, Arena, FITNESS_DECAY_SCALE};
+
+/// An agent with every field zeroed, as yet unscored.
+fn agent() -> AgentAccount {
+    AgentAccount::deserialize(&mut &[0u8; AgentAccount::INIT_SPACE][..]).unwrap()
+}
+
+proptest! {
+    #[test]
+    fn retention_halves_weight_over_the_half_life(half_life in 1..=u16::MAX) {
+        let retention = Arena::decay_retention(half_life);
+        let exact = FITNESS_DECAY_SCALE as f64 * 0.5f64.powf(1.0 / half_life as f64);
+        // Exact up to the fixed-point rounding of the bisection.
+        prop_assert!((retention as f64 - exact).abs() <= 2.0);
+        prop_assert!(retention < FITNESS_DECAY_SCALE);
+    }
+
+    #[test]
+    fn longer_half_lives_retain_more(half_life in 1..u16::MAX) {
+        prop_assert!(Arena::decay_retention(half_life) <= Arena::decay_retention(half_life + 1));
+    }
+
+    #[test]
+    fn decayed_fitness_stays_between_the_scores(
+        scores in prop::collection::vec(any::<u64>(), 1..32),
+        half_life in 1..64u16,
+    ) {
+        let retention = Arena::decay_retention(half_life);
+        let mut agent = agent();
+        for &fitness in &scores {
+            agent.record_fitness(fitness, retention);
+            agent.rounds_participated += 1;
+        }
+        prop_assert!(agent.decayed_fitness >= *scores.iter().min().unwrap());
+        prop_assert!(agent.decayed_fitness <= *scores.iter().max().unwrap());
+    }
+}
+
+#[test]
+fn one_round_half_life_averages_consecutive_scores() {
+    assert_eq!(Arena::decay_retention(1), FITNESS_DECAY_SCALE / 2);
+    let mut agent = agent();
+    agent.record_fitness(1000, Arena::decay_retention(1));
+    assert_eq!(agent.decayed_fitness, 1000);
+    agent.rounds_participated = 1;
+    agent.record_fitness(0, Arena::decay_retention(1));
+    assert_eq!(agent.decayed_fitness, 500);
+}
+
+#[test]
+fn a_score_weighs_half_after_its_half_life() {
+    let half_life = Arena::DEFAULT_HALF_LIFE;
+    let retention = Arena::decay_retention(half_life);
+    let mut agent = agent();
+    agent.record_fitness(1_000_000, retention);
+    for round in 1..=half_life as u64 {
+        agent.rounds_participated = round;
+        agent.record_fitness(0, retention);
+    }
+    assert!(agent.decayed_fitness.abs_diff(500_000) <= half_life as u64);
+}
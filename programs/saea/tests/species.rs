@@ -0,0 +1,46 @@
+//! Species assignment across rounds.
+
+use anchor_lang::prelude::Pubkey;
+use saea::state::{GenomeDistance, SpeciesRegistry};
+
+fn registry(threshold: u32) -> SpeciesRegistry {
+    SpeciesRegistry {
+        arena: Pubkey::default(),
+        distance: GenomeDistance::Hamming,
+        threshold,
+        round_number: 0,
+        next_species_id: 1,
+        entries: Vec::new(),
+        bump: 0,
+    }
+}
+
+#[test]
+fn representatives_follow_their_species() {
+    let mut species = registry(2);
+    let founder = [0u8; 8];
+    let drifted = [1, 1, 0, 0, 0, 0, 0, 0];
+    let further = [1, 1, 1, 1, 0, 0, 0, 0];
+
+    let id = species.assign(&founder, 1);
+    assert_eq!(species.assign(&drifted, 2), id);
+    assert_eq!(species.entries[0].representative, drifted);
+    // Four genes from the founder but two from this round's representative.
+    assert_eq!(species.assign(&further, 2), id);
+    assert_eq!(species.entries[0].representative, drifted);
+    assert_eq!(species.members(id), 2);
+}
+
+#[test]
+fn species_left_empty_go_extinct() {
+    let mut species = registry(0);
+    let first = species.assign(&[0], 1);
+    let second = species.assign(&[9], 1);
+    assert_ne!(first, second);
+
+    species.assign(&[0], 2);
+    species.assign(&[0], 3);
+    assert_eq!(species.members(first), 1);
+    assert_eq!(species.members(second), 0);
+    assert_eq!(species.entries.len(), 1);
+}
@@ -0,0 +1,46 @@
+//! Invariants of the tournament draw over generated registries and seeds.
+
+use anchor_lang::prelude::Pubkey;
+use proptest::prelude::*;
+use saea::instructions::draw_pairings;
+use saea::state::MAX_TOURNAMENT_ENTRANTS;
+
+fn registered() -> impl Strategy<Value = Vec<Pubkey>> {
+    prop::collection::hash_set(any::<[u8; 32]>(), 0..=3 * MAX_TOURNAMENT_ENTRANTS)
+        .prop_map(|keys| keys.into_iter().map(Pubkey::new_from_array).collect())
+}
+
+proptest! {
+    #[test]
+    fn draws_distinct_registered_pairs(registered in registered(), seed in any::<[u8; 32]>()) {
+        let pairings = draw_pairings(registered.clone(), &seed);
+        prop_assert_eq!(pairings.len(), registered.len().min(MAX_TOURNAMENT_ENTRANTS) & !1);
+        prop_assert!(pairings.iter().all(|a| registered.contains(a)));
+        let mut distinct = pairings.clone();
+        distinct.sort();
+        distinct.dedup();
+        prop_assert_eq!(distinct.len(), pairings.len());
+    }
+
+    #[test]
+    fn draw_follows_the_seed(registered in registered(), seed in any::<[u8; 32]>()) {
+        prop_assert_eq!(
+            draw_pairings(registered.clone(), &seed),
+            draw_pairings(registered, &seed)
+        );
+    }
+}
+
+#[test]
+fn large_registries_draw_varying_subsets() {
+    let registered: Vec<Pubkey> = (0..4 * MAX_TOURNAMENT_ENTRANTS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let first = draw_pairings(registered.clone(), &[1; 32]);
+    let second = draw_pairings(registered, &[2; 32]);
+    let mut first_drawn = first.clone();
+    let mut second_drawn = second.clone();
+    first_drawn.sort();
+    second_drawn.sort();
+    assert_ne!(first_drawn, second_drawn);
+}
@@ -0,0 +1,27 @@
+#!/bin/bash
+set -e
+
+# LiteSVM suite prerequisites: the SBF toolchain, which the suite's build
+# script uses to compile the program, and the harness's own dependencies,
+# which are not vendored with the workspace. Extra arguments are passed to
+# cargo test.
+
+cd "$(dirname "$0")/.."
+MANIFEST=tests/litesvm/Cargo.toml
+
+echo "=== SAEA LiteSVM Tests ==="
+echo ""
+
+if [ -z "$SAEA_PROGRAM_SO" ] && ! command -v cargo-build-sbf &> /dev/null; then
+    echo "cargo-build-sbf not found; run scripts/bootstrap.sh or set SAEA_PROGRAM_SO"
+    exit 1
+fi
+
+# Only the first run needs network; afterwards everything is in the cache.
+if ! cargo fetch --manifest-path $MANIFEST --offline &> /dev/null; then
+    echo "Fetching LiteSVM dependencies..."
+    cargo fetch --manifest-path $MANIFEST
+fi
+
+echo "Running tests..."
+cargo test --manifest-path $MANIFEST --offline "$@"
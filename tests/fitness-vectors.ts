@@ -0,0 +1,38 @@
+import * as fs from "fs";
+import * as path from "path";
+import { expect } from "chai";
+import {
+  computeFitness,
+  computeFitnessBreakdown,
+  FITNESS_VERSION,
+} from "../agent/src/fitness";
+
+interface Vector {
+  genome: number[];
+  seed: string;
+  fitness: number;
+  breakdown: Record<string, number>;
+}
+
+describe("fitness vectors", () => {
+  const file = JSON.parse(
+    fs.readFileSync(path.join(__dirname, "vectors", "fitness.json"), "utf-8")
+  );
+
+  it("matches the scorer version", () => {
+    expect(file.version).to.equal(FITNESS_VERSION);
+  });
+
+  it("reproduces every golden vector", () => {
+    expect(file.vectors).to.not.be.empty;
+    file.vectors.forEach((vector: Vector, index: number) => {
+      const genome = Buffer.from(vector.genome);
+      const seed = Buffer.from(vector.seed, "hex");
+      const label = `vector ${index}`;
+      expect(computeFitnessBreakdown(genome, seed), label).to.deep.equal(
+        vector.breakdown
+      );
+      expect(computeFitness(genome, seed), label).to.equal(vector.fitness);
+    });
+  });
+});
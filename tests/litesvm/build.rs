@@ -0,0 +1,56 @@
+//! Build the program for the suite so it never runs against a stale
+//! `target/deploy/saea.so`. Set SAEA_PROGRAM_SO to load a prebuilt binary
+//! instead. Shared with the LiteSVM fuzz target.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=SAEA_PROGRAM_SO");
+    if let Ok(path) = env::var("SAEA_PROGRAM_SO") {
+        println!("cargo:rustc-env=SAEA_PROGRAM_SO={path}");
+        return;
+    }
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let program = manifest_dir
+        .ancestors()
+        .map(|dir| dir.join("programs/saea"))
+        .find(|dir| dir.join("Cargo.toml").exists())
+        .expect("programs/saea not found above the manifest");
+    println!("cargo:rerun-if-changed={}", program.join("src").display());
+    println!(
+        "cargo:rerun-if-changed={}",
+        program.join("Cargo.toml").display()
+    );
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap()).join("deploy");
+    // The program builds in its own workspace's target directory; inheriting
+    // this build's would block on its lock.
+    let status = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .arg("build-sbf")
+        .arg("--manifest-path")
+        .arg(program.join("Cargo.toml"))
+        .arg("--sbf-out-dir")
+        .arg(&out_dir)
+        .env_remove("CARGO_TARGET_DIR")
+        .env_remove("RUSTFLAGS")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .status();
+    let failure = match status {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(status.to_string()),
+        Err(e) => Some(e.to_string()),
+    };
+    if let Some(failure) = failure {
+        panic!(
+            "cargo build-sbf failed ({failure}); install the Solana toolchain \
+             (scripts/bootstrap.sh) or set SAEA_PROGRAM_SO to a built saea.so"
+        );
+    }
+    println!(
+        "cargo:rustc-env=SAEA_PROGRAM_SO={}",
+        out_dir.join("saea.so").display()
+    );
+}
@@ -0,0 +1,256 @@
+//! Shared harness: a fresh LiteSVM per test with the compiled program
+//! loaded and a funded arena authority.
+
+#![allow(dead_code)]
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::error::InstructionError;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use litesvm::types::{FailedTransactionMetadata, TransactionMetadata, TransactionResult};
+use litesvm::LiteSVM;
+use saea::errors::SaeaError;
+use saea::state::{AgentAccount, Arena, Round};
+use saea_client::accounts::{decode, decode_population, PopulationSnapshot};
+use saea_client::events::{parse_logs, SaeaEvent};
+use saea_client::{instructions as ix, pda};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+pub const MAX_AGENTS: u64 = 8;
+pub const MIN_FITNESS_THRESHOLD: u64 = 500;
+pub const MUTATION_RATE_BPS: u16 = 3000;
+
+/// Upper bounds on compute units per instruction; a regression past one of
+/// these fails `assert_compute_units`.
+pub const CU_INITIALIZE_ARENA: u64 = 40_000;
+pub const CU_REGISTER_AGENT: u64 = 40_000;
+pub const CU_SUBMIT_GENOME: u64 = 25_000;
+pub const CU_RUN_ROUND: u64 = 40_000;
+pub const CU_SCORE_AGENT: u64 = 60_000;
+pub const CU_COMPLETE_ROUND: u64 = 40_000;
+pub const CU_PRUNE_AGENT: u64 = 25_000;
+pub const CU_ADVANCE_GENERATION: u64 = 40_000;
+pub const CU_CONFIGURE: u64 = 40_000; // every configure_* instruction
+pub const CU_RUN_TOURNAMENT_ROUND: u64 = 60_000;
+pub const CU_PLAY_MATCH: u64 = 80_000;
+pub const CU_UPDATE_RATINGS: u64 = 40_000;
+pub const CU_SHARE_FITNESS: u64 = 120_000;
+pub const CU_MIGRATE: u64 = 120_000;
+pub const CU_RANK_PARETO: u64 = 150_000;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub authority: Keypair,
+}
+
+impl Harness {
+    /// A VM with the program loaded and a funded authority, no arena yet.
+    pub fn empty() -> Self {
+        let mut svm = LiteSVM::new();
+        // Built by build.rs, or SAEA_PROGRAM_SO when set at build time.
+        let path = env!("SAEA_PROGRAM_SO");
+        svm.add_program_from_file(saea::ID, path)
+            .unwrap_or_else(|e| panic!("load {path}: {e:?}"));
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100 * LAMPORTS_PER_SOL)
+            .unwrap();
+        Self { svm, authority }
+    }
+
+    /// A VM with the arena initialized by `authority`.
+    pub fn new() -> Self {
+        Self::with_threshold(MIN_FITNESS_THRESHOLD)
+    }
+
+    /// As `new`, pruning agents below `min_fitness_threshold`.
+    pub fn with_threshold(min_fitness_threshold: u64) -> Self {
+        let mut h = Self::empty();
+        h.ok(ix::initialize_arena(
+            h.authority.pubkey(),
+            MAX_AGENTS,
+            min_fitness_threshold,
+            MUTATION_RATE_BPS,
+        ));
+        h
+    }
+
+    pub fn funded_keypair(&mut self) -> Keypair {
+        let keypair = Keypair::new();
+        self.svm
+            .airdrop(&keypair.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .unwrap();
+        keypair
+    }
+
+    /// Send one instruction signed by `signer`, who also pays.
+    pub fn send_as(&mut self, instruction: Instruction, signer: &Keypair) -> TransactionResult {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        // Identical follow-up transactions would otherwise be rejected as
+        // already processed.
+        self.svm.expire_blockhash();
+        result
+    }
+
+    pub fn send(&mut self, instruction: Instruction) -> TransactionResult {
+        let authority = self.authority.insecure_clone();
+        self.send_as(instruction, &authority)
+    }
+
+    pub fn ok(&mut self, instruction: Instruction) -> TransactionMetadata {
+        unwrap_ok(self.send(instruction))
+    }
+
+    pub fn ok_as(&mut self, instruction: Instruction, signer: &Keypair) -> TransactionMetadata {
+        unwrap_ok(self.send_as(instruction, signer))
+    }
+
+    pub fn account<T: AccountDeserialize>(&self, key: &Pubkey) -> T {
+        let account = self
+            .svm
+            .get_account(key)
+            .unwrap_or_else(|| panic!("account {key} missing"));
+        decode(&account.data).unwrap()
+    }
+
+    pub fn arena(&self) -> Arena {
+        self.account(&pda::arena().0)
+    }
+
+    pub fn agent(&self, key: &Pubkey) -> AgentAccount {
+        self.account(key)
+    }
+
+    pub fn round(&self, round_number: u64) -> Round {
+        self.account(&pda::round(&pda::arena().0, round_number).0)
+    }
+
+    pub fn population(&self) -> PopulationSnapshot {
+        let key = pda::population(&pda::arena().0).0;
+        decode_population(&self.svm.get_account(&key).unwrap().data).unwrap()
+    }
+
+    /// Register a new agent owned by a fresh funded keypair.
+    pub fn register(&mut self, genome: &[u8]) -> (Keypair, Pubkey) {
+        let owner = self.funded_keypair();
+        let index = self.arena().total_agents;
+        self.ok_as(
+            ix::register_agent(owner.pubkey(), index, genome.to_vec()),
+            &owner,
+        );
+        let agent = pda::agent(&pda::arena().0, &owner.pubkey(), index).0;
+        (owner, agent)
+    }
+
+    /// Start the next solo round and return its number.
+    pub fn start_round(&mut self) -> u64 {
+        let round_number = self.arena().current_round + 1;
+        self.ok(ix::run_round(self.authority.pubkey(), round_number));
+        round_number
+    }
+
+    pub fn score(&mut self, round_number: u64, agent: Pubkey) -> TransactionMetadata {
+        self.ok(ix::score_agent(
+            self.authority.pubkey(),
+            round_number,
+            agent,
+            ix::ScoreAccounts::default(),
+        ))
+    }
+
+    /// Run a full round over `agents` and return its number.
+    pub fn play_round(&mut self, agents: &[Pubkey]) -> u64 {
+        let round_number = self.start_round();
+        for agent in agents {
+            self.score(round_number, *agent);
+        }
+        self.ok(ix::complete_round(self.authority.pubkey(), round_number));
+        round_number
+    }
+
+    /// Opponents `agent` is rated against after solo round `round_number`.
+    pub fn rating_opponents(&self, round_number: u64, agent: Pubkey) -> Vec<Pubkey> {
+        let scored: Vec<Pubkey> = self
+            .population()
+            .entries
+            .iter()
+            .filter(|e| e.last_round == round_number)
+            .map(|e| e.agent)
+            .collect();
+        let seed = self.round(round_number).seed;
+        saea::instructions::rating_opponents(&scored, &agent, &seed).unwrap_or_default()
+    }
+
+    /// `prune_agent` for `agent`, refunding its owner.
+    pub fn prune(&mut self, agent: Pubkey) -> TransactionResult {
+        let owner = self.agent(&agent).owner;
+        let current_round = Some(self.arena().current_round).filter(|&n| n > 0);
+        self.send(ix::prune_agent(
+            self.authority.pubkey(),
+            agent,
+            owner,
+            current_round,
+        ))
+    }
+
+    pub fn advance_generation(&mut self) -> TransactionMetadata {
+        let generation = self.arena().current_generation;
+        self.ok(ix::advance_generation(self.authority.pubkey(), generation))
+    }
+}
+
+pub fn unwrap_ok(result: TransactionResult) -> TransactionMetadata {
+    match result {
+        Ok(meta) => meta,
+        Err(FailedTransactionMetadata { err, meta }) => {
+            panic!("transaction failed: {err:?}\n{:#?}", meta.logs)
+        }
+    }
+}
+
+/// Assert the transaction failed with `error` from its only instruction.
+pub fn assert_error(result: TransactionResult, error: SaeaError) {
+    let expected = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(error as u32 + ERROR_CODE_OFFSET),
+    );
+    match result {
+        Ok(meta) => panic!(
+            "expected {error:?}, transaction succeeded\n{:#?}",
+            meta.logs
+        ),
+        Err(failed) => assert_eq!(
+            failed.err, expected,
+            "expected {error:?}\n{:#?}",
+            failed.meta.logs
+        ),
+    }
+}
+
+/// Assert the transaction failed, for errors raised by the runtime or
+/// Anchor rather than the program.
+pub fn assert_failed(result: TransactionResult) {
+    assert!(result.is_err(), "expected the transaction to fail");
+}
+
+pub fn events(meta: &TransactionMetadata) -> Vec<SaeaEvent> {
+    parse_logs(&meta.logs)
+}
+
+pub fn assert_compute_units(meta: &TransactionMetadata, ceiling: u64, instruction: &str) {
+    assert!(
+        meta.compute_units_consumed <= ceiling,
+        "{instruction} used {} CU, ceiling {ceiling}",
+        meta.compute_units_consumed
+    );
+}
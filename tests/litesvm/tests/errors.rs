@@ -0,0 +1,1030 @@
+//! Every reachable `SaeaError`, each triggered through the instruction that
+//! raises it.
+//!
+//! Not covered: `ArenaAlreadyInitialized`, `AgentAlreadyActive`,
+//! `NoAgentsToPrune` and `InvalidFitnessThreshold` are never raised by the
+//! program; no instruction deactivates an arena, so `ArenaNotActive` is
+//! unreachable; and `ArithmeticOverflow` needs counters near `u64::MAX`.
+
+mod common;
+
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use common::*;
+use saea::errors::SaeaError;
+use saea::instructions::MAX_PARETO_BATCH;
+use saea::state::{
+    BenchmarkModel, CompletionPolicy, FitnessMode, GenomeDistance, MigrationTopology,
+    SelectionMetric, MAX_ISLANDS, MAX_NOVELTY_WEIGHT,
+};
+use saea_client::instructions as ix;
+use solana_signer::Signer;
+
+const GENOME: [u8; 16] = [
+    10, 250, 30, 200, 50, 180, 70, 160, 90, 140, 110, 120, 130, 100, 150, 80,
+];
+
+/// `set_fitness_mode` with its optional benchmark and novelty archive both
+/// left out, which the client builder never produces.
+fn set_fitness_mode_bare(
+    authority: anchor_lang::prelude::Pubkey,
+    mode: FitnessMode,
+) -> Instruction {
+    let mut instruction = ix::set_fitness_mode(authority, mode);
+    for meta in &mut instruction.accounts[1..3] {
+        *meta = AccountMeta::new_readonly(saea::ID, false);
+    }
+    instruction
+}
+
+fn register_many(h: &mut Harness, count: usize) -> Vec<anchor_lang::prelude::Pubkey> {
+    (0..count)
+        .map(|i| {
+            let mut genome = GENOME;
+            genome[0] = i as u8;
+            h.register(&genome).1
+        })
+        .collect()
+}
+
+#[test]
+fn initialize_arena_errors() {
+    let mut h = Harness::empty();
+    let authority = h.authority.pubkey();
+    assert_error(
+        h.send(ix::initialize_arena(authority, 1, 0, 0)),
+        SaeaError::InvalidMaxAgents,
+    );
+    assert_error(
+        h.send(ix::initialize_arena(authority, 8, 0, 10_001)),
+        SaeaError::InvalidMutationRate,
+    );
+    h.ok(ix::initialize_arena(authority, 8, 0, 0));
+    // The arena PDA already exists.
+    assert_failed(h.send(ix::initialize_arena(authority, 8, 0, 0)));
+}
+
+#[test]
+fn register_agent_errors() {
+    let mut h = Harness::new();
+    let owner = h.funded_keypair();
+    assert_error(
+        h.send_as(ix::register_agent(owner.pubkey(), 0, vec![]), &owner),
+        SaeaError::InvalidGenomeLength,
+    );
+    assert_error(
+        h.send_as(ix::register_agent(owner.pubkey(), 0, vec![1; 33]), &owner),
+        SaeaError::InvalidGenomeLength,
+    );
+
+    register_many(&mut h, MAX_AGENTS as usize);
+    let owner = h.funded_keypair();
+    let index = h.arena().total_agents;
+    assert_error(
+        h.send_as(
+            ix::register_agent(owner.pubkey(), index, GENOME.to_vec()),
+            &owner,
+        ),
+        SaeaError::MaxAgentsReached,
+    );
+}
+
+#[test]
+fn submit_genome_errors() {
+    let mut h = Harness::with_threshold(u64::MAX);
+    let (owner, agent) = h.register(&GENOME);
+    let (_, other) = h.register(&GENOME);
+    let stranger = h.funded_keypair();
+
+    assert_error(
+        h.send_as(
+            ix::submit_genome(stranger.pubkey(), agent, GENOME.to_vec(), other),
+            &stranger,
+        ),
+        SaeaError::Unauthorized,
+    );
+    assert_error(
+        h.send_as(
+            ix::submit_genome(owner.pubkey(), agent, vec![], other),
+            &owner,
+        ),
+        SaeaError::InvalidGenomeLength,
+    );
+
+    h.play_round(&[agent, other]);
+    unwrap_ok(h.prune(agent));
+    assert_error(
+        h.send_as(
+            ix::submit_genome(owner.pubkey(), agent, GENOME.to_vec(), other),
+            &owner,
+        ),
+        SaeaError::AgentNotActive,
+    );
+}
+
+#[test]
+fn scoring_errors() {
+    let mut h = Harness::new();
+    let agents = register_many(&mut h, 2);
+    let round_number = h.start_round();
+    h.score(round_number, agents[0]);
+    assert_error(
+        h.send(ix::score_agent(
+            h.authority.pubkey(),
+            round_number,
+            agents[0],
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::AlreadyParticipated,
+    );
+
+    let intruder = h.funded_keypair();
+    assert_error(
+        h.send_as(
+            ix::score_agent(
+                intruder.pubkey(),
+                round_number,
+                agents[1],
+                ix::ScoreAccounts::default(),
+            ),
+            &intruder,
+        ),
+        SaeaError::Unauthorized,
+    );
+
+    h.ok(ix::complete_round(h.authority.pubkey(), round_number));
+    assert_error(
+        h.send(ix::score_agent(
+            h.authority.pubkey(),
+            round_number,
+            agents[1],
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::RoundAlreadyComplete,
+    );
+    assert_error(
+        h.send(ix::complete_round(h.authority.pubkey(), round_number)),
+        SaeaError::RoundAlreadyComplete,
+    );
+}
+
+#[test]
+fn unauthorized_admin_instructions() {
+    let mut h = Harness::new();
+    let intruder = h.funded_keypair();
+    let key = intruder.pubkey();
+    let attempts = vec![
+        ix::run_round(key, 1),
+        ix::advance_generation(key, 0),
+        ix::configure_selection(key, SelectionMetric::Decayed, 4, 4),
+        ix::configure_histogram(key, 0, 100),
+        ix::configure_mutation(key, true, 0, 10_000, 1),
+        ix::configure_strategy_genes(key, 1),
+        ix::configure_islands(key, 2, 1, MigrationTopology::Ring, 1),
+        ix::configure_pareto(key, true, 1),
+        ix::configure_completion(key, CompletionPolicy::Strict, 100),
+        ix::set_fitness_mode(key, FitnessMode::Seeded),
+        ix::initialize_hall_of_fame(key),
+        ix::initialize_species(key, GenomeDistance::Hamming, 4),
+        ix::initialize_novelty_archive(key, 1, 0, 1),
+        ix::create_benchmark(key, BenchmarkModel::Linear, 1, 2, 1),
+    ];
+    for attempt in attempts {
+        assert_error(h.send_as(attempt, &intruder), SaeaError::Unauthorized);
+    }
+}
+
+#[test]
+fn prune_errors() {
+    let mut h = Harness::with_threshold(0);
+    let (_, agent) = h.register(&GENOME);
+    let (_, other) = h.register(&GENOME);
+    h.play_round(&[agent, other]);
+    assert_error(h.prune(agent), SaeaError::AgentAboveThreshold);
+
+    let mut h = Harness::with_threshold(u64::MAX);
+    let (_, agent) = h.register(&GENOME);
+    let (_, other) = h.register(&GENOME);
+    h.play_round(&[agent, other]);
+    unwrap_ok(h.prune(agent));
+    assert_error(h.prune(agent), SaeaError::AgentNotActive);
+    // Without islands the last agent can be pruned too.
+    unwrap_ok(h.prune(other));
+    assert_eq!(h.arena().active_agents, 0);
+}
+
+#[test]
+fn configuration_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let cases = vec![
+        (
+            ix::configure_selection(authority, SelectionMetric::Decayed, 0, 4),
+            SaeaError::InvalidSelectionConfig,
+        ),
+        (
+            ix::configure_selection(authority, SelectionMetric::Windowed, 4, 0),
+            SaeaError::InvalidSelectionConfig,
+        ),
+        (
+            ix::configure_histogram(authority, 0, 0),
+            SaeaError::InvalidHistogramConfig,
+        ),
+        (
+            ix::configure_histogram(authority, u64::MAX - 1000, 1000),
+            SaeaError::InvalidHistogramConfig,
+        ),
+        (
+            ix::configure_histogram(authority, 0, u64::MAX / 8),
+            SaeaError::InvalidHistogramConfig,
+        ),
+        (
+            ix::configure_mutation(authority, true, 6000, 5000, 1),
+            SaeaError::InvalidMutationBounds,
+        ),
+        (
+            ix::configure_mutation(authority, true, 0, 10_001, 1),
+            SaeaError::InvalidMutationBounds,
+        ),
+        (
+            ix::configure_strategy_genes(authority, 32),
+            SaeaError::InvalidStrategyGenes,
+        ),
+        (
+            ix::configure_islands(authority, 0, 1, MigrationTopology::Ring, 1),
+            SaeaError::InvalidIslandConfig,
+        ),
+        (
+            ix::configure_islands(
+                authority,
+                MAX_ISLANDS as u8 + 1,
+                1,
+                MigrationTopology::Ring,
+                1,
+            ),
+            SaeaError::InvalidIslandConfig,
+        ),
+        (
+            ix::configure_completion(authority, CompletionPolicy::Quorum, 0),
+            SaeaError::InvalidCompletionQuorum,
+        ),
+        (
+            ix::configure_completion(authority, CompletionPolicy::Quorum, 101),
+            SaeaError::InvalidCompletionQuorum,
+        ),
+        (
+            ix::initialize_novelty_archive(authority, 0, 0, 1),
+            SaeaError::InvalidNoveltyConfig,
+        ),
+        (
+            ix::initialize_novelty_archive(authority, 1, 0, MAX_NOVELTY_WEIGHT + 1),
+            SaeaError::InvalidNoveltyConfig,
+        ),
+        (
+            set_fitness_mode_bare(authority, FitnessMode::Benchmark),
+            SaeaError::BenchmarkRequired,
+        ),
+        (
+            set_fitness_mode_bare(authority, FitnessMode::Novelty),
+            SaeaError::NoveltyArchiveRequired,
+        ),
+    ];
+    for (instruction, error) in cases {
+        assert_error(h.send(instruction), error);
+    }
+
+    h.register(&GENOME);
+    assert_error(
+        h.send(ix::configure_islands(
+            authority,
+            2,
+            1,
+            MigrationTopology::Ring,
+            1,
+        )),
+        SaeaError::IslandsLocked,
+    );
+}
+
+#[test]
+fn benchmark_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    assert_error(
+        h.send(ix::create_benchmark(
+            authority,
+            BenchmarkModel::Linear,
+            0,
+            2,
+            1,
+        )),
+        SaeaError::InvalidBenchmarkShape,
+    );
+    assert_error(
+        h.send(ix::create_benchmark(
+            authority,
+            BenchmarkModel::Linear,
+            1,
+            1,
+            1,
+        )),
+        SaeaError::InvalidBenchmarkShape,
+    );
+
+    h.ok(ix::create_benchmark(
+        authority,
+        BenchmarkModel::DecisionTable,
+        1,
+        2,
+        2,
+    ));
+    // Setting the mode before the benchmark is frozen.
+    assert_error(
+        h.send(ix::set_fitness_mode(authority, FitnessMode::Benchmark)),
+        SaeaError::BenchmarkNotFrozen,
+    );
+    assert_error(
+        h.send(ix::upload_benchmark_chunk(authority, 2, vec![0, 0])),
+        SaeaError::InvalidBenchmarkChunk,
+    );
+    assert_error(
+        h.send(ix::upload_benchmark_chunk(authority, 0, vec![0; 5])),
+        SaeaError::InvalidBenchmarkChunk,
+    );
+    h.ok(ix::upload_benchmark_chunk(authority, 0, vec![0, 0]));
+    assert_error(
+        h.send(ix::freeze_benchmark(authority)),
+        SaeaError::InvalidBenchmarkData,
+    );
+    // Label 2 is out of range for two classes.
+    h.ok(ix::upload_benchmark_chunk(authority, 2, vec![200, 2]));
+    assert_error(
+        h.send(ix::freeze_benchmark(authority)),
+        SaeaError::InvalidBenchmarkData,
+    );
+}
+
+#[test]
+fn frozen_benchmark_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::create_benchmark(
+        authority,
+        BenchmarkModel::DecisionTable,
+        1,
+        2,
+        1,
+    ));
+    h.ok(ix::upload_benchmark_chunk(authority, 0, vec![0, 0]));
+    h.ok(ix::freeze_benchmark(authority));
+    assert_error(
+        h.send(ix::upload_benchmark_chunk(authority, 2, vec![0, 0])),
+        SaeaError::BenchmarkFrozen,
+    );
+    assert_error(
+        h.send(ix::freeze_benchmark(authority)),
+        SaeaError::BenchmarkFrozen,
+    );
+
+    h.ok(ix::set_fitness_mode(authority, FitnessMode::Benchmark));
+    let (_, agent) = h.register(&GENOME);
+    let round_number = h.start_round();
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            round_number,
+            agent,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::BenchmarkRequired,
+    );
+
+    // Benchmark scores have no objectives to rank by.
+    assert_error(
+        h.send(ix::configure_pareto(authority, true, 1)),
+        SaeaError::ParetoNeedsObjectives,
+    );
+    h.ok(ix::complete_round(authority, round_number));
+    assert_error(
+        h.send(ix::rank_pareto(authority, round_number, &[agent])),
+        SaeaError::ParetoNeedsObjectives,
+    );
+}
+
+#[test]
+fn pareto_errors() {
+    // More agents than one batch ranks could never all carry a rank.
+    let mut h = Harness::empty();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_arena(
+        authority,
+        MAX_PARETO_BATCH as u64 + 1,
+        MIN_FITNESS_THRESHOLD,
+        MUTATION_RATE_BPS,
+    ));
+    assert_error(
+        h.send(ix::configure_pareto(authority, true, 1)),
+        SaeaError::ParetoArenaTooLarge,
+    );
+
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::configure_pareto(authority, true, 1));
+    assert_error(
+        h.send(set_fitness_mode_bare(authority, FitnessMode::Benchmark)),
+        SaeaError::ParetoNeedsObjectives,
+    );
+
+    let agents = register_many(&mut h, 2);
+    h.ok(ix::run_tournament_round(authority, 1));
+    let (a, b) = h.round(1).pairing(0).unwrap();
+    h.ok(ix::play_match(
+        authority,
+        1,
+        0,
+        a,
+        b,
+        ix::ScoreAccounts::default(),
+    ));
+    h.ok(ix::complete_round(authority, 1));
+    assert_error(
+        h.send(ix::rank_pareto(authority, 1, &agents)),
+        SaeaError::WrongRoundKind,
+    );
+}
+
+#[test]
+fn mutate_genome_errors() {
+    let mut h = Harness::new();
+    let (owner, agent) = h.register(&GENOME);
+    assert_error(
+        h.send_as(ix::mutate_genome(owner.pubkey(), agent, 1), &owner),
+        SaeaError::SelfAdaptationDisabled,
+    );
+    h.ok(ix::configure_strategy_genes(h.authority.pubkey(), 4));
+    assert_error(
+        h.send_as(ix::mutate_genome(owner.pubkey(), agent, 1), &owner),
+        SaeaError::RoundRequired,
+    );
+    let round_number = h.start_round();
+    let stranger = h.funded_keypair();
+    assert_error(
+        h.send_as(
+            ix::mutate_genome(stranger.pubkey(), agent, round_number),
+            &stranger,
+        ),
+        SaeaError::Unauthorized,
+    );
+    let parent = h.register(&GENOME).1;
+    assert_error(
+        h.send_as(
+            ix::submit_genome(owner.pubkey(), agent, GENOME.to_vec(), parent),
+            &owner,
+        ),
+        SaeaError::SelfAdaptationEnabled,
+    );
+}
+
+#[test]
+fn tournament_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let mut agents = register_many(&mut h, 1);
+    assert_error(
+        h.send(ix::run_tournament_round(authority, 1)),
+        SaeaError::InvalidTournamentEntrants,
+    );
+
+    agents.extend(register_many(&mut h, 3));
+    h.ok(ix::run_tournament_round(authority, 1));
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            1,
+            agents[0],
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::WrongRoundKind,
+    );
+    assert_error(
+        h.send(ix::simulate_fitness(GENOME.to_vec(), 1, false, false)),
+        SaeaError::WrongRoundKind,
+    );
+
+    let (a, b) = h.round(1).pairing(0).unwrap();
+    assert_error(
+        h.send(ix::play_match(
+            authority,
+            1,
+            0,
+            b,
+            a,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::PairingMismatch,
+    );
+    h.ok(ix::play_match(
+        authority,
+        1,
+        0,
+        a,
+        b,
+        ix::ScoreAccounts::default(),
+    ));
+    assert_error(
+        h.send(ix::play_match(
+            authority,
+            1,
+            0,
+            a,
+            b,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::AlreadyParticipated,
+    );
+    let (c, d) = h.round(1).pairing(1).unwrap();
+    h.ok(ix::play_match(
+        authority,
+        1,
+        1,
+        c,
+        d,
+        ix::ScoreAccounts::default(),
+    ));
+    h.ok(ix::complete_round(authority, 1));
+    assert_error(
+        h.send(ix::update_ratings(authority, 1, a, &[b])),
+        SaeaError::WrongRoundKind,
+    );
+
+    let solo = h.start_round();
+    assert_error(
+        h.send(ix::play_match(
+            authority,
+            solo,
+            0,
+            a,
+            b,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::WrongRoundKind,
+    );
+}
+
+#[test]
+fn batch_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let agents = register_many(&mut h, 3);
+    let open = h.start_round();
+    assert_error(
+        h.send(ix::update_ratings(
+            authority,
+            open,
+            agents[0],
+            &agents[1..2],
+        )),
+        SaeaError::RoundNotComplete,
+    );
+    assert_error(
+        h.send(ix::rank_pareto(authority, open, &agents)),
+        SaeaError::RoundNotComplete,
+    );
+    for agent in &agents[..2] {
+        h.score(open, *agent);
+    }
+    h.ok(ix::complete_round(authority, open));
+
+    assert_error(
+        h.send(ix::update_ratings(authority, open, agents[0], &[])),
+        SaeaError::InvalidRatingOpponents,
+    );
+    assert_error(
+        h.send(ix::update_ratings(authority, open, agents[0], &agents[2..])),
+        SaeaError::InvalidRatingOpponents,
+    );
+    assert_error(
+        h.send(ix::update_ratings(authority, open, agents[2], &agents[..1])),
+        SaeaError::AgentNotInRound,
+    );
+    assert_error(
+        h.send(ix::rank_pareto(authority, open, &agents[..1])),
+        SaeaError::InvalidParetoBatch,
+    );
+    assert_error(
+        h.send(ix::rank_pareto(authority, open, &agents)),
+        SaeaError::InvalidParetoBatch,
+    );
+    assert_error(
+        h.send(ix::rank_pareto(authority, open, &[agents[0], agents[2]])),
+        SaeaError::AgentNotInRound,
+    );
+
+    h.ok(ix::update_ratings(
+        authority,
+        open,
+        agents[0],
+        &agents[1..2],
+    ));
+    assert_error(
+        h.send(ix::update_ratings(
+            authority,
+            open,
+            agents[0],
+            &agents[1..2],
+        )),
+        SaeaError::AlreadyRated,
+    );
+}
+
+#[test]
+fn ratings_close_with_the_round() {
+    let mut h = Harness::with_threshold(u64::MAX);
+    let authority = h.authority.pubkey();
+    let agents = register_many(&mut h, 4);
+    let played = h.play_round(&agents);
+    let opponents = h.rating_opponents(played, agents[0]);
+    h.ok(ix::update_ratings(authority, played, agents[0], &opponents));
+
+    // A pruned scored agent would leave its neighbours with new opponents.
+    unwrap_ok(h.prune(agents[3]));
+    assert_eq!(h.round(played).agents_removed, 1);
+    let opponents = h.rating_opponents(played, agents[1]);
+    assert_error(
+        h.send(ix::update_ratings(authority, played, agents[1], &opponents)),
+        SaeaError::RatingsClosed,
+    );
+
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let agents = register_many(&mut h, 3);
+    let played = h.play_round(&agents);
+    let opponents = h.rating_opponents(played, agents[0]);
+    h.start_round();
+    assert_error(
+        h.send(ix::update_ratings(authority, played, agents[0], &opponents)),
+        SaeaError::RatingsClosed,
+    );
+}
+
+#[test]
+fn island_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::configure_islands(
+        authority,
+        2,
+        2,
+        MigrationTopology::Ring,
+        1,
+    ));
+    let agents = register_many(&mut h, 4);
+
+    assert_error(
+        h.send(ix::run_island_round(authority, 1, 2)),
+        SaeaError::InvalidIsland,
+    );
+    h.ok(ix::run_island_round(authority, 1, 0));
+    let outsider = agents
+        .iter()
+        .copied()
+        .find(|a| h.agent(a).island == 1)
+        .unwrap();
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            1,
+            outsider,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::WrongIsland,
+    );
+
+    // Nothing is scored yet, so each island sends its lowest key.
+    let migrants: Vec<_> = (0..2)
+        .map(|island| {
+            agents
+                .iter()
+                .copied()
+                .filter(|a| h.agent(a).island == island)
+                .min()
+                .unwrap()
+        })
+        .collect();
+    assert_error(
+        h.send(ix::migrate(authority, None, &migrants)),
+        SaeaError::MigrationNotDue,
+    );
+    h.ok(ix::complete_round(authority, 1));
+    h.advance_generation();
+    h.advance_generation();
+    let stayer = agents
+        .iter()
+        .copied()
+        .find(|a| !migrants.contains(a))
+        .unwrap();
+    assert_error(
+        h.send(ix::migrate(
+            authority,
+            None,
+            &[migrants[0], migrants[1], stayer],
+        )),
+        SaeaError::TooManyMigrants,
+    );
+    assert_error(
+        h.send(ix::migrate(authority, None, &[])),
+        SaeaError::MigrationAgentsIncomplete,
+    );
+    assert_error(
+        h.send(ix::migrate(authority, None, &migrants[..1])),
+        SaeaError::MigrationAgentsIncomplete,
+    );
+    assert_error(
+        h.send(ix::migrate(authority, None, &[migrants[0], migrants[0]])),
+        SaeaError::MigrantNotFittest,
+    );
+    assert_error(
+        h.send(ix::migrate(authority, None, &[migrants[0], stayer])),
+        SaeaError::MigrantNotFittest,
+    );
+}
+
+#[test]
+fn migrants_must_be_fittest() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::configure_islands(
+        authority,
+        2,
+        1,
+        MigrationTopology::Random,
+        1,
+    ));
+    // Islands are dealt round-robin, so `weak` and `strong` share island 0.
+    let (_, weak) = h.register(&[0]);
+    let (_, other) = h.register(&GENOME);
+    let (_, strong) = h.register(&GENOME);
+    let round_number = h.play_round(&[weak, other, strong]);
+
+    assert_error(
+        h.send(ix::migrate(authority, None, &[strong, other])),
+        SaeaError::RoundRequired,
+    );
+    assert_error(
+        h.send(ix::migrate(authority, Some(round_number), &[weak, other])),
+        SaeaError::MigrantNotFittest,
+    );
+    h.ok(ix::migrate(authority, Some(round_number), &[strong, other]));
+    assert_eq!(h.agent(&strong).island, 1);
+    assert_eq!(h.agent(&other).island, 0);
+    assert_eq!(h.agent(&weak).island, 0);
+}
+
+#[test]
+fn island_depletion_on_migration() {
+    let mut h = Harness::with_threshold(u64::MAX);
+    let authority = h.authority.pubkey();
+    h.ok(ix::configure_islands(
+        authority,
+        3,
+        1,
+        MigrationTopology::Random,
+        1,
+    ));
+    let agents = register_many(&mut h, 3);
+    // Each island holds one agent and sends it; a draw that leaves an
+    // island without arrivals empties it.
+    let depleting = (0..16).find_map(|_| {
+        let round_number = h.play_round(&agents);
+        h.advance_generation();
+        let arena = h.arena();
+        let seed = h.round(round_number).seed;
+        let reached: Vec<u8> = (0..3)
+            .map(|island| arena.migration_destination(island, &seed))
+            .collect();
+        (0..3)
+            .any(|island| !reached.contains(&island))
+            .then_some(round_number)
+    });
+    let round_number = depleting.expect("no draw left an island without arrivals");
+    assert_error(
+        h.send(ix::migrate(authority, Some(round_number), &agents)),
+        SaeaError::IslandDepleted,
+    );
+    assert_error(h.prune(agents[0]), SaeaError::IslandDepleted);
+}
+
+#[test]
+fn speciation_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_species(
+        authority,
+        GenomeDistance::Hamming,
+        4,
+    ));
+    let agents = register_many(&mut h, 2);
+
+    let first = h.start_round();
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            first,
+            agents[0],
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::SpeciesRequired,
+    );
+    let with_species = ix::ScoreAccounts {
+        species: true,
+        ..Default::default()
+    };
+    for agent in &agents {
+        h.ok(ix::score_agent(authority, first, *agent, with_species));
+    }
+    assert_error(
+        h.send(ix::share_fitness(authority, first, &agents)),
+        SaeaError::RoundNotComplete,
+    );
+    h.ok(ix::complete_round(authority, first));
+
+    let second = h.start_round();
+    h.ok(ix::score_agent(authority, second, agents[0], with_species));
+    h.ok(ix::complete_round(authority, second));
+    assert_error(
+        h.send(ix::share_fitness(authority, first, &agents)),
+        SaeaError::FitnessSharingClosed,
+    );
+    assert_error(
+        h.send(ix::share_fitness(authority, second, &agents)),
+        SaeaError::AgentNotInRound,
+    );
+}
+
+#[test]
+fn novelty_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_novelty_archive(authority, 1, 0, 1));
+    h.ok(ix::set_fitness_mode(authority, FitnessMode::Novelty));
+    let (_, agent) = h.register(&GENOME);
+    let round_number = h.start_round();
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            round_number,
+            agent,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::NoveltyArchiveRequired,
+    );
+}
+
+#[test]
+fn hall_of_fame_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_hall_of_fame(authority));
+    let (_, agent) = h.register(&GENOME);
+    let round_number = h.start_round();
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            round_number,
+            agent,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::HallOfFameRequired,
+    );
+
+    let (_, opponent) = h.register(&GENOME);
+    let round_number = h.arena().current_round + 1;
+    h.ok(ix::run_tournament_round(authority, round_number));
+    let (a, b) = h.round(round_number).pairing(0).unwrap();
+    assert!([a, b].contains(&opponent));
+    assert_error(
+        h.send(ix::play_match(
+            authority,
+            round_number,
+            0,
+            a,
+            b,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::HallOfFameRequired,
+    );
+}
+
+#[test]
+fn completion_policy_errors() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let agents = register_many(&mut h, 2);
+
+    h.ok(ix::configure_completion(
+        authority,
+        CompletionPolicy::Strict,
+        100,
+    ));
+    let round_number = h.start_round();
+    h.score(round_number, agents[0]);
+    assert_error(
+        h.send(ix::complete_round(authority, round_number)),
+        SaeaError::RoundQuorumNotMet,
+    );
+    // The policy is fixed when the round starts.
+    h.ok(ix::configure_completion(
+        authority,
+        CompletionPolicy::Quorum,
+        50,
+    ));
+    assert_error(
+        h.send(ix::complete_round(authority, round_number)),
+        SaeaError::RoundQuorumNotMet,
+    );
+
+    // Agents registered mid-round are neither expected nor scorable.
+    let (_, late) = h.register(&GENOME);
+    assert_error(
+        h.send(ix::score_agent(
+            authority,
+            round_number,
+            late,
+            ix::ScoreAccounts::default(),
+        )),
+        SaeaError::AgentNotEligible,
+    );
+    h.score(round_number, agents[1]);
+    h.ok(ix::complete_round(authority, round_number));
+
+    let round_number = h.start_round();
+    assert_eq!(h.round(round_number).eligible_agents, 3);
+    h.score(round_number, late);
+    assert_error(
+        h.send(ix::complete_round(authority, round_number)),
+        SaeaError::RoundQuorumNotMet,
+    );
+    h.score(round_number, agents[0]);
+    h.ok(ix::complete_round(authority, round_number));
+}
+
+#[test]
+fn pruning_shrinks_the_open_round() {
+    let mut h = Harness::with_threshold(u64::MAX);
+    let authority = h.authority.pubkey();
+    let agents = register_many(&mut h, 3);
+    h.play_round(&agents);
+    h.ok(ix::configure_completion(
+        authority,
+        CompletionPolicy::Strict,
+        100,
+    ));
+
+    let round_number = h.start_round();
+    assert_error(
+        h.send(ix::prune_agent(
+            authority,
+            agents[0],
+            h.agent(&agents[0]).owner,
+            None,
+        )),
+        SaeaError::RoundRequired,
+    );
+    h.score(round_number, agents[0]);
+    unwrap_ok(h.prune(agents[0]));
+    unwrap_ok(h.prune(agents[1]));
+    assert_eq!(h.round(round_number).eligible_agents, 1);
+    h.score(round_number, agents[2]);
+    h.ok(ix::complete_round(authority, round_number));
+
+    // Nor is the odd tournament entrant out.
+    let entrants = [vec![agents[2]], register_many(&mut h, 2)].concat();
+    let round_number = round_number + 1;
+    h.ok(ix::run_tournament_round(authority, round_number));
+    let round = h.round(round_number);
+    assert_eq!(round.eligible_agents, 2);
+    let sitting_out = entrants
+        .into_iter()
+        .find(|a| !round.pairings.contains(a))
+        .unwrap();
+    unwrap_ok(h.prune(sitting_out));
+    assert_eq!(h.round(round_number).eligible_agents, 2);
+}
+
+#[test]
+fn simulate_fitness_errors() {
+    let mut h = Harness::new();
+    let round_number = h.start_round();
+    assert_error(
+        h.send(ix::simulate_fitness(vec![], round_number, false, false)),
+        SaeaError::InvalidGenomeLength,
+    );
+    assert_error(
+        h.send(ix::simulate_fitness(
+            vec![1; 33],
+            round_number,
+            false,
+            false,
+        )),
+        SaeaError::InvalidGenomeLength,
+    );
+}
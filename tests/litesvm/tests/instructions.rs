@@ -0,0 +1,610 @@
+//! Every instruction's success path, its events and compute-unit ceiling.
+
+mod common;
+
+use common::*;
+use saea::instructions::{compute_fitness, draw_pairings, mutation_seed, self_adaptive_mutate};
+use saea::state::{
+    BenchmarkModel, CompletionPolicy, FitnessMode, GenomeDistance, MigrationTopology,
+    SelectionMetric, MAX_GENOME_LEN, MAX_TOURNAMENT_ENTRANTS,
+};
+use saea_client::events::SaeaEvent;
+use saea_client::{instructions as ix, pda};
+use solana_signer::Signer;
+
+const GENOMES: [[u8; 16]; 4] = [
+    [
+        10, 250, 30, 200, 50, 180, 70, 160, 90, 140, 110, 120, 130, 100, 150, 80,
+    ],
+    [128; 16],
+    [
+        0, 255, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255, 0, 255,
+    ],
+    [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+];
+
+#[test]
+fn initialize_arena() {
+    let mut h = Harness::empty();
+    let meta = h.ok(ix::initialize_arena(
+        h.authority.pubkey(),
+        MAX_AGENTS,
+        MIN_FITNESS_THRESHOLD,
+        MUTATION_RATE_BPS,
+    ));
+    assert_compute_units(&meta, CU_INITIALIZE_ARENA, "initialize_arena");
+
+    let arena = h.arena();
+    assert_eq!(arena.authority, h.authority.pubkey());
+    assert_eq!(arena.max_agents, MAX_AGENTS);
+    assert_eq!(arena.min_fitness_threshold, MIN_FITNESS_THRESHOLD);
+    assert_eq!(arena.current_round, 0);
+    assert!(arena.is_active);
+    assert!(!arena.adaptive_mutation);
+    assert!(h.population().entries.is_empty());
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::ArenaInitialized(e)] if e.authority == h.authority.pubkey() && e.max_agents == MAX_AGENTS
+    ));
+}
+
+#[test]
+fn register_agent() {
+    let mut h = Harness::new();
+    let owner = h.funded_keypair();
+    let meta = h.ok_as(
+        ix::register_agent(owner.pubkey(), 0, GENOMES[0].to_vec()),
+        &owner,
+    );
+    assert_compute_units(&meta, CU_REGISTER_AGENT, "register_agent");
+
+    let key = pda::agent(&pda::arena().0, &owner.pubkey(), 0).0;
+    let agent = h.agent(&key);
+    assert_eq!(agent.owner, owner.pubkey());
+    assert_eq!(agent.genome, GENOMES[0]);
+    assert!(agent.is_active);
+    assert_eq!(h.arena().active_agents, 1);
+    assert_eq!(h.population().entries[0].agent, key);
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::AgentRegistered(e)] if e.agent == key && e.owner == owner.pubkey()
+    ));
+}
+
+#[test]
+fn round_lifecycle_scores_with_compute_fitness() {
+    let mut h = Harness::new();
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+
+    let round_number = h.arena().current_round + 1;
+    let meta = h.ok(ix::run_round(h.authority.pubkey(), round_number));
+    assert_compute_units(&meta, CU_RUN_ROUND, "run_round");
+    let round = h.round(round_number);
+    assert_eq!(round.eligible_agents, agents.len() as u64);
+    assert!(!round.is_complete);
+
+    for (agent, genome) in agents.iter().zip(&GENOMES) {
+        let meta = h.score(round_number, *agent);
+        assert_compute_units(&meta, CU_SCORE_AGENT, "score_agent");
+        let expected = compute_fitness(genome, &round.seed);
+        assert_eq!(h.agent(agent).fitness, expected);
+        assert!(matches!(
+            events(&meta).as_slice(),
+            [SaeaEvent::AgentScored(e)] if e.agent == *agent && e.fitness == expected && e.breakdown.total() == Some(expected)
+        ));
+    }
+
+    let meta = h.ok(ix::complete_round(h.authority.pubkey(), round_number));
+    assert_compute_units(&meta, CU_COMPLETE_ROUND, "complete_round");
+    let round = h.round(round_number);
+    assert!(round.is_complete);
+    assert_eq!(round.participants, agents.len() as u64);
+    let completed = events(&meta)
+        .into_iter()
+        .find_map(|e| match e {
+            SaeaEvent::RoundCompleted(e) => Some(e),
+            _ => None,
+        })
+        .expect("RoundCompleted emitted");
+    assert_eq!(completed.best_fitness, round.best_fitness);
+    assert_eq!(completed.average_fitness, round.total_fitness / 4);
+    assert_eq!(completed.agents_skipped, 0);
+    assert!(h
+        .population()
+        .entries
+        .iter()
+        .all(|e| e.last_round == round_number));
+}
+
+#[test]
+fn scoring_cost_is_free_of_per_gene_hashing() {
+    // 31 extra genes must cost less than the SHA-256 syscall base fee
+    // (85 CU) the old per-gene target and direction hashing paid 62 times.
+    let mut h = Harness::new();
+    let short = h.register(&[0x5a]).1;
+    let long = h.register(&[0x5a; MAX_GENOME_LEN]).1;
+    let round_number = h.start_round();
+    let short_units = h.score(round_number, short).compute_units_consumed;
+    let long_units = h.score(round_number, long).compute_units_consumed;
+    assert!(
+        long_units.saturating_sub(short_units) < 62 * 85,
+        "{MAX_GENOME_LEN} genes cost {long_units} CU, 1 gene {short_units} CU"
+    );
+}
+
+#[test]
+fn submit_genome() {
+    let mut h = Harness::new();
+    let (owner, agent) = h.register(&GENOMES[0]);
+    let (_, parent) = h.register(&GENOMES[1]);
+    let meta = h.ok_as(
+        ix::submit_genome(owner.pubkey(), agent, GENOMES[2].to_vec(), parent),
+        &owner,
+    );
+    assert_compute_units(&meta, CU_SUBMIT_GENOME, "submit_genome");
+
+    let account = h.agent(&agent);
+    assert_eq!(account.genome, GENOMES[2]);
+    assert_eq!(account.parent, parent);
+    assert_eq!(account.mutation_count, 1);
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::GenomeSubmitted(e)] if e.agent == agent && e.mutation_count == 1 && e.parent == parent
+    ));
+}
+
+#[test]
+fn prune_agent() {
+    let mut h = Harness::with_threshold(u64::MAX);
+    let (owner, weak) = h.register(&[0]);
+    let (_, strong) = h.register(&GENOMES[0]);
+    h.play_round(&[weak, strong]);
+
+    let (owner, population_key) = (owner.pubkey(), pda::population(&pda::arena().0).0);
+    let balance = |h: &Harness, key| h.svm.get_balance(key).unwrap();
+    let (registry_before, owner_before) = (balance(&h, &population_key), balance(&h, &owner));
+    let meta = unwrap_ok(h.prune(weak));
+    assert_compute_units(&meta, CU_PRUNE_AGENT, "prune_agent");
+    // The owner paid for the registry entry, so the owner gets its rent back.
+    let refund = registry_before - balance(&h, &population_key);
+    assert!(refund > 0);
+    assert_eq!(balance(&h, &owner), owner_before + refund);
+    assert!(!h.agent(&weak).is_active);
+    assert_eq!(h.arena().active_agents, 1);
+    let population = h.population();
+    assert_eq!(population.entries.len(), 1);
+    assert_eq!(population.entries[0].agent, strong);
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::AgentPruned(e)] if e.agent == weak
+    ));
+}
+
+#[test]
+fn advance_generation() {
+    let mut h = Harness::new();
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+    h.play_round(&agents);
+
+    let meta = h.advance_generation();
+    assert_compute_units(&meta, CU_ADVANCE_GENERATION, "advance_generation");
+    assert_eq!(h.arena().current_generation, 1);
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::GenerationAdvanced(e)] if e.old_generation == 0 && e.new_generation == 1 && e.active_agents == 4
+    ));
+    let generation: saea::state::Generation = h.account(&pda::generation(&pda::arena().0, 0).0);
+    assert_eq!(generation.rounds_run, 1);
+    assert_eq!(generation.agents_born, 4);
+}
+
+#[test]
+fn benchmark_scoring() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::create_benchmark(
+        authority,
+        BenchmarkModel::DecisionTable,
+        1,
+        2,
+        2,
+    ));
+    // Feature 0 -> class 0, feature 200 -> class 1.
+    h.ok(ix::upload_benchmark_chunk(authority, 0, vec![0, 0]));
+    h.ok(ix::upload_benchmark_chunk(authority, 2, vec![200, 1]));
+    let meta = h.ok(ix::freeze_benchmark(authority));
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::BenchmarkFrozen(e)] if e.case_count == 2
+    ));
+
+    let meta = h.ok(ix::set_fitness_mode(authority, FitnessMode::Benchmark));
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::FitnessModeChanged(e)] if e.new_mode == FitnessMode::Benchmark
+    ));
+
+    let (_, agent) = h.register(&[0, 1]);
+    let round_number = h.start_round();
+    h.ok(ix::score_agent(
+        authority,
+        round_number,
+        agent,
+        ix::ScoreAccounts {
+            benchmark: true,
+            ..Default::default()
+        },
+    ));
+    assert!(h.agent(&agent).fitness > 0);
+}
+
+#[test]
+fn configure_instructions() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+
+    let meta = h.ok(ix::configure_selection(
+        authority,
+        SelectionMetric::Windowed,
+        4,
+        3,
+    ));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_selection");
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::SelectionConfigured(e)] if e.metric == SelectionMetric::Windowed && e.window == 3
+    ));
+
+    let meta = h.ok(ix::configure_histogram(authority, 1000, 250));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_histogram");
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::HistogramConfigured(e)] if e.base == 1000 && e.bucket_width == 250
+    ));
+
+    let meta = h.ok(ix::configure_mutation(authority, true, 500, 5000, 3));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_mutation");
+    let meta = h.ok(ix::configure_pareto(authority, true, 1));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_pareto");
+    let meta = h.ok(ix::configure_completion(
+        authority,
+        CompletionPolicy::Quorum,
+        75,
+    ));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_completion");
+
+    let arena = h.arena();
+    assert_eq!(arena.selection_metric, SelectionMetric::Windowed);
+    assert_eq!(arena.histogram_bucket_width, 250);
+    assert!(arena.adaptive_mutation);
+    assert_eq!(
+        (arena.mutation_rate_min_bps, arena.mutation_rate_max_bps),
+        (500, 5000)
+    );
+    assert!(arena.pareto_selection);
+    assert_eq!(arena.completion_policy, CompletionPolicy::Quorum);
+    assert_eq!(arena.completion_quorum_pct, 75);
+}
+
+#[test]
+fn hall_of_fame_records_best_scores() {
+    let mut h = Harness::new();
+    h.ok(ix::initialize_hall_of_fame(h.authority.pubkey()));
+    assert!(h.arena().hall_of_fame_enabled);
+    let (_, agent) = h.register(&GENOMES[0]);
+    let round_number = h.start_round();
+    let meta = h.ok(ix::score_agent(
+        h.authority.pubkey(),
+        round_number,
+        agent,
+        ix::ScoreAccounts {
+            hall_of_fame: true,
+            ..Default::default()
+        },
+    ));
+    assert!(events(&meta)
+        .iter()
+        .any(|e| matches!(e, SaeaEvent::HallOfFameUpdated(e) if e.agent == agent && e.rank == 0)));
+}
+
+#[test]
+fn self_adaptive_mutation() {
+    let mut h = Harness::new();
+    let meta = h.ok(ix::configure_strategy_genes(h.authority.pubkey(), 2));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_strategy_genes");
+    let (owner, agent) = h.register(&GENOMES[0]);
+    let round_number = h.start_round();
+    let before = h.agent(&agent).genome;
+    h.ok_as(
+        ix::mutate_genome(owner.pubkey(), agent, round_number),
+        &owner,
+    );
+    let after = h.agent(&agent);
+    assert_eq!(after.mutation_count, 1);
+    // The round seed, not the owner, decides the mutation.
+    let seed = mutation_seed(&h.round(round_number).seed, &agent, 0);
+    assert_eq!(after.genome, self_adaptive_mutate(&before, 2, &seed));
+}
+
+#[test]
+fn tournament_round_and_ratings() {
+    let mut h = Harness::new();
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+    let round_number = h.arena().current_round + 1;
+    let meta = h.ok(ix::run_tournament_round(h.authority.pubkey(), round_number));
+    assert_compute_units(&meta, CU_RUN_TOURNAMENT_ROUND, "run_tournament_round");
+
+    let round = h.round(round_number);
+    assert_eq!(round.pairings.len(), 4);
+    assert!(agents.iter().all(|a| round.pairings.contains(a)));
+    for match_index in 0..2u16 {
+        let (a, b) = round.pairing(match_index).unwrap();
+        let meta = h.ok(ix::play_match(
+            h.authority.pubkey(),
+            round_number,
+            match_index,
+            a,
+            b,
+            ix::ScoreAccounts::default(),
+        ));
+        assert_compute_units(&meta, CU_PLAY_MATCH, "play_match");
+        let events = events(&meta);
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, SaeaEvent::MatchPlayed(e) if e.match_index == match_index)));
+        let rated = events
+            .iter()
+            .filter(|e| matches!(e, SaeaEvent::RatingUpdated(e) if e.agent == a || e.agent == b))
+            .count();
+        assert_eq!(rated, 2);
+    }
+    h.ok(ix::complete_round(h.authority.pubkey(), round_number));
+    assert!(agents
+        .iter()
+        .all(|a| h.agent(a).rated_round == round_number));
+}
+
+#[test]
+fn tournament_draws_at_most_max_entrants() {
+    let mut h = Harness::empty();
+    let authority = h.authority.pubkey();
+    let registered = MAX_TOURNAMENT_ENTRANTS + 3;
+    h.ok(ix::initialize_arena(
+        authority,
+        registered as u64,
+        MIN_FITNESS_THRESHOLD,
+        MUTATION_RATE_BPS,
+    ));
+    let agents: Vec<_> = (0..registered)
+        .map(|i| {
+            let mut genome = GENOMES[0];
+            genome[0] = i as u8;
+            h.register(&genome).1
+        })
+        .collect();
+
+    let round_number = h.arena().current_round + 1;
+    h.ok(ix::run_tournament_round(authority, round_number));
+    let round = h.round(round_number);
+    assert_eq!(round.pairings.len(), MAX_TOURNAMENT_ENTRANTS);
+    assert_eq!(round.eligible_agents, MAX_TOURNAMENT_ENTRANTS as u64);
+    assert!(round.pairings.iter().all(|a| agents.contains(a)));
+    let entrants = h.population().entries.iter().map(|e| e.agent).collect();
+    assert_eq!(round.pairings, draw_pairings(entrants, &round.seed));
+}
+
+#[test]
+fn tournament_scores_enter_the_hall_of_fame() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_hall_of_fame(authority));
+    for genome in &GENOMES[..2] {
+        h.register(genome);
+    }
+    let round_number = h.arena().current_round + 1;
+    h.ok(ix::run_tournament_round(authority, round_number));
+    let (a, b) = h.round(round_number).pairing(0).unwrap();
+    let meta = h.ok(ix::play_match(
+        authority,
+        round_number,
+        0,
+        a,
+        b,
+        ix::ScoreAccounts {
+            hall_of_fame: true,
+            ..Default::default()
+        },
+    ));
+    let ranked: Vec<_> = events(&meta)
+        .into_iter()
+        .filter_map(|e| match e {
+            SaeaEvent::HallOfFameUpdated(e) => Some(e.agent),
+            _ => None,
+        })
+        .collect();
+    assert!(ranked.contains(&a) && ranked.contains(&b));
+}
+
+#[test]
+fn solo_round_ratings() {
+    let mut h = Harness::new();
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+    let round_number = h.play_round(&agents);
+
+    for agent in &agents {
+        let opponents = h.rating_opponents(round_number, *agent);
+        assert_eq!(opponents.len(), 2);
+        let meta = h.ok(ix::update_ratings(
+            h.authority.pubkey(),
+            round_number,
+            *agent,
+            &opponents,
+        ));
+        assert_compute_units(&meta, CU_UPDATE_RATINGS, "update_ratings");
+        assert!(matches!(
+            events(&meta).as_slice(),
+            [SaeaEvent::RatingUpdated(e)] if e.agent == *agent && e.round_number == round_number
+        ));
+    }
+    let total: u64 = agents.iter().map(|a| h.agent(a).rating).sum();
+    assert!(total.abs_diff(4 * saea::state::INITIAL_RATING) <= 4);
+}
+
+#[test]
+fn islands_and_migration() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let meta = h.ok(ix::configure_islands(
+        authority,
+        2,
+        1,
+        MigrationTopology::Ring,
+        1,
+    ));
+    assert_compute_units(&meta, CU_CONFIGURE, "configure_islands");
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+    assert_eq!(h.arena().island_populations[..2], [2, 2]);
+
+    let round_number = h.arena().current_round + 1;
+    h.ok(ix::run_island_round(authority, round_number, 0));
+    let island_zero: Vec<_> = agents
+        .iter()
+        .copied()
+        .filter(|a| h.agent(a).island == 0)
+        .collect();
+    for agent in &island_zero {
+        h.score(round_number, *agent);
+    }
+    h.ok(ix::complete_round(authority, round_number));
+    h.advance_generation();
+
+    // Ring migration needs no round. Each island sends its fittest agent,
+    // ties going to the lower key; island 1 is unscored.
+    let from_zero = *island_zero
+        .iter()
+        .max_by_key(|a| (h.agent(a).fitness, std::cmp::Reverse(**a)))
+        .unwrap();
+    let from_one = *agents
+        .iter()
+        .filter(|a| !island_zero.contains(a))
+        .min()
+        .unwrap();
+    let meta = h.ok(ix::migrate(authority, None, &[from_zero, from_one]));
+    assert_compute_units(&meta, CU_MIGRATE, "migrate");
+    assert_eq!(h.agent(&from_zero).island, 1);
+    assert_eq!(h.agent(&from_one).island, 0);
+    assert_eq!(h.arena().island_populations[..2], [2, 2]);
+    for entry in h.population().entries {
+        assert_eq!(entry.island, h.agent(&entry.agent).island);
+    }
+    assert!(matches!(
+        events(&meta).as_slice(),
+        [SaeaEvent::AgentMigrated(a), SaeaEvent::AgentMigrated(b)]
+            if a.agent == from_zero && a.to_island == 1 && b.agent == from_one && b.to_island == 0
+    ));
+}
+
+#[test]
+fn speciation_and_fitness_sharing() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_species(authority, GenomeDistance::L1, 64));
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+
+    let round_number = h.start_round();
+    for agent in &agents {
+        h.ok(ix::score_agent(
+            authority,
+            round_number,
+            *agent,
+            ix::ScoreAccounts {
+                species: true,
+                ..Default::default()
+            },
+        ));
+    }
+    h.ok(ix::complete_round(authority, round_number));
+    let meta = h.ok(ix::share_fitness(authority, round_number, &agents));
+    assert_compute_units(&meta, CU_SHARE_FITNESS, "share_fitness");
+
+    for agent in &agents {
+        let account = h.agent(agent);
+        assert_ne!(account.species, 0);
+        assert!(account.shared_fitness <= account.fitness);
+        assert_eq!(account.shared_round, round_number);
+    }
+}
+
+#[test]
+fn novelty_scoring() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    h.ok(ix::initialize_novelty_archive(authority, 1, 0, 1));
+    h.ok(ix::set_fitness_mode(authority, FitnessMode::Novelty));
+    let (_, agent) = h.register(&GENOMES[0]);
+
+    let round_number = h.start_round();
+    let meta = h.ok(ix::score_agent(
+        authority,
+        round_number,
+        agent,
+        ix::ScoreAccounts {
+            novelty_archive: true,
+            ..Default::default()
+        },
+    ));
+    assert!(events(&meta)
+        .iter()
+        .any(|e| matches!(e, SaeaEvent::NoveltyArchived(e) if e.agent == agent)));
+}
+
+#[test]
+fn fitness_mode_is_fixed_per_round() {
+    let mut h = Harness::new();
+    let authority = h.authority.pubkey();
+    let (_, agent) = h.register(&GENOMES[0]);
+    let round_number = h.start_round();
+
+    h.ok(ix::initialize_novelty_archive(authority, 1, 0, 1));
+    h.ok(ix::set_fitness_mode(authority, FitnessMode::Novelty));
+    assert_eq!(h.round(round_number).fitness_mode, FitnessMode::Seeded);
+
+    h.score(round_number, agent);
+    let seed = h.round(round_number).seed;
+    assert_eq!(h.agent(&agent).fitness, compute_fitness(&GENOMES[0], &seed));
+}
+
+#[test]
+fn pareto_ranking() {
+    let mut h = Harness::new();
+    let agents: Vec<_> = GENOMES.iter().map(|g| h.register(g).1).collect();
+    let round_number = h.play_round(&agents);
+
+    let meta = h.ok(ix::rank_pareto(h.authority.pubkey(), round_number, &agents));
+    assert_compute_units(&meta, CU_RANK_PARETO, "rank_pareto");
+    let ranked = events(&meta)
+        .into_iter()
+        .filter(|e| matches!(e, SaeaEvent::ParetoRanked(_)))
+        .count();
+    assert_eq!(ranked, agents.len());
+    assert!(agents.iter().any(|a| h.agent(a).pareto_front == 0));
+}
+
+#[test]
+fn simulate_fitness_matches_score() {
+    let mut h = Harness::new();
+    let (_, agent) = h.register(&GENOMES[0]);
+    let round_number = h.start_round();
+
+    let meta = h.ok(ix::simulate_fitness(
+        GENOMES[0].to_vec(),
+        round_number,
+        false,
+        false,
+    ));
+    let simulation: saea::instructions::FitnessSimulation =
+        anchor_lang::AnchorDeserialize::deserialize(&mut meta.return_data.data.as_slice()).unwrap();
+
+    h.score(round_number, agent);
+    assert_eq!(simulation.fitness, h.agent(&agent).fitness);
+}
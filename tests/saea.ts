@@ -13,6 +13,7 @@ describe("saea", () => {
 
   let arenaPda: PublicKey;
   let arenaBump: number;
+  let populationPda: PublicKey;
   let agentPdas: PublicKey[] = [];
 
   before(async () => {
@@ -20,6 +21,10 @@ describe("saea", () => {
       [Buffer.from("arena")],
       program.programId
     );
+    [populationPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("population"), arenaPda.toBuffer()],
+      program.programId
+    );
   });
 
   function getAgentPda(ownerKey: PublicKey, index: number): [PublicKey, number] {
@@ -31,6 +36,15 @@ describe("saea", () => {
     );
   }
 
+  function getGenerationPda(generationNumber: number): [PublicKey, number] {
+    const genBuf = Buffer.alloc(8);
+    genBuf.writeBigUInt64LE(BigInt(generationNumber));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("generation"), arenaPda.toBuffer(), genBuf],
+      program.programId
+    );
+  }
+
   function getRoundPda(roundNumber: number): [PublicKey, number] {
     const roundBuf = Buffer.alloc(8);
     roundBuf.writeBigUInt64LE(BigInt(roundNumber));
@@ -49,6 +63,7 @@ describe("saea", () => {
       )
       .accounts({
         arena: arenaPda,
+        population: populationPda,
         authority: authority.publicKey,
         systemProgram: SystemProgram.programId,
       } as any)
@@ -77,6 +92,7 @@ describe("saea", () => {
         .registerAgent(Buffer.from(genome))
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           agent: agentPda,
           owner: authority.publicKey,
           systemProgram: SystemProgram.programId,
@@ -93,6 +109,9 @@ describe("saea", () => {
     const arena = await (program.account as any).arena.fetch(arenaPda);
     expect(arena.totalAgents.toNumber()).to.equal(4);
     expect(arena.activeAgents.toNumber()).to.equal(4);
+
+    const population = await (program.account as any).population.fetch(populationPda);
+    expect(population.len).to.equal(4);
   });
 
   it("rejects invalid genome length", async () => {
@@ -104,6 +123,7 @@ describe("saea", () => {
         .registerAgent(emptyGenome)
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           agent: agentPda,
           owner: authority.publicKey,
           systemProgram: SystemProgram.programId,
@@ -142,6 +162,7 @@ describe("saea", () => {
         .scoreAgent()
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           round: roundPda,
           agent: agentPda,
           authority: authority.publicKey,
@@ -155,6 +176,7 @@ describe("saea", () => {
       .accounts({
         arena: arenaPda,
         round: roundPda,
+        population: populationPda,
         authority: authority.publicKey,
       } as any)
       .rpc();
@@ -182,6 +204,7 @@ describe("saea", () => {
         .scoreAgent()
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           round: roundPda,
           agent: agentPdas[0],
           authority: authority.publicKey,
@@ -234,6 +257,7 @@ describe("saea", () => {
         .scoreAgent()
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           round: roundPda,
           agent: agentPda,
           authority: authority.publicKey,
@@ -246,6 +270,7 @@ describe("saea", () => {
       .accounts({
         arena: arenaPda,
         round: roundPda,
+        population: populationPda,
         authority: authority.publicKey,
       } as any)
       .rpc();
@@ -256,35 +281,52 @@ describe("saea", () => {
   });
 
   it("advances generation", async () => {
+    const [generationPda] = getGenerationPda(1);
+
     await program.methods
       .advanceGeneration()
       .accounts({
         arena: arenaPda,
+        generation: generationPda,
         authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
       } as any)
       .rpc();
 
     const arena = await (program.account as any).arena.fetch(arenaPda);
     expect(arena.currentGeneration.toNumber()).to.equal(2);
+
+    const summary = await (program.account as any).generation.fetch(generationPda);
+    expect(summary.generationNumber.toNumber()).to.equal(1);
+    expect(summary.roundsRun.toNumber()).to.equal(2);
+    expect(summary.agentsBorn.toNumber()).to.equal(4);
+    expect(summary.genomesSubmitted.toNumber()).to.equal(1);
+    expect(summary.bestFitness.toNumber()).to.be.greaterThan(0);
+    expect(summary.previousHash.every((b: number) => b === 0)).to.be.true;
   });
 
   it("prunes low-fitness agent", async () => {
     const agents = await Promise.all(
       agentPdas.map(async (pda) => {
         const a = await (program.account as any).agentAccount.fetch(pda);
-        return { pda, fitness: a.fitness.toNumber() };
+        return { pda, owner: a.owner, fitness: a.fitness.toNumber() };
       })
     );
 
     agents.sort((a, b) => a.fitness - b.fitness);
     const weakest = agents[0];
+    const { currentRound } = await (program.account as any).arena.fetch(arenaPda);
+    const [roundPda] = getRoundPda(currentRound.toNumber());
 
     if (weakest.fitness < 100) {
       await program.methods
         .pruneAgent()
         .accounts({
           arena: arenaPda,
+          population: populationPda,
           agent: weakest.pda,
+          round: roundPda,
+          owner: weakest.owner,
           authority: authority.publicKey,
         } as any)
         .rpc();
@@ -301,7 +343,10 @@ describe("saea", () => {
           .pruneAgent()
           .accounts({
             arena: arenaPda,
+            population: populationPda,
             agent: weakest.pda,
+            round: roundPda,
+            owner: weakest.owner,
             authority: authority.publicKey,
           } as any)
           .rpc();
@@ -321,7 +366,9 @@ describe("saea", () => {
         .advanceGeneration()
         .accountsStrict({
           arena: arenaPda,
+          generation: getGenerationPda(2)[0],
           authority: unauthorized.publicKey,
+          systemProgram: SystemProgram.programId,
         })
         .signers([unauthorized])
         .rpc();
@@ -351,6 +398,7 @@ describe("saea", () => {
       .scoreAgent()
       .accounts({
         arena: arenaPda,
+        population: populationPda,
         round: roundPda3,
         agent: agentPdas[0],
         authority: authority.publicKey,
@@ -366,4 +414,59 @@ describe("saea", () => {
     expect(round.seed.length).to.equal(32);
     expect(round.seed.some((b: number) => b !== 0)).to.be.true;
   });
+
+  it("keeps per-gene scoring cost free of hashing", async () => {
+    // Gene targets are precomputed in run_round, so 31 extra genes must cost
+    // less than the SHA-256 syscall base fee (85 CU) the old per-gene
+    // target and direction hashing paid 62 times. Agents are only scored in
+    // rounds started after they registered, so both play round 4.
+    const lengths = [1, 32];
+    const agents: PublicKey[] = [];
+    for (const [offset, length] of lengths.entries()) {
+      const [agentPda] = getAgentPda(authority.publicKey, 4 + offset);
+      await program.methods
+        .registerAgent(Buffer.alloc(length, 0x5a))
+        .accounts({
+          arena: arenaPda,
+          population: populationPda,
+          agent: agentPda,
+          owner: authority.publicKey,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+      agents.push(agentPda);
+    }
+
+    const [roundPda4] = getRoundPda(4);
+    await program.methods
+      .runRound()
+      .accounts({
+        arena: arenaPda,
+        round: roundPda4,
+        authority: authority.publicKey,
+        systemProgram: SystemProgram.programId,
+      } as any)
+      .rpc();
+
+    const unitsByLength: Record<number, number> = {};
+    for (const [i, length] of lengths.entries()) {
+      const sig = await program.methods
+        .scoreAgent()
+        .accounts({
+          arena: arenaPda,
+          population: populationPda,
+          round: roundPda4,
+          agent: agents[i],
+          authority: authority.publicKey,
+        } as any)
+        .rpc({ commitment: "confirmed" });
+      const tx = await provider.connection.getTransaction(sig, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      unitsByLength[length] = tx!.meta!.computeUnitsConsumed!;
+    }
+
+    expect(unitsByLength[32] - unitsByLength[1]).to.be.lessThan(62 * 85);
+  });
 });